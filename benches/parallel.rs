@@ -0,0 +1,57 @@
+//! Latency of the per-counterparty loops in sign_stage2 (MtA) and sign_stage6
+//! (PDL proof verification), and of a batch of sign_stage2 sessions. Compare
+//!
+//!     cargo bench --bench parallel
+//!     cargo bench --bench parallel --features parallel
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use hello::*;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{keygen, run, Quorum};
+
+/// (threshold, share_count, quorum size)
+const CONFIGURATIONS: [(u16, u16, u16); 2] = [(2, 5, 3), (6, 10, 7)];
+
+const BATCH_SIZE: usize = 4;
+
+fn bench_parallel_stages(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(10);
+
+    for &(threshold, share_count, quorum_size) in CONFIGURATIONS.iter() {
+        let keys = keygen(threshold, share_count, false);
+        let parties: Vec<u16> = (1..=quorum_size).collect();
+        let quorum = Quorum::new(&keys, &parties);
+
+        let stage1 = run(&quorum.stage1_inputs(), sign_stage1);
+        let stage2_inputs = quorum.stage2_inputs(&stage1);
+        let stage2 = run(&stage2_inputs, sign_stage2);
+        let stage3 = run(&quorum.stage3_inputs(&stage1, &stage2), sign_stage3);
+        let stage4 = run(&quorum.stage4_inputs(&stage3), sign_stage4);
+        let stage5 = run(&quorum.stage5_inputs(&stage1, &stage2, &stage4), sign_stage5);
+        let stage6_inputs = quorum.stage6_inputs(&stage1, &stage3, &stage5);
+
+        let name = format!("{}-of-{}", quorum_size, share_count);
+        group.bench_function(format!("sign_stage2/{}", name), |b| {
+            b.iter(|| sign_stage2(&stage2_inputs[0]).unwrap())
+        });
+        group.bench_function(format!("sign_stage6/{}", name), |b| {
+            b.iter(|| sign_stage6(&stage6_inputs[0]).unwrap())
+        });
+        let batch = Secp256k1SignBatch {
+            sessions: vec![stage2_inputs[0].clone(); BATCH_SIZE],
+        };
+        group.bench_function(format!("sign_batch_stage2/{}", name), |b| {
+            b.iter(|| sign_batch(&batch, sign_stage2).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_stages);
+criterion_main!(benches);
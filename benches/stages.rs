@@ -0,0 +1,103 @@
+//! Time per keygen and signing stage for a single party, for several (t, n)
+//! configurations. The JSON size of each stage output is printed and used as
+//! the Criterion throughput, so reports also show how messages grow with n.
+//!
+//! Safe prime keygen takes minutes per party and is only run for the
+//! smallest configuration.
+
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, Throughput};
+use serde::Serialize;
+
+use hello::error::Error;
+use hello::message::MessageHashing;
+use hello::*;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::*;
+
+/// (threshold, share_count, quorum size)
+const CONFIGURATIONS: [(u16, u16, u16); 3] = [(1, 3, 2), (2, 5, 3), (6, 10, 7)];
+
+fn bench_stage<I, O: Serialize>(
+    group: &mut BenchmarkGroup<WallTime>,
+    group_name: &str,
+    name: &str,
+    inputs: &[I],
+    stage: fn(&I) -> Result<O, Error>,
+) -> Vec<O> {
+    let outputs = run(inputs, stage);
+    let size = serde_json::to_vec(&outputs[0]).unwrap().len();
+    println!("{}/{}: output {} bytes", group_name, name, size);
+    group.throughput(Throughput::Bytes(size as u64));
+    group.bench_function(name, |b| b.iter(|| stage(&inputs[0]).unwrap()));
+    outputs
+}
+
+fn bench_keygen(c: &mut Criterion) {
+    for &use_safe_prime in [false, true].iter() {
+        for &(threshold, share_count, _) in CONFIGURATIONS.iter() {
+            if use_safe_prime && share_count > CONFIGURATIONS[0].1 {
+                continue;
+            }
+            let name = format!(
+                "keygen/{}-of-{}{}",
+                threshold + 1,
+                share_count,
+                if use_safe_prime { "/safe_prime" } else { "" }
+            );
+            let mut group = c.benchmark_group(&name);
+            group.sample_size(10);
+
+            let inputs = keygen_stage1_inputs(share_count, use_safe_prime);
+            let stage1 = bench_stage(&mut group, &name, "stage1", &inputs, keygen_stage1);
+            let inputs = keygen_stage2_inputs(&stage1, threshold, share_count);
+            let stage2 = bench_stage(&mut group, &name, "stage2", &inputs, keygen_stage2);
+            let inputs = keygen_stage3_inputs(&stage1, &stage2, threshold, share_count);
+            let stage3 = bench_stage(&mut group, &name, "stage3", &inputs, keygen_stage3);
+            let inputs = keygen_stage4_inputs(&stage1, &stage2, &stage3, threshold, share_count);
+            bench_stage(&mut group, &name, "stage4", &inputs, keygen_stage4);
+
+            group.finish();
+        }
+    }
+}
+
+fn bench_sign(c: &mut Criterion) {
+    for &(threshold, share_count, quorum_size) in CONFIGURATIONS.iter() {
+        let keys = keygen(threshold, share_count, false);
+        let parties: Vec<u16> = (1..=quorum_size).collect();
+        let quorum = Quorum::new(&keys, &parties);
+
+        let name = format!("sign/{}-of-{}", quorum_size, share_count);
+        let mut group = c.benchmark_group(&name);
+        group.sample_size(10);
+
+        let inputs = quorum.stage1_inputs();
+        let stage1 = bench_stage(&mut group, &name, "stage1", &inputs, sign_stage1);
+        let inputs = quorum.stage2_inputs(&stage1);
+        let stage2 = bench_stage(&mut group, &name, "stage2", &inputs, sign_stage2);
+        let inputs = quorum.stage3_inputs(&stage1, &stage2);
+        let stage3 = bench_stage(&mut group, &name, "stage3", &inputs, sign_stage3);
+        let inputs = quorum.stage4_inputs(&stage3);
+        let stage4 = bench_stage(&mut group, &name, "stage4", &inputs, sign_stage4);
+        let inputs = quorum.stage5_inputs(&stage1, &stage2, &stage4);
+        let stage5 = bench_stage(&mut group, &name, "stage5", &inputs, sign_stage5);
+        let inputs = quorum.stage6_inputs(&stage1, &stage3, &stage5);
+        let stage6 = bench_stage(&mut group, &name, "stage6", &inputs, sign_stage6);
+        let completed = quorum.completed_offline_stages(&stage1, &stage3, &stage5);
+        let inputs = quorum.stage7_inputs(&completed, &stage6);
+        bench_stage(&mut group, &name, "stage7", &inputs, sign_stage7);
+        let inputs = quorum.stage8_inputs(&completed, b"benchmark", MessageHashing::Sha256);
+        let stage8 = bench_stage(&mut group, &name, "stage8", &inputs, sign_stage8);
+        let inputs = quorum.stage9_inputs(&stage8);
+        bench_stage(&mut group, &name, "stage9", &inputs, sign_stage9);
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_keygen, bench_sign);
+criterion_main!(benches);
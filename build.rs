@@ -0,0 +1,49 @@
+// The stage list of src/ffi_exports.rs as (name, doc lines)
+#[cfg(feature = "ffi")]
+macro_rules! exports {
+    ($($(#[doc = $doc:literal])* $name:ident => $stage:path;)*) => {
+        &[$((stringify!($name), &[$($doc),*] as &[&str])),*]
+    };
+}
+
+fn main() {
+    // Regenerate the C header for the FFI layer
+    #[cfg(feature = "ffi")]
+    {
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir)).unwrap();
+        let mut header = Vec::new();
+        cbindgen::Builder::new()
+            .with_src(format!("{}/src/ffi.rs", crate_dir))
+            .with_config(config)
+            .generate()
+            .expect("cannot generate include/mpc.h")
+            .write(&mut header);
+        let mut header = String::from_utf8(header).unwrap();
+
+        // cbindgen does not expand macros, so declare the stage entry points
+        // ffi.rs generates from the list before the closing include guard
+        let stages: &[(&str, &[&str])] = include!("src/ffi_exports.rs");
+        let mut declarations = String::new();
+        for (name, doc) in stages {
+            for line in doc.iter() {
+                declarations.push_str(&format!("//{}\n", line));
+            }
+            declarations.push_str(&format!("enum MpcStatus {}(const char *input, char **output);\n\n", name));
+        }
+        let guard = header.rfind("#endif").expect("include/mpc.h has no include guard");
+        header.insert_str(guard, &declarations);
+
+        // The module documentation, safety rules included, heads the header
+        let source = std::fs::read_to_string(format!("{}/src/ffi.rs", crate_dir)).unwrap();
+        let mut preamble = String::new();
+        for line in source.lines().take_while(|line| line.starts_with("//!")) {
+            preamble.push_str(format!("//{}", &line[3..]).trim_end());
+            preamble.push('\n');
+        }
+        header.insert_str(0, &format!("{}\n", preamble));
+        std::fs::write(format!("{}/include/mpc.h", crate_dir), header).unwrap();
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=src/ffi_exports.rs");
+    }
+}
@@ -0,0 +1,132 @@
+// C ABI over the JSON stage API, for hosts that cannot load the Node addon.
+//
+// Every function takes a NUL-terminated UTF-8 JSON input and stores a JSON
+// string in `*output`: the stage output on `MPC_STATUS_OK`, otherwise
+// `{"error": "...", "blamed_parties": [...]}`. Strings stored in `*output`
+// belong to the caller and must be released with `mpc_string_free`.
+//
+// # Safety
+//
+// The `mpc_secp256k1_*` stage functions are sound as long as the caller
+// upholds these rules:
+//
+// - `input` is NULL or points to a NUL-terminated string that stays valid
+//   and unmodified for the duration of the call.
+// - `output` is NULL or points to writable, properly aligned storage for
+//   one `char *`. Its previous value is overwritten, not freed.
+// - A string stored in `*output` is released exactly once, with
+//   `mpc_string_free` and never with `free`, and is not used afterwards.
+
+#ifndef MPC_H
+#define MPC_H
+
+/* Generated by cbindgen from src/ffi.rs and src/ffi_exports.rs with `cargo build --features ffi`. Do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum MpcStatus {
+  MPC_STATUS_OK = 0,
+  // `input` or `output` is NULL.
+  MPC_STATUS_NULL_POINTER = 1,
+  // `input` is not UTF-8, not valid JSON for the stage, or describes an
+  // impossible quorum or message set.
+  MPC_STATUS_INVALID_INPUT = 2,
+  // The stage aborted because of the parties in `blamed_parties`.
+  MPC_STATUS_BLAME = 3,
+  // The stage failed for another reason, see `error`.
+  MPC_STATUS_FAILED = 4,
+  // The library panicked. This is a bug.
+  MPC_STATUS_PANIC = 5,
+} MpcStatus;
+
+// Wipes and releases a string returned through `output`. Passing NULL is
+// a no-op.
+//
+// # Safety
+//
+// `s` is NULL or a string stored in `*output` by this library that has not
+// been released yet. It must not be used after this call.
+void mpc_string_free(char *s);
+
+// Keygen round 1: this party's Paillier key and its commitments.
+enum MpcStatus mpc_secp256k1_keygen_stage1(const char *input, char **output);
+
+// Keygen round 2: opens the round 1 commitments and deals VSS shares.
+enum MpcStatus mpc_secp256k1_keygen_stage2(const char *input, char **output);
+
+// Keygen round 3: checks the received shares and proves the key share.
+enum MpcStatus mpc_secp256k1_keygen_stage3(const char *input, char **output);
+
+// Keygen round 4: checks every party's key share proof.
+enum MpcStatus mpc_secp256k1_keygen_stage4(const char *input, char **output);
+
+// The key share to save once keygen round 4 succeeded.
+enum MpcStatus mpc_secp256k1_keygen_finalize(const char *input, char **output);
+
+// Signing round 1: the nonce shares, their commitment and MtA message A.
+enum MpcStatus mpc_secp256k1_sign_stage1(const char *input, char **output);
+
+// Signing round 2: the MtA responses to every counterparty.
+enum MpcStatus mpc_secp256k1_sign_stage2(const char *input, char **output);
+
+// Signing round 3: delta_i and sigma_i from the MtA results.
+enum MpcStatus mpc_secp256k1_sign_stage3(const char *input, char **output);
+
+// Signing round 4: the inverse of the summed deltas.
+enum MpcStatus mpc_secp256k1_sign_stage4(const char *input, char **output);
+
+// Signing round 5: R, R_i and their consistency proofs.
+enum MpcStatus mpc_secp256k1_sign_stage5(const char *input, char **output);
+
+// Signing round 6: checks the round 5 proofs and publishes S_i.
+enum MpcStatus mpc_secp256k1_sign_stage6(const char *input, char **output);
+
+// Signing round 7: checks that the S_i sum to the public key.
+enum MpcStatus mpc_secp256k1_sign_stage7(const char *input, char **output);
+
+// Signing round 8: this party's share of s.
+enum MpcStatus mpc_secp256k1_sign_stage8(const char *input, char **output);
+
+// Signing round 9: the combined, verified signature.
+enum MpcStatus mpc_secp256k1_sign_stage9(const char *input, char **output);
+
+// A key share shifted by a BIP32 tweak.
+enum MpcStatus mpc_secp256k1_tweak_key(const char *input, char **output);
+
+// The private key interpolated from t + 1 shares.
+enum MpcStatus mpc_secp256k1_construct_private_key(const char *input, char **output);
+
+// Whether a signature verifies.
+enum MpcStatus mpc_secp256k1_verify_signature(const char *input, char **output);
+
+// CGGMP21 aux info round 1: a Paillier key, s and t, and their commitment.
+enum MpcStatus mpc_secp256k1_cggmp21_aux_info_stage1(const char *input, char **output);
+
+// CGGMP21 aux info round 2: opens the commitments and proves the modulus.
+enum MpcStatus mpc_secp256k1_cggmp21_aux_info_stage2(const char *input, char **output);
+
+// The CGGMP21 key share to save once every modulus proof verified.
+enum MpcStatus mpc_secp256k1_cggmp21_aux_info_finalize(const char *input, char **output);
+
+// CGGMP21 presigning round 1: the encrypted nonces k_i and gamma_i.
+enum MpcStatus mpc_secp256k1_cggmp21_presign_stage1(const char *input, char **output);
+
+// CGGMP21 presigning round 2: the MtA responses to every counterparty.
+enum MpcStatus mpc_secp256k1_cggmp21_presign_stage2(const char *input, char **output);
+
+// CGGMP21 presigning round 3: delta_i, Delta_i and S_i with their proofs.
+enum MpcStatus mpc_secp256k1_cggmp21_presign_stage3(const char *input, char **output);
+
+// The presignature once every round 3 proof verified.
+enum MpcStatus mpc_secp256k1_cggmp21_presign_output(const char *input, char **output);
+
+// CGGMP21 signing: this party's share of s.
+enum MpcStatus mpc_secp256k1_cggmp21_sign(const char *input, char **output);
+
+// The combined signature, or the parties whose shares are wrong.
+enum MpcStatus mpc_secp256k1_cggmp21_sign_combine(const char *input, char **output);
+
+#endif /* MPC_H */
@@ -0,0 +1,313 @@
+// Signatures of the wrappers in index.js. The types come from types.d.ts,
+// which mpc-schema generates from the Rust structs during `npm install`.
+
+import {
+  AddressFormat,
+  EncodedSignature,
+  LocalKey,
+  MessageHashing,
+  Network,
+  PartyIndex,
+  Scalar,
+  Secp256k1AddressOutput,
+  Secp256k1AuditKeyShareOutput,
+  Secp256k1Cggmp21AuxInfoFinalizeInput,
+  Secp256k1Cggmp21AuxInfoStage1Output,
+  Secp256k1Cggmp21AuxInfoStage2Input,
+  Secp256k1Cggmp21AuxInfoStage2Output,
+  Secp256k1Cggmp21KeyShare,
+  Secp256k1Cggmp21PresignOutputInput,
+  Secp256k1Cggmp21PresignStage1Output,
+  Secp256k1Cggmp21PresignStage2Input,
+  Secp256k1Cggmp21PresignStage2Output,
+  Secp256k1Cggmp21PresignStage3Input,
+  Secp256k1Cggmp21PresignStage3Output,
+  Secp256k1Cggmp21Presignature,
+  Secp256k1Cggmp21SignCombineInput,
+  Secp256k1Cggmp21SignCombineOutput,
+  Secp256k1Cggmp21SignOutput,
+  Secp256k1ConstructPrivateKeyInput,
+  Secp256k1DeriveXpubOutput,
+  Secp256k1Eip712HashInput,
+  Secp256k1Eip712HashOutput,
+  Secp256k1Eip712RecoverOutput,
+  Secp256k1EthTxEncodeSignedInput,
+  Secp256k1EthTxEncodeSignedOutput,
+  Secp256k1EthTxHashOutput,
+  Secp256k1ExportXpubOutput,
+  Secp256k1InspectKeyShareOutput,
+  Secp256k1KeyGenFinalizeInput,
+  Secp256k1KeyGenStage1Output,
+  Secp256k1KeyGenStage2Input,
+  Secp256k1KeyGenStage2Output,
+  Secp256k1KeyGenStage3Input,
+  Secp256k1KeyGenStage3Output,
+  Secp256k1KeyGenStage4Input,
+  Secp256k1KeyGenStage4Output,
+  Secp256k1KeyShare,
+  Secp256k1ParseXpubOutput,
+  Secp256k1PsbtAddSignaturesInput,
+  Secp256k1PsbtAddSignaturesOutput,
+  Secp256k1PsbtSighashesInput,
+  Secp256k1PsbtSighashesOutput,
+  Secp256k1RecoverPublicKeyOutput,
+  Secp256k1SignStage1Input,
+  Secp256k1SignStage1Output,
+  Secp256k1SignStage2Input,
+  Secp256k1SignStage2Output,
+  Secp256k1SignStage3Input,
+  Secp256k1SignStage3Output,
+  Secp256k1SignStage4Input,
+  Secp256k1SignStage4Output,
+  Secp256k1SignStage5Input,
+  Secp256k1SignStage5Output,
+  Secp256k1SignStage6Input,
+  Secp256k1SignStage6Output,
+  Secp256k1SignStage7Input,
+  Secp256k1SignStage7Output,
+  Secp256k1SignStage8Input,
+  Secp256k1SignStage8Output,
+  Secp256k1SignStage9Input,
+  Secp256k1SignStage9Output,
+  Secp256k1TweakKeyInput,
+  Secp256k1TweakKeyOutput,
+  Secp256k1VerifySignatureInput,
+  Secp256k1VerifySignatureOutput,
+  SignatureRecid,
+} from './types';
+
+export * from './types';
+
+/** Byte strings are accepted as anything spreadable into numbers. */
+type Bytes = Uint8Array | number[];
+
+/** Every stage of one signing session, run for each session in a batch. */
+interface Batch<T> {
+  sessions: T[];
+}
+
+export function secp256k1_keygen_stage1(index: PartyIndex, useSafePrime?: boolean): Secp256k1KeyGenStage1Output;
+export function secp256k1_keygen_stage2(
+  key: Secp256k1KeyGenStage2Input['key'],
+  bc1s: Secp256k1KeyGenStage2Input['bc1s'],
+  decom1s: Secp256k1KeyGenStage2Input['decom1s'],
+  chain_code_coms: Secp256k1KeyGenStage2Input['chain_code_coms'],
+  chain_code_decoms: Secp256k1KeyGenStage2Input['chain_code_decoms'],
+  threshold: number,
+  share_count: number,
+): Secp256k1KeyGenStage2Output;
+export function secp256k1_keygen_stage3(
+  key: Secp256k1KeyGenStage3Input['key'],
+  ys: Secp256k1KeyGenStage3Input['ys'],
+  vss_schemes: Secp256k1KeyGenStage3Input['vss_schemes'],
+  party_shares: Secp256k1KeyGenStage3Input['party_shares'],
+  threshold: number,
+  share_count: number,
+): Secp256k1KeyGenStage3Output;
+export function secp256k1_keygen_stage4(
+  ys: Secp256k1KeyGenStage4Input['ys'],
+  vss_schemes: Secp256k1KeyGenStage4Input['vss_schemes'],
+  dlog_proofs: Secp256k1KeyGenStage4Input['dlog_proofs'],
+  threshold: number,
+  share_count: number,
+): Secp256k1KeyGenStage4Output;
+export function secp256k1_keygen_finalize(
+  key: Secp256k1KeyGenFinalizeInput['key'],
+  bc1s: Secp256k1KeyGenFinalizeInput['bc1s'],
+  vss_scheme: Secp256k1KeyGenFinalizeInput['vss_scheme'],
+  shared_key: Secp256k1KeyGenFinalizeInput['shared_key'],
+  dlog_proofs: Secp256k1KeyGenFinalizeInput['dlog_proofs'],
+  chain_code: Bytes,
+  threshold: number,
+  share_count: number,
+): Secp256k1KeyShare;
+export function secp256k1_construct_private_key(
+  vss_scheme: Secp256k1ConstructPrivateKeyInput['vss_scheme'],
+  parties: PartyIndex[],
+  xs: Secp256k1ConstructPrivateKeyInput['xs'],
+): Scalar;
+
+export function secp256k1_sign_stage1(parties: PartyIndex[], local_key: LocalKey): Secp256k1SignStage1Output;
+export function secp256k1_sign_stage2(
+  parties: PartyIndex[],
+  local_key: LocalKey,
+  m_as: Secp256k1SignStage2Input['m_as'],
+  sign_key: Secp256k1SignStage2Input['sign_key'],
+): Secp256k1SignStage2Output;
+export function secp256k1_sign_stage3(
+  parties: PartyIndex[],
+  local_key: LocalKey,
+  sign_key: Secp256k1SignStage3Input['sign_key'],
+  nis: Secp256k1SignStage3Input['nis'],
+  betas: Secp256k1SignStage3Input['betas'],
+  m_b_gammas: Secp256k1SignStage3Input['m_b_gammas'],
+  m_b_ws: Secp256k1SignStage3Input['m_b_ws'],
+): Secp256k1SignStage3Output;
+export function secp256k1_sign_stage4(
+  parties: PartyIndex[],
+  deltas: Secp256k1SignStage4Input['deltas'],
+  ts: Secp256k1SignStage4Input['ts'],
+  t_proofs: Secp256k1SignStage4Input['t_proofs'],
+): Secp256k1SignStage4Output;
+export function secp256k1_sign_stage5(
+  parties: PartyIndex[],
+  local_key: LocalKey,
+  sign_key: Secp256k1SignStage5Input['sign_key'],
+  m_a: Secp256k1SignStage5Input['m_a'],
+  m_b_gammas: Secp256k1SignStage5Input['m_b_gammas'],
+  bc1s: Secp256k1SignStage5Input['bc1s'],
+  delta_inv: Secp256k1SignStage5Input['delta_inv'],
+  decom1s: Secp256k1SignStage5Input['decom1s'],
+): Secp256k1SignStage5Output;
+export function secp256k1_sign_stage6(
+  parties: PartyIndex[],
+  local_key: LocalKey,
+  m_as: Secp256k1SignStage6Input['m_as'],
+  t_i: Secp256k1SignStage6Input['t_i'],
+  l_i: Secp256k1SignStage6Input['l_i'],
+  sigma_i: Secp256k1SignStage6Input['sigma_i'],
+  r: Secp256k1SignStage6Input['r'],
+  r_dashes: Secp256k1SignStage6Input['r_dashes'],
+  phase5_proofss: Secp256k1SignStage6Input['phase5_proofss'],
+): Secp256k1SignStage6Output;
+export function secp256k1_sign_stage7(
+  ss: Secp256k1SignStage7Input['ss'],
+  homo_elgamal_proofs: Secp256k1SignStage7Input['homo_elgamal_proofs'],
+  parties: PartyIndex[],
+  completed_offline_stage: Secp256k1SignStage7Input['completed_offline_stage'],
+): Secp256k1SignStage7Output;
+export function secp256k1_sign_stage8(
+  completed_offline_stage: Secp256k1SignStage8Input['completed_offline_stage'],
+  message: Bytes,
+  hashing?: MessageHashing,
+): Secp256k1SignStage8Output;
+export function secp256k1_sign_stage9(
+  local_signature: Secp256k1SignStage9Input['local_signature'],
+  partial_signatures: Secp256k1SignStage9Input['partial_signatures'],
+): Secp256k1SignStage9Output;
+
+export function secp256k1_sign_batch_stage1(sessions: Secp256k1SignStage1Input[]): Batch<Secp256k1SignStage1Output>;
+export function secp256k1_sign_batch_stage2(sessions: Secp256k1SignStage2Input[]): Batch<Secp256k1SignStage2Output>;
+export function secp256k1_sign_batch_stage3(sessions: Secp256k1SignStage3Input[]): Batch<Secp256k1SignStage3Output>;
+export function secp256k1_sign_batch_stage4(sessions: Secp256k1SignStage4Input[]): Batch<Secp256k1SignStage4Output>;
+export function secp256k1_sign_batch_stage5(sessions: Secp256k1SignStage5Input[]): Batch<Secp256k1SignStage5Output>;
+export function secp256k1_sign_batch_stage6(sessions: Secp256k1SignStage6Input[]): Batch<Secp256k1SignStage6Output>;
+export function secp256k1_sign_batch_stage7(sessions: Secp256k1SignStage7Input[]): Batch<Secp256k1SignStage7Output>;
+export function secp256k1_sign_batch_stage8(sessions: Secp256k1SignStage8Input[]): Batch<Secp256k1SignStage8Output>;
+export function secp256k1_sign_batch_stage9(sessions: Secp256k1SignStage9Input[]): Batch<Secp256k1SignStage9Output>;
+
+export function secp256k1_cggmp21_aux_info_stage1(
+  local_key: LocalKey,
+  useSafePrime?: boolean,
+): Secp256k1Cggmp21AuxInfoStage1Output;
+export function secp256k1_cggmp21_aux_info_stage2(
+  local_key: LocalKey,
+  paillier_key: Secp256k1Cggmp21AuxInfoStage2Input['paillier_key'],
+  commitments: Secp256k1Cggmp21AuxInfoStage2Input['commitments'],
+  decommitments: Secp256k1Cggmp21AuxInfoStage2Input['decommitments'],
+): Secp256k1Cggmp21AuxInfoStage2Output;
+export function secp256k1_cggmp21_aux_info_finalize(
+  local_key: LocalKey,
+  paillier_key: Secp256k1Cggmp21AuxInfoFinalizeInput['paillier_key'],
+  commitments: Secp256k1Cggmp21AuxInfoFinalizeInput['commitments'],
+  decommitments: Secp256k1Cggmp21AuxInfoFinalizeInput['decommitments'],
+  mod_proofs: Secp256k1Cggmp21AuxInfoFinalizeInput['mod_proofs'],
+  fac_proofs: Secp256k1Cggmp21AuxInfoFinalizeInput['fac_proofs'],
+): Secp256k1Cggmp21KeyShare;
+export function secp256k1_cggmp21_presign_stage1(
+  parties: PartyIndex[],
+  key_share: Secp256k1Cggmp21KeyShare,
+): Secp256k1Cggmp21PresignStage1Output;
+export function secp256k1_cggmp21_presign_stage2(
+  parties: PartyIndex[],
+  key_share: Secp256k1Cggmp21KeyShare,
+  nonces: Secp256k1Cggmp21PresignStage2Input['nonces'],
+  messages1: Secp256k1Cggmp21PresignStage2Input['messages1'],
+): Secp256k1Cggmp21PresignStage2Output;
+export function secp256k1_cggmp21_presign_stage3(
+  parties: PartyIndex[],
+  key_share: Secp256k1Cggmp21KeyShare,
+  nonces: Secp256k1Cggmp21PresignStage3Input['nonces'],
+  messages1: Secp256k1Cggmp21PresignStage3Input['messages1'],
+  messages2: Secp256k1Cggmp21PresignStage3Input['messages2'],
+): Secp256k1Cggmp21PresignStage3Output;
+export function secp256k1_cggmp21_presign_output(
+  parties: PartyIndex[],
+  key_share: Secp256k1Cggmp21KeyShare,
+  nonces: Secp256k1Cggmp21PresignOutputInput['nonces'],
+  chi: Secp256k1Cggmp21PresignOutputInput['chi'],
+  messages1: Secp256k1Cggmp21PresignOutputInput['messages1'],
+  messages2: Secp256k1Cggmp21PresignOutputInput['messages2'],
+  messages3: Secp256k1Cggmp21PresignOutputInput['messages3'],
+): Secp256k1Cggmp21Presignature;
+export function secp256k1_cggmp21_sign(
+  presignature: Secp256k1Cggmp21Presignature,
+  message: Bytes,
+  hashing?: MessageHashing,
+): Secp256k1Cggmp21SignOutput;
+export function secp256k1_cggmp21_sign_combine(
+  presignature: Secp256k1Cggmp21Presignature,
+  message: Bytes,
+  partial_signatures: Secp256k1Cggmp21SignCombineInput['partial_signatures'],
+  hashing?: MessageHashing,
+): Secp256k1Cggmp21SignCombineOutput;
+
+export function secp256k1_tweak_key(
+  local_key: LocalKey,
+  il: Secp256k1TweakKeyInput['il'],
+): Secp256k1TweakKeyOutput;
+export function secp256k1_export_xpub(
+  local_key: LocalKey,
+  chain_code: Bytes,
+  network?: Network,
+): Secp256k1ExportXpubOutput;
+export function secp256k1_parse_xpub(xpub: string): Secp256k1ParseXpubOutput;
+export function secp256k1_derive_xpub(xpub: string, path: string): Secp256k1DeriveXpubOutput;
+export function secp256k1_verify_signature(
+  signature: SignatureRecid,
+  key: NonNullable<Secp256k1VerifySignatureInput['public_key']> | LocalKey,
+  message: Bytes,
+  hashing?: MessageHashing,
+): Secp256k1VerifySignatureOutput;
+export function secp256k1_recover_public_key(
+  signature: SignatureRecid,
+  message: Bytes,
+  hashing?: MessageHashing,
+): Secp256k1RecoverPublicKeyOutput;
+export function secp256k1_encode_signature(signature: SignatureRecid): EncodedSignature;
+export function secp256k1_eth_tx_hash(
+  transaction: Secp256k1EthTxEncodeSignedInput['transaction'],
+): Secp256k1EthTxHashOutput;
+export function secp256k1_eth_tx_encode_signed(
+  transaction: Secp256k1EthTxEncodeSignedInput['transaction'],
+  signature: SignatureRecid,
+  local_key: LocalKey,
+  chain_code?: Bytes | null,
+  path?: string | null,
+): Secp256k1EthTxEncodeSignedOutput;
+export function secp256k1_eip712_hash(typed_data: Secp256k1Eip712HashInput['typed_data']): Secp256k1Eip712HashOutput;
+export function secp256k1_eip712_recover(
+  typed_data: Secp256k1Eip712HashInput['typed_data'],
+  signature: SignatureRecid,
+): Secp256k1Eip712RecoverOutput;
+export function secp256k1_psbt_sighashes(
+  psbt: Bytes,
+  local_key: LocalKey,
+  chain_code?: Bytes | null,
+  path?: Secp256k1PsbtSighashesInput['path'],
+  allowed_sighash_types?: number[],
+): Secp256k1PsbtSighashesOutput;
+export function secp256k1_psbt_add_signatures(
+  psbt: Bytes,
+  public_key: Secp256k1PsbtAddSignaturesInput['public_key'],
+  signatures: Secp256k1PsbtAddSignaturesInput['signatures'],
+  allowed_sighash_types?: number[],
+): Secp256k1PsbtAddSignaturesOutput;
+export function secp256k1_address(
+  local_key: LocalKey,
+  format: AddressFormat,
+  chain_code?: Bytes | null,
+  path?: string | null,
+): Secp256k1AddressOutput;
+export function secp256k1_inspect_key_share(local_key: LocalKey): Secp256k1InspectKeyShareOutput;
+export function secp256k1_audit_key_share(key_share: Secp256k1KeyShare): Secp256k1AuditKeyShareOutput;
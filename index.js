@@ -1,6 +1,5 @@
 const EC = require('elliptic').ec;
 const ec = new EC('secp256k1');
-const Signature = require('elliptic/lib/elliptic/ec/signature');
 const ecc = require('tiny-secp256k1')
 const { BIP32Factory } = require('bip32');
 const bip32 = BIP32Factory(ecc);
@@ -14,11 +13,13 @@ function secp256k1_keygen_stage1(index, useSafePrime) {
   })));
 }
 
-function secp256k1_keygen_stage2(key, bc1s, decom1s, threshold, share_count) {
+function secp256k1_keygen_stage2(key, bc1s, decom1s, chain_code_coms, chain_code_decoms, threshold, share_count) {
   return JSON.parse(native.secp256k1_keygen_stage2(JSON.stringify({
     key,
     bc1s,
     decom1s,
+    chain_code_coms,
+    chain_code_decoms,
     threshold,
     share_count,
   })));
@@ -45,6 +46,19 @@ function secp256k1_keygen_stage4(ys, vss_schemes, dlog_proofs, threshold, share_
   })));
 }
 
+function secp256k1_keygen_finalize(key, bc1s, vss_scheme, shared_key, dlog_proofs, chain_code, threshold, share_count) {
+  return JSON.parse(native.secp256k1_keygen_finalize(JSON.stringify({
+    key,
+    bc1s,
+    vss_scheme,
+    shared_key,
+    dlog_proofs,
+    chain_code: [...chain_code],
+    threshold,
+    share_count,
+  })));
+}
+
 function secp256k1_construct_private_key(vss_scheme, parties, xs) {
   return JSON.parse(native.secp256k1_construct_private_key(JSON.stringify({
     vss_scheme,
@@ -53,17 +67,15 @@ function secp256k1_construct_private_key(vss_scheme, parties, xs) {
   })));
 }
 
-function secp256k1_sign_stage1(index, parties, local_key) {
+function secp256k1_sign_stage1(parties, local_key) {
   return JSON.parse(native.secp256k1_sign_stage1(JSON.stringify({
-    index,
     parties,
     local_key,
   })));
 }
 
-function secp256k1_sign_stage2(index, parties, local_key, m_as, sign_key) {
+function secp256k1_sign_stage2(parties, local_key, m_as, sign_key) {
   return JSON.parse(native.secp256k1_sign_stage2(JSON.stringify({
-    index,
     parties,
     local_key,
     m_as,
@@ -71,9 +83,8 @@ function secp256k1_sign_stage2(index, parties, local_key, m_as, sign_key) {
   })));
 }
 
-function secp256k1_sign_stage3(index, parties, local_key, sign_key, nis, betas, m_b_gammas, m_b_ws) {
+function secp256k1_sign_stage3(parties, local_key, sign_key, nis, betas, m_b_gammas, m_b_ws) {
   return JSON.parse(native.secp256k1_sign_stage3(JSON.stringify({
-    index,
     parties,
     local_key,
     sign_key,
@@ -93,9 +104,8 @@ function secp256k1_sign_stage4(parties, deltas, ts, t_proofs) {
   })));
 }
 
-function secp256k1_sign_stage5(index, parties, local_key, sign_key, m_a, m_b_gammas, bc1s, delta_inv, decom1s) {
+function secp256k1_sign_stage5(parties, local_key, sign_key, m_a, m_b_gammas, bc1s, delta_inv, decom1s) {
   return JSON.parse(native.secp256k1_sign_stage5(JSON.stringify({
-    index,
     parties,
     local_key,
     sign_key,
@@ -107,9 +117,8 @@ function secp256k1_sign_stage5(index, parties, local_key, sign_key, m_a, m_b_gam
   })));
 }
 
-function secp256k1_sign_stage6(index, parties, local_key, m_as, t_i, l_i, sigma_i, r, r_dashes, phase5_proofss) {
+function secp256k1_sign_stage6(parties, local_key, m_as, t_i, l_i, sigma_i, r, r_dashes, phase5_proofss) {
   return JSON.parse(native.secp256k1_sign_stage6(JSON.stringify({
-    index,
     parties,
     local_key,
     m_as,
@@ -131,11 +140,12 @@ function secp256k1_sign_stage7(ss, homo_elgamal_proofs, parties, completed_offli
   })));
 }
 
-function secp256k1_sign_stage8(completed_offline_stage, message) {
+function secp256k1_sign_stage8(completed_offline_stage, message, hashing) {
   return JSON.parse(native.secp256k1_sign_stage8(JSON.stringify({
     completed_offline_stage,
     // To array of number
     message: [...message],
+    hashing: hashing || 'prehashed',
   })));
 }
 
@@ -146,20 +156,271 @@ function secp256k1_sign_stage9(local_signature, partial_signatures) {
   })));
 }
 
-function secp256k1_tweak_key(index, local_key, il) {
+function secp256k1_sign_batch_stage1(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage1(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage2(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage2(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage3(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage3(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage4(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage4(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage5(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage5(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage6(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage6(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage7(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage7(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage8(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage8(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_sign_batch_stage9(sessions) {
+  return JSON.parse(native.secp256k1_sign_batch_stage9(JSON.stringify({
+    sessions,
+  })));
+}
+
+function secp256k1_cggmp21_aux_info_stage1(local_key, useSafePrime) {
+  return JSON.parse(native.secp256k1_cggmp21_aux_info_stage1(JSON.stringify({
+    local_key,
+    use_safe_prime: !!useSafePrime,
+  })));
+}
+
+function secp256k1_cggmp21_aux_info_stage2(local_key, paillier_key, commitments, decommitments) {
+  return JSON.parse(native.secp256k1_cggmp21_aux_info_stage2(JSON.stringify({
+    local_key,
+    paillier_key,
+    commitments,
+    decommitments,
+  })));
+}
+
+function secp256k1_cggmp21_aux_info_finalize(local_key, paillier_key, commitments, decommitments, mod_proofs, fac_proofs) {
+  return JSON.parse(native.secp256k1_cggmp21_aux_info_finalize(JSON.stringify({
+    local_key,
+    paillier_key,
+    commitments,
+    decommitments,
+    mod_proofs,
+    fac_proofs,
+  })));
+}
+
+function secp256k1_cggmp21_presign_stage1(parties, key_share) {
+  return JSON.parse(native.secp256k1_cggmp21_presign_stage1(JSON.stringify({
+    parties,
+    key_share,
+  })));
+}
+
+function secp256k1_cggmp21_presign_stage2(parties, key_share, nonces, messages1) {
+  return JSON.parse(native.secp256k1_cggmp21_presign_stage2(JSON.stringify({
+    parties,
+    key_share,
+    nonces,
+    messages1,
+  })));
+}
+
+function secp256k1_cggmp21_presign_stage3(parties, key_share, nonces, messages1, messages2) {
+  return JSON.parse(native.secp256k1_cggmp21_presign_stage3(JSON.stringify({
+    parties,
+    key_share,
+    nonces,
+    messages1,
+    messages2,
+  })));
+}
+
+function secp256k1_cggmp21_presign_output(parties, key_share, nonces, chi, messages1, messages2, messages3) {
+  return JSON.parse(native.secp256k1_cggmp21_presign_output(JSON.stringify({
+    parties,
+    key_share,
+    nonces,
+    chi,
+    messages1,
+    messages2,
+    messages3,
+  })));
+}
+
+function secp256k1_cggmp21_sign(presignature, message, hashing) {
+  return JSON.parse(native.secp256k1_cggmp21_sign(JSON.stringify({
+    presignature,
+    message: [...message],
+    hashing: hashing || 'prehashed',
+  })));
+}
+
+function secp256k1_cggmp21_sign_combine(presignature, message, partial_signatures, hashing) {
+  return JSON.parse(native.secp256k1_cggmp21_sign_combine(JSON.stringify({
+    presignature,
+    message: [...message],
+    partial_signatures,
+    hashing: hashing || 'prehashed',
+  })));
+}
+
+function secp256k1_tweak_key(local_key, il) {
   return JSON.parse(native.secp256k1_tweak_key(JSON.stringify({
-    index,
     local_key,
     il,
   })));
 }
 
+function secp256k1_export_xpub(local_key, chain_code, network) {
+  return JSON.parse(native.secp256k1_export_xpub(JSON.stringify({
+    local_key,
+    chain_code: [...chain_code],
+    network: network || 'mainnet',
+  })));
+}
+
+function secp256k1_parse_xpub(xpub) {
+  return JSON.parse(native.secp256k1_parse_xpub(JSON.stringify({
+    xpub,
+  })));
+}
+
+function secp256k1_derive_xpub(xpub, path) {
+  return JSON.parse(native.secp256k1_derive_xpub(JSON.stringify({
+    xpub,
+    path,
+  })));
+}
+
+// `key` is a public key or a LocalKey, whose y_sum_s is used
+function secp256k1_verify_signature(signature, key, message, hashing) {
+  const isLocalKey = key && key.y_sum_s !== undefined;
+  return JSON.parse(native.secp256k1_verify_signature(JSON.stringify({
+    signature,
+    public_key: isLocalKey ? null : key,
+    local_key: isLocalKey ? key : null,
+    message: [...message],
+    hashing: hashing || 'prehashed',
+  })));
+}
+
+function secp256k1_recover_public_key(signature, message, hashing) {
+  return JSON.parse(native.secp256k1_recover_public_key(JSON.stringify({
+    signature,
+    message: [...message],
+    hashing: hashing || 'prehashed',
+  })));
+}
+
+function secp256k1_encode_signature(signature) {
+  return JSON.parse(native.secp256k1_encode_signature(JSON.stringify({
+    signature,
+  })));
+}
+
+function secp256k1_eth_tx_hash(transaction) {
+  return JSON.parse(native.secp256k1_eth_tx_hash(JSON.stringify({
+    transaction,
+  })));
+}
+
+function secp256k1_eth_tx_encode_signed(transaction, signature, local_key, chain_code, path) {
+  return JSON.parse(native.secp256k1_eth_tx_encode_signed(JSON.stringify({
+    transaction,
+    signature,
+    local_key,
+    chain_code: chain_code ? [...chain_code] : null,
+    path: path || null,
+  })));
+}
+
+function secp256k1_eip712_hash(typed_data) {
+  return JSON.parse(native.secp256k1_eip712_hash(JSON.stringify({
+    typed_data,
+  })));
+}
+
+function secp256k1_eip712_recover(typed_data, signature) {
+  return JSON.parse(native.secp256k1_eip712_recover(JSON.stringify({
+    typed_data,
+    signature,
+  })));
+}
+
+function secp256k1_psbt_sighashes(psbt, local_key, chain_code, path, allowed_sighash_types) {
+  return JSON.parse(native.secp256k1_psbt_sighashes(JSON.stringify({
+    psbt: [...psbt],
+    local_key,
+    chain_code: chain_code ? [...chain_code] : null,
+    path: path || null,
+    allowed_sighash_types: allowed_sighash_types || [],
+  })));
+}
+
+function secp256k1_psbt_add_signatures(psbt, public_key, signatures, allowed_sighash_types) {
+  return JSON.parse(native.secp256k1_psbt_add_signatures(JSON.stringify({
+    psbt: [...psbt],
+    public_key,
+    signatures,
+    allowed_sighash_types: allowed_sighash_types || [],
+  })));
+}
+
+function secp256k1_address(local_key, format, chain_code, path) {
+  return JSON.parse(native.secp256k1_address(JSON.stringify({
+    local_key,
+    format,
+    chain_code: chain_code ? [...chain_code] : null,
+    path: path || null,
+  })));
+}
+
+function secp256k1_inspect_key_share(local_key) {
+  return JSON.parse(native.secp256k1_inspect_key_share(JSON.stringify({
+    local_key,
+  })));
+}
+
+// Takes the key share saved from secp256k1_keygen_finalize as it is
+function secp256k1_audit_key_share(key_share) {
+  return JSON.parse(native.secp256k1_audit_key_share(JSON.stringify(key_share)));
+}
+
 module.exports = {
   // Wrap object function
   secp256k1_keygen_stage1,
   secp256k1_keygen_stage2,
   secp256k1_keygen_stage3,
   secp256k1_keygen_stage4,
+  secp256k1_keygen_finalize,
   secp256k1_construct_private_key,
   secp256k1_sign_stage1,
   secp256k1_sign_stage2,
@@ -170,7 +431,40 @@ module.exports = {
   secp256k1_sign_stage7,
   secp256k1_sign_stage8,
   secp256k1_sign_stage9,
+  secp256k1_sign_batch_stage1,
+  secp256k1_sign_batch_stage2,
+  secp256k1_sign_batch_stage3,
+  secp256k1_sign_batch_stage4,
+  secp256k1_sign_batch_stage5,
+  secp256k1_sign_batch_stage6,
+  secp256k1_sign_batch_stage7,
+  secp256k1_sign_batch_stage8,
+  secp256k1_sign_batch_stage9,
+  secp256k1_cggmp21_aux_info_stage1,
+  secp256k1_cggmp21_aux_info_stage2,
+  secp256k1_cggmp21_aux_info_finalize,
+  secp256k1_cggmp21_presign_stage1,
+  secp256k1_cggmp21_presign_stage2,
+  secp256k1_cggmp21_presign_stage3,
+  secp256k1_cggmp21_presign_output,
+  secp256k1_cggmp21_sign,
+  secp256k1_cggmp21_sign_combine,
   secp256k1_tweak_key,
+  secp256k1_export_xpub,
+  secp256k1_parse_xpub,
+  secp256k1_derive_xpub,
+  secp256k1_verify_signature,
+  secp256k1_recover_public_key,
+  secp256k1_encode_signature,
+  secp256k1_eth_tx_hash,
+  secp256k1_eth_tx_encode_signed,
+  secp256k1_eip712_hash,
+  secp256k1_eip712_recover,
+  secp256k1_psbt_sighashes,
+  secp256k1_psbt_add_signatures,
+  secp256k1_address,
+  secp256k1_inspect_key_share,
+  secp256k1_audit_key_share,
 };
 
 const { createHash, createHmac } = require('crypto');
@@ -187,9 +481,11 @@ const { toASCII } = require('punycode');
 // const decom1s = stage1.map(s => s.decom1);
 // const keys = stage1.map(s => s.key);
 // const ys = decom1s.map(d => d.y_i);
+// const chainCodeComs = stage1.map(s => s.chain_code_com);
+// const chainCodeDecoms = stage1.map(s => s.chain_code_decom);
 
 // console.log('Getting vss...');
-// const stage2 = keys.map(k => secp256k1_keygen_stage2(k, bc1s, decom1s, threshold, shareCount));
+// const stage2 = keys.map(k => secp256k1_keygen_stage2(k, bc1s, decom1s, chainCodeComs, chainCodeDecoms, threshold, shareCount));
 // const vssSchemes = stage2.map(s => s.vss[0]);
 // const partySharess = parties.map((_, i) => stage2.map(s => s.vss[1][i]));
 
@@ -214,29 +510,19 @@ const { toASCII } = require('punycode');
 // console.log(`To public key key: ${publicKey}`);
 
 // // Save key
-// const localKeys = parties.map((i, index) => ({
-//   paillier_dk: keys[index].dk,
-//   pk_vec: dlogProofs.map(d => d.pk),
-//   keys_linear: shareKeys[index],
-//   paillier_key_vec: bc1s.map(b => b.e),
-//   y_sum_s: shareKeys[index].y,
-//   h1_h2_n_tilde_vec: bc1s.map(b => b.dlog_statement),
-//   vss_scheme: vssSchemes[index],
-//   i,
-//   t: threshold,
-//   n: shareCount,
-// }));
-
-// writeFileSync('keys-3-4.json', JSON.stringify(localKeys));
-
-const keys = JSON.parse(readFileSync('keys-3-4.json', 'utf-8'));
-const masterLocalKeys = [keys[0], keys[1], keys[2]];
+// const keyShares = parties.map((_, index) => secp256k1_keygen_finalize(
+//   keys[index], bc1s, vssSchemes[index], shareKeys[index], dlogProofs, stage2[index].chain_code, threshold, shareCount));
+
+// writeFileSync('keys-3-4.json', JSON.stringify(keyShares));
+
+const keyShares = JSON.parse(readFileSync('keys-3-4.json', 'utf-8'));
+const masterLocalKeys = [keyShares[0], keyShares[1], keyShares[2]].map(s => s.local_key);
 const parties = masterLocalKeys.map(k => k.i); // [2, 3, 4] // [1, 2, 3]
 console.log(parties);
 
 masterLocalKeys.forEach(k => {
-  const publicKey = ec.keyFromPublic(k.keys_linear.y.point).getPublic(true, 'hex');
-  console.log(`From share ${k.i}: ${publicKey}`);
+  const { address } = secp256k1_address(k, { type: 'ethereum' });
+  console.log(`From share ${k.i}: ${address}`);
 });
 
 console.log('Construct private key...');
@@ -252,8 +538,8 @@ console.log(`To public key: ${publicKey}`);
 //   scalar: [...Buffer.from('52a2b4ac5024276cfbf98c82098aa332a6c7b941f33631f477ab2ca25ac58087', 'hex')],
 // };
 
-// const localKeys = masterLocalKeys.map((k, i) => {
-//   const { new_local_key } = secp256k1_tweak_key(i, k, il);
+// const localKeys = masterLocalKeys.map(k => {
+//   const { new_local_key } = secp256k1_tweak_key(k, il);
 //   return new_local_key;
 // });
 
@@ -270,13 +556,13 @@ console.log(`To public key: ${publicKey}`);
 
 const localKeys = masterLocalKeys;
 console.log('Stage 1...');
-const stage1 = localKeys.map((k, i) => secp256k1_sign_stage1(i + 1, parties, k));
+const stage1 = localKeys.map((k, i) => secp256k1_sign_stage1(parties, k));
 const mAs = stage1.map(s => s.m_a[0]);
 const bc1s = stage1.map(s => s.bc1);
 const decom1s = stage1.map(s => s.decom1);
 const signKeys = stage1.map(s => s.sign_key);
 console.log('Stage 2...');
-const stage2 = signKeys.map((k, i) => secp256k1_sign_stage2(i + 1, parties, localKeys[i], mAs, k));
+const stage2 = signKeys.map((k, i) => secp256k1_sign_stage2(parties, localKeys[i], mAs, k));
 const mBs = parties.map((_, i) => {
   const mBs = stage2[i].m_b_gammas.map((g, j) => [g, stage2[i].m_b_ws[j]]);
   mBs.splice(i, 0, undefined);
@@ -289,7 +575,7 @@ const mBGammass = mBss.map(m => m.map(m => m[0]));
 const mBWss = mBss.map(m => m.map(m => m[1]));
 
 console.log('Stage 3...');
-const stage3 = signKeys.map((k, i) => secp256k1_sign_stage3(i + 1, parties, localKeys[i], k, stage2[i].nis, stage2[i].betas, mBGammass[i], mBWss[i]));
+const stage3 = signKeys.map((k, i) => secp256k1_sign_stage3(parties, localKeys[i], k, stage2[i].nis, stage2[i].betas, mBGammass[i], mBWss[i]));
 const deltas = stage3.map(s => s.delta_i);
 const ts = stage3.map(s => s.t_i);
 const tProofs = stage3.map(s => s.t_i_proof);
@@ -298,16 +584,15 @@ console.log('Stage 4...');
 const stage4 = parties.map((_, i) => secp256k1_sign_stage4(parties, deltas, ts, tProofs));
 
 console.log('Stage 5...');
-const stage5 = signKeys.map((k, i) => secp256k1_sign_stage5(i + 1, parties, localKeys[i], k, stage1[i].m_a, mBGammass[i], bc1s, stage4[i].delta_inv, decom1s));
+const stage5 = signKeys.map((k, i) => secp256k1_sign_stage5(parties, localKeys[i], k, stage1[i].m_a, mBGammass[i], bc1s, stage4[i].delta_inv, decom1s));
 const rDashes = stage5.map(s => s.r_dash);
 const phase5Proofss = stage5.map(s => s.phase5_proofs);
 
 console.log('Stage 6...');
-const stage6 = localKeys.map((k, i) => secp256k1_sign_stage6(i + 1, parties, k, mAs, stage3[i].t_i, stage3[i].l_i, stage3[i].sigma_i, stage5[i].r, rDashes, phase5Proofss));
+const stage6 = localKeys.map((k, i) => secp256k1_sign_stage6(parties, k, mAs, stage3[i].t_i, stage3[i].l_i, stage3[i].sigma_i, stage5[i].r, rDashes, phase5Proofss));
 const ss = stage6.map(s => s.s_i);
 const homoElgamalProofs = stage6.map(s => s.homo_elgamal_proof);
-const completedOfflineStages = parties.map((index, i) => ({
-  index,
+const completedOfflineStages = parties.map((_, i) => ({
   local_key: localKeys[i],
   sign_key: signKeys[i],
   ts,
@@ -328,12 +613,8 @@ const partialSignaturess = stage8.map((s, i) => {
 console.log('Stage 9...');
 const stage9 = stage8.map((s, i) => secp256k1_sign_stage9(s.local_signature, partialSignaturess[i]));
 localKeys.forEach((k, i) => {
-  const signature = new Signature({
-    r: stage9[i].signature.r.scalar,
-    s: stage9[i].signature.s.scalar,
-    recoveryParam: stage9[i].signature.recid,
-  });
-  console.log(`Verify signature ${k.i}: ${privateKey.verify(message, signature) }`);
+  const { valid } = secp256k1_verify_signature(stage9[i].signature, k.y_sum_s, message);
+  console.log(`Verify signature ${k.i}: ${valid}`);
 });
 
 // Generate random root chain code
@@ -0,0 +1,127 @@
+use bech32::{u5, ToBase32, Variant};
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::bip32::{hash160, Network};
+use crate::error::Error;
+use crate::ethereum;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AddressFormat {
+    /// EIP-55 mixed-case checksum address.
+    Ethereum,
+    BitcoinP2pkh {
+        #[serde(default)]
+        network: Network,
+    },
+    BitcoinP2wpkh {
+        #[serde(default)]
+        network: Network,
+    },
+    /// BIP86 key-path-only taproot output. Spending it needs a BIP340
+    /// Schnorr signature, which the GG20 stages cannot produce.
+    BitcoinP2tr {
+        #[serde(default)]
+        network: Network,
+    },
+    Tron,
+    Cosmos {
+        hrp: String,
+    },
+}
+
+impl AddressFormat {
+    pub fn address(&self, public_key: &Point<Secp256k1>) -> Result<String, Error> {
+        match self {
+            AddressFormat::Ethereum => Ok(ethereum_address(public_key)),
+            AddressFormat::BitcoinP2pkh { network } => {
+                let version = match network {
+                    Network::Mainnet => 0x00,
+                    Network::Testnet => 0x6f,
+                };
+                let mut data = vec![version];
+                data.extend_from_slice(&hash160(&public_key.to_bytes(true)));
+                Ok(bs58::encode(data).with_check().into_string())
+            }
+            AddressFormat::BitcoinP2wpkh { network } => {
+                segwit_address(*network, 0, &hash160(&public_key.to_bytes(true)))
+            }
+            AddressFormat::BitcoinP2tr { network } => {
+                segwit_address(*network, 1, &taproot_output_key(public_key))
+            }
+            AddressFormat::Tron => {
+                let mut data = vec![0x41];
+                data.extend_from_slice(&ethereum::address(public_key));
+                Ok(bs58::encode(data).with_check().into_string())
+            }
+            AddressFormat::Cosmos { hrp } => {
+                let data = hash160(&public_key.to_bytes(true)).to_base32();
+                bech32::encode(hrp, data, Variant::Bech32)
+                    .map_err(|e| Error::InvalidAddressFormat(e.to_string()))
+            }
+        }
+    }
+}
+
+pub fn ethereum_address(public_key: &Point<Secp256k1>) -> String {
+    checksum_ethereum_address(&ethereum::address(public_key))
+}
+
+pub fn checksum_ethereum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = ethereum::keccak256(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", checksummed)
+}
+
+fn segwit_address(network: Network, version: u8, program: &[u8]) -> Result<String, Error> {
+    let hrp = match network {
+        Network::Mainnet => "bc",
+        Network::Testnet => "tb",
+    };
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    let mut data = vec![u5::try_from_u8(version).expect("witness version fits in 5 bits")];
+    data.extend(program.to_base32());
+    bech32::encode(hrp, data, variant).map_err(|e| Error::InvalidAddressFormat(e.to_string()))
+}
+
+/// x(P + tagged_hash("TapTweak", x(P)) * G) with P lifted to even y, as in
+/// BIP86 for an output without a script tree.
+fn taproot_output_key(public_key: &Point<Secp256k1>) -> [u8; 32] {
+    let mut even = public_key.to_bytes(true).to_vec();
+    even[0] = 0x02;
+    let internal_key =
+        Point::<Secp256k1>::from_bytes(&even).expect("x coordinate comes from a valid point");
+
+    let tag = Sha256::digest(b"TapTweak");
+    let mut hasher = Sha256::new();
+    hasher.update(&tag);
+    hasher.update(&tag);
+    hasher.update(&even[1..]);
+    let tweak = Scalar::<Secp256k1>::from_bigint(&BigInt::from_bytes(&hasher.finalize()));
+
+    let output_key = internal_key + Point::generator() * tweak;
+    let mut x = [0; 32];
+    x.copy_from_slice(&output_key.to_bytes(true)[1..]);
+    x
+}
@@ -0,0 +1,26 @@
+//! Writes `schema/<Type>.json` for every stage input and output and
+//! `types.d.ts` with their TypeScript declarations, into the directory given
+//! as the only argument (the current directory by default).
+//!
+//! `npm install` runs this after building the addon, so the declarations
+//! always match the structs the addon was built from.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use hello::schema;
+
+fn main() -> io::Result<()> {
+    let dir = PathBuf::from(env::args().nth(1).unwrap_or_else(|| ".".to_string()));
+
+    let schema_dir = dir.join("schema");
+    fs::create_dir_all(&schema_dir)?;
+    for (name, root) in schema::schemas() {
+        let json = serde_json::to_string_pretty(&root)?;
+        fs::write(schema_dir.join(format!("{}.json", name)), json + "\n")?;
+    }
+
+    fs::write(dir.join("types.d.ts"), schema::typescript())
+}
@@ -0,0 +1,209 @@
+//! Runs single keygen and signing rounds from JSON files, so an air-gapped
+//! party can take part by carrying message files instead of running Node.
+//!
+//! Round inputs and outputs use the same JSON as the Node exports: the file
+//! given to `mpc sign 3` is what `secp256k1_sign_stage3` takes. Output
+//! files are created readable by their owner only, as most hold secrets.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::process;
+
+use clap::{Args, Parser, Subcommand};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use hello::error::Error;
+use hello::secret;
+use hello::*;
+
+#[derive(Parser)]
+#[clap(name = "mpc", version, about = "Offline GG20 threshold ECDSA ceremonies")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run one keygen round (1 to 4) for this party
+    Keygen {
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=4))]
+        round: u8,
+        #[clap(flatten)]
+        files: Files,
+    },
+    /// Assemble the key share to save once keygen round 4 succeeded
+    Finalize {
+        #[clap(flatten)]
+        files: Files,
+    },
+    /// Run one signing round (1 to 9) for this party
+    Sign {
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=9))]
+        round: u8,
+        #[clap(flatten)]
+        files: Files,
+    },
+    /// Verify a signature, exits with status 1 if it does not verify
+    Verify {
+        #[clap(flatten)]
+        files: Files,
+    },
+    /// Derive a non-hardened BIP32 child of an xpub
+    Derive {
+        /// Extended public key to derive from
+        xpub: String,
+        /// Derivation path such as m/0/1
+        path: String,
+        /// Output JSON file, `-` for stdout
+        #[clap(short, long, default_value = "-")]
+        output: String,
+    },
+    /// Print the public facts about a saved key share
+    Inspect {
+        #[clap(flatten)]
+        files: Files,
+    },
+    /// Check the Paillier and ring-Pedersen parameters a saved key share
+    /// holds for every party, exits with status 1 if any has issues
+    Audit {
+        #[clap(flatten)]
+        files: Files,
+    },
+}
+
+#[derive(Args)]
+struct Files {
+    /// Input JSON file, `-` for stdin
+    #[clap(short, long, default_value = "-")]
+    input: String,
+    /// Output JSON file, `-` for stdout
+    #[clap(short, long, default_value = "-")]
+    output: String,
+}
+
+fn read(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut s = String::new();
+        io::stdin().read_to_string(&mut s)?;
+        Ok(s)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+fn write(path: &str, contents: &str) -> io::Result<()> {
+    if path == "-" {
+        writeln!(io::stdout(), "{}", contents)
+    } else {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(path)?;
+        // The mode only applies to new files, tighten an existing one too
+        #[cfg(unix)]
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(contents.as_bytes())
+    }
+}
+
+fn fail(message: String) -> ! {
+    eprintln!("mpc: {}", message);
+    process::exit(1)
+}
+
+fn run<I, O>(files: &Files, stage: fn(&I) -> Result<O, Error>) -> O
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    let json = read(&files.input).unwrap_or_else(|e| fail(format!("{}: {}", files.input, e)));
+    let input: I = secret::from_json(json)
+        .unwrap_or_else(|e| fail(format!("{}: {}", files.input, e)));
+    let output = stage(&input).unwrap_or_else(|e| match e {
+        Error::Blame { reason, parties } => {
+            eprintln!("mpc: {}", reason);
+            eprintln!("mpc: blamed parties {:?}", parties);
+            process::exit(2)
+        }
+        e => fail(e.to_string()),
+    });
+    let json = Zeroizing::new(serde_json::to_string_pretty(&output).unwrap());
+    write(&files.output, &json).unwrap_or_else(|e| fail(format!("{}: {}", files.output, e)));
+    output
+}
+
+fn main() {
+    match Cli::parse().command {
+        Command::Keygen { round, files } => match round {
+            1 => {
+                run(&files, keygen_stage1);
+            }
+            2 => {
+                run(&files, keygen_stage2);
+            }
+            3 => {
+                run(&files, keygen_stage3);
+            }
+            _ => {
+                run(&files, keygen_stage4);
+            }
+        },
+        Command::Finalize { files } => {
+            run(&files, keygen_finalize);
+        }
+        Command::Sign { round, files } => match round {
+            1 => {
+                run(&files, sign_stage1);
+            }
+            2 => {
+                run(&files, sign_stage2);
+            }
+            3 => {
+                run(&files, sign_stage3);
+            }
+            4 => {
+                run(&files, sign_stage4);
+            }
+            5 => {
+                run(&files, sign_stage5);
+            }
+            6 => {
+                run(&files, sign_stage6);
+            }
+            7 => {
+                run(&files, sign_stage7);
+            }
+            8 => {
+                run(&files, sign_stage8);
+            }
+            _ => {
+                run(&files, sign_stage9);
+            }
+        },
+        Command::Verify { files } => {
+            if !run(&files, verify_signature).valid {
+                process::exit(1);
+            }
+        }
+        Command::Derive { xpub, path, output } => {
+            let derived = derive_xpub(&Secp256k1DeriveXpubInput { xpub, path })
+                .unwrap_or_else(|e| fail(e.to_string()));
+            let json = serde_json::to_string_pretty(&derived).unwrap();
+            write(&output, &json).unwrap_or_else(|e| fail(format!("{}: {}", output, e)));
+        }
+        Command::Inspect { files } => {
+            run(&files, inspect_key_share);
+        }
+        Command::Audit { files } => {
+            if !run(&files, audit_key_share).ok {
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use curv::arithmetic::{Converter, Samplable};
+use curv::cryptographic_primitives::commitments::hash_commitment::HashCommitment;
+use curv::cryptographic_primitives::commitments::traits::Commitment;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use hmac::{Hmac, Mac, NewMac};
+use ripemd160::Ripemd160;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::error::Error;
+
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const TPUB_VERSION: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum Network {
+    Mainnet,
+    Testnet,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Network::Mainnet
+    }
+}
+
+impl Network {
+    fn version(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => XPUB_VERSION,
+            Network::Testnet => TPUB_VERSION,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtendedPublicKey {
+    pub network: Network,
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: [u8; 32],
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+}
+
+impl ExtendedPublicKey {
+    /// Master extended public key for a threshold key, i.e. `y_sum_s` of the
+    /// LocalKey together with the chain code agreed on during keygen.
+    pub fn new(public_key: Point<Secp256k1>, chain_code: [u8; 32], network: Network) -> Self {
+        ExtendedPublicKey {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            chain_code,
+            public_key,
+        }
+    }
+
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let hash = hash160(&self.public_key.to_bytes(true));
+        let mut fingerprint = [0; 4];
+        fingerprint.copy_from_slice(&hash[..4]);
+        fingerprint
+    }
+
+    pub fn to_base58(&self) -> String {
+        let mut data = Vec::with_capacity(78);
+        data.extend_from_slice(&self.network.version());
+        data.push(self.depth);
+        data.extend_from_slice(&self.parent_fingerprint);
+        data.extend_from_slice(&self.child_number.to_be_bytes());
+        data.extend_from_slice(&self.chain_code);
+        data.extend_from_slice(&self.public_key.to_bytes(true));
+        bs58::encode(data).with_check().into_string()
+    }
+
+    pub fn from_base58(xpub: &str) -> Result<Self, Error> {
+        let data = bs58::decode(xpub)
+            .with_check(None)
+            .into_vec()
+            .map_err(|_| Error::InvalidExtendedKey("bad base58check encoding"))?;
+        if data.len() != 78 {
+            return Err(Error::InvalidExtendedKey("unexpected length"));
+        }
+        let network = match &data[0..4] {
+            v if v == XPUB_VERSION => Network::Mainnet,
+            v if v == TPUB_VERSION => Network::Testnet,
+            _ => return Err(Error::InvalidExtendedKey("unknown version bytes")),
+        };
+        let depth = data[4];
+        let mut parent_fingerprint = [0; 4];
+        parent_fingerprint.copy_from_slice(&data[5..9]);
+        let mut child_number = [0; 4];
+        child_number.copy_from_slice(&data[9..13]);
+        let child_number = u32::from_be_bytes(child_number);
+        if depth == 0 && (parent_fingerprint != [0; 4] || child_number != 0) {
+            return Err(Error::InvalidExtendedKey("master key with a parent"));
+        }
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&data[13..45]);
+        let public_key = Point::from_bytes(&data[45..78])
+            .map_err(|_| Error::InvalidExtendedKey("public key is not on the curve"))?;
+        Ok(ExtendedPublicKey {
+            network,
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            public_key,
+        })
+    }
+
+    /// Non-hardened CKDpub. Also returns the tweak `il`, which is what
+    /// secp256k1_tweak_key needs to sign for the child key.
+    pub fn derive_child(&self, index: u32) -> Result<(Self, Scalar<Secp256k1>), Error> {
+        if index >= HARDENED_OFFSET {
+            return Err(Error::HardenedDerivation(index));
+        }
+        let depth = self
+            .depth
+            .checked_add(1)
+            .ok_or(Error::InvalidExtendedKey("maximum depth reached"))?;
+        let mut mac = Hmac::<Sha512>::new_from_slice(&self.chain_code)
+            .expect("HMAC accepts keys of any length");
+        mac.update(&self.public_key.to_bytes(true));
+        mac.update(&index.to_be_bytes());
+        let i = mac.finalize().into_bytes();
+
+        let il = BigInt::from_bytes(&i[..32]);
+        if &il >= Scalar::<Secp256k1>::group_order() {
+            return Err(Error::InvalidChildKey(index));
+        }
+        let il = Scalar::<Secp256k1>::from_bigint(&il);
+        let public_key = self.public_key.clone() + Point::generator() * il.clone();
+        if public_key.is_zero() {
+            return Err(Error::InvalidChildKey(index));
+        }
+        let mut chain_code = [0; 32];
+        chain_code.copy_from_slice(&i[32..]);
+
+        Ok((
+            ExtendedPublicKey {
+                network: self.network,
+                depth,
+                parent_fingerprint: self.fingerprint(),
+                child_number: index,
+                chain_code,
+                public_key,
+            },
+            il,
+        ))
+    }
+
+    /// Derives along a path such as `m/0/1`. The returned tweak is the sum of
+    /// every step's `il`, so a single secp256k1_tweak_key call is enough.
+    pub fn derive_path(&self, path: &str) -> Result<(Self, Scalar<Secp256k1>), Error> {
+        let mut xpub = self.clone();
+        let mut tweak = Scalar::<Secp256k1>::zero();
+        for index in parse_path(path)? {
+            let (child, il) = xpub.derive_child(index)?;
+            xpub = child;
+            tweak = tweak + il;
+        }
+        Ok((xpub, tweak))
+    }
+}
+
+/// Public key the quorum ends up signing for: the master key itself, or its
+/// child at `path` together with the tweak for secp256k1_tweak_key.
+pub fn derive_public_key(
+    public_key: &Point<Secp256k1>,
+    chain_code: Option<[u8; 32]>,
+    path: Option<&str>,
+) -> Result<(Point<Secp256k1>, Option<Scalar<Secp256k1>>), Error> {
+    match (chain_code, path) {
+        (_, None) => Ok((public_key.clone(), None)),
+        (Some(chain_code), Some(path)) => {
+            let master = ExtendedPublicKey::new(public_key.clone(), chain_code, Network::Mainnet);
+            let (child, il) = master.derive_path(path)?;
+            Ok((child.public_key, Some(il)))
+        }
+        (None, Some(_)) => Err(Error::InvalidExtendedKey("a chain code is needed to derive")),
+    }
+}
+
+/// One party's contribution to the shared chain code. The commitment is
+/// broadcast alongside bc1 in keygen stage 1 and this is revealed with decom1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ChainCodeDecommitment {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub chain_code_share: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub blind_factor: BigInt,
+}
+
+pub fn commit_chain_code_share() -> (BigInt, ChainCodeDecommitment) {
+    let chain_code_share = BigInt::sample(256);
+    let (com, blind_factor) = HashCommitment::<Sha256>::create_commitment(&chain_code_share);
+    (
+        com,
+        ChainCodeDecommitment {
+            chain_code_share,
+            blind_factor,
+        },
+    )
+}
+
+/// Blames the party at position `i` of the keygen broadcast vectors.
+fn chain_code_blame(i: usize) -> Error {
+    Error::Blame {
+        reason: "chain code decommitment does not match".to_string(),
+        parties: vec![i as u16 + 1],
+    }
+}
+
+/// Checks every party's reveal against its commitment and hashes all shares,
+/// in party order, into the chain code. No single party controls the result
+/// as long as one of them sampled its share honestly. A reveal that does not
+/// match blames its sender, like the GG20 commitment checks of keygen stage 2.
+pub fn combine_chain_code_shares(
+    coms: &[BigInt],
+    decoms: &[ChainCodeDecommitment],
+) -> Result<[u8; 32], Error> {
+    if coms.len() != decoms.len() {
+        return Err(chain_code_blame(coms.len().min(decoms.len())));
+    }
+    let mut hasher = Sha256::new();
+    for (i, (com, decom)) in coms.iter().zip(decoms).enumerate() {
+        let expected = HashCommitment::<Sha256>::create_commitment_with_user_defined_randomness(
+            &decom.chain_code_share,
+            &decom.blind_factor,
+        );
+        let share = decom.chain_code_share.to_bytes();
+        if &expected != com || share.len() > 32 {
+            return Err(chain_code_blame(i));
+        }
+        hasher.update(&[0; 32][share.len()..]);
+        hasher.update(&share);
+    }
+    let mut chain_code = [0; 32];
+    chain_code.copy_from_slice(&hasher.finalize());
+    Ok(chain_code)
+}
+
+pub fn parse_path(path: &str) -> Result<Vec<u32>, Error> {
+    if path.is_empty() || path == "m" {
+        return Ok(Vec::new());
+    }
+    let mut segments = path.split('/').peekable();
+    if segments.peek() == Some(&"m") {
+        segments.next();
+    }
+    segments
+        .map(|segment| {
+            let (number, hardened) = match segment.strip_suffix(|c: char| c == '\'' || c == 'h') {
+                Some(number) => (number, true),
+                None => (segment, false),
+            };
+            let index: u32 = number
+                .parse()
+                .ok()
+                .filter(|&index| index < HARDENED_OFFSET)
+                .ok_or_else(|| Error::InvalidDerivationPath(path.to_string()))?;
+            Ok(if hardened { index + HARDENED_OFFSET } else { index })
+        })
+        .collect()
+}
+
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    let mut hash = [0; 20];
+    hash.copy_from_slice(&Ripemd160::digest(&Sha256::digest(data)));
+    hash
+}
@@ -0,0 +1,269 @@
+//! Auxiliary info (Fig. 6, without the key refresh) over all n parties of a
+//! GG20 key. Round 1 broadcasts a commitment to this party's modulus,
+//! ring-Pedersen parameters and a random share of rid, round 2 opens it.
+//! Round 3 proves the modulus a Paillier-Blum one (Π^mod) and, to each
+//! verifier, free of small factors (Π^fac). Those proofs are bound to rid,
+//! the XOR of every party's share, which no party controls.
+
+use curv::arithmetic::{Converter, Modulo, Samplable};
+use curv::elliptic::curves::secp256_k1::Secp256k1;
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::{Deserialize, Serialize};
+
+use super::paillier::PaillierKey;
+use super::zk::{self, AuxInfo, FacProof, ModProof, PrmProof, Transcript};
+use super::{blame, slot, Secp256k1Cggmp21KeyShare};
+use crate::error::Error;
+use crate::parameters;
+use crate::party::PartyIndex;
+use crate::secret::Secret;
+use crate::signature;
+use crate::validate::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21AuxInfoStage1Input {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub use_safe_prime: bool,
+}
+
+/// What a party opens in round 2.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AuxInfoDecommitment {
+    pub aux_info: AuxInfo,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::PrmProof"))]
+    pub prm_proof: PrmProof,
+    pub rid: [u8; 32],
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub blind: BigInt,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21AuxInfoStage1Output {
+    #[cfg_attr(feature = "schema", schemars(with = "PaillierKey"))]
+    pub paillier_key: Secret<PaillierKey>,
+    /// Broadcast in round 1.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub commitment: BigInt,
+    /// Broadcast in round 2, once every party's commitment has arrived.
+    pub decommitment: AuxInfoDecommitment,
+}
+
+/// The transcript every aux info proof and commitment starts from, bound to
+/// the key.
+fn session(local_key: &LocalKey<Secp256k1>) -> Transcript {
+    let mut transcript = Transcript::new("cggmp21 aux info")
+        .bytes(&local_key.t.to_be_bytes())
+        .bytes(&local_key.n.to_be_bytes())
+        .point(&local_key.y_sum_s);
+    for pk in &local_key.pk_vec {
+        transcript = transcript.point(pk);
+    }
+    transcript
+}
+
+fn commit(session: &Transcript, party: PartyIndex, decommitment: &AuxInfoDecommitment) -> BigInt {
+    let digest = session
+        .clone()
+        .bytes(b"commitment")
+        .party(party)
+        .aux_info(&decommitment.aux_info)
+        .bytes(&decommitment.rid)
+        .int(&decommitment.blind)
+        .digest();
+    BigInt::from_bytes(&digest)
+}
+
+/// Samples this party's Paillier key and ring-Pedersen parameters and
+/// commits to them.
+pub fn aux_info_stage1(
+    input: &Secp256k1Cggmp21AuxInfoStage1Input,
+) -> Result<Secp256k1Cggmp21AuxInfoStage1Output, Error> {
+    input.validate()?;
+    let party = PartyIndex::of(&input.local_key);
+    let paillier_key = PaillierKey::generate(input.use_safe_prime);
+    let n = paillier_key.n();
+    let phi = paillier_key.phi();
+    // t is a random square and s = t^lambda, so both generate the same
+    // subgroup of the quadratic residues
+    let r = zk::sample_unit(&n);
+    let t = BigInt::mod_mul(&r, &r, &n);
+    let lambda = BigInt::sample_below(&phi);
+    let aux_info = AuxInfo {
+        s: BigInt::mod_pow(&t, &lambda, &n),
+        t,
+        n,
+    };
+
+    let session = session(&input.local_key);
+    let prm_proof = PrmProof::prove(session.clone().party(party), &aux_info, &lambda, &phi);
+    let decommitment = AuxInfoDecommitment {
+        aux_info,
+        prm_proof,
+        rid: signature::to_32_bytes(&BigInt::sample(256)),
+        blind: BigInt::sample(256),
+    };
+    Ok(Secp256k1Cggmp21AuxInfoStage1Output {
+        paillier_key: Secret::new(paillier_key),
+        commitment: commit(&session, party, &decommitment),
+        decommitment,
+    })
+}
+
+/// Checks every party's decommitment against its commitment and its
+/// parameters and Π^prm, blaming the parties that fail. Returns rid.
+fn check_decommitments(
+    session: &Transcript,
+    local_key: &LocalKey<Secp256k1>,
+    paillier_key: &PaillierKey,
+    commitments: &[BigInt],
+    decommitments: &[AuxInfoDecommitment],
+) -> Result<[u8; 32], Error> {
+    let own = PartyIndex::of(local_key);
+    if decommitments[own.offset()].aux_info.n != paillier_key.n() {
+        return Err(Error::InvalidInput(format!(
+            "decommitments[{}] is not this party's own",
+            own.offset()
+        )));
+    }
+    let issues = commitments
+        .iter()
+        .zip(decommitments)
+        .enumerate()
+        .map(|(offset, (commitment, decommitment))| {
+            let party = PartyIndex(offset as u16 + 1);
+            let aux = &decommitment.aux_info;
+            let mut issues = Vec::new();
+            if commit(session, party, decommitment) != *commitment {
+                issues.push("decommitment does not match its commitment".to_string());
+            }
+            parameters::modulus_issues("Paillier modulus", &aux.n, &mut issues);
+            parameters::ring_pedersen_issues(&aux.n, [("s", &aux.s), ("t", &aux.t)], &mut issues);
+            if issues.is_empty() && !decommitment.prm_proof.verify(session.clone().party(party), aux) {
+                issues.push("s/t proof does not verify".to_string());
+            }
+            (party, issues)
+        })
+        .collect();
+    parameters::check(issues)?;
+
+    let mut rid = [0; 32];
+    for decommitment in decommitments {
+        rid.iter_mut().zip(&decommitment.rid).for_each(|(a, b)| *a ^= b);
+    }
+    Ok(rid)
+}
+
+/// The keygen indices of every party but `own`.
+fn others(local_key: &LocalKey<Secp256k1>) -> impl Iterator<Item = PartyIndex> {
+    let own = local_key.i;
+    (1..=local_key.n).filter(move |&i| i != own).map(PartyIndex)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21AuxInfoStage2Input {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "PaillierKey"))]
+    pub paillier_key: Secret<PaillierKey>,
+    /// Every party's round 1 commitment, by keygen index.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::BigInt>"))]
+    pub commitments: Vec<BigInt>,
+    /// Every party's round 2 decommitment, by keygen index.
+    pub decommitments: Vec<AuxInfoDecommitment>,
+}
+
+/// Broadcast in round 3.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21AuxInfoStage2Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::ModProof"))]
+    pub mod_proof: ModProof,
+    /// One for every other party, by keygen index.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::FacProof>"))]
+    pub fac_proofs: Vec<FacProof>,
+}
+
+/// Checks the opened parameters and proves this party's modulus well formed.
+pub fn aux_info_stage2(
+    input: &Secp256k1Cggmp21AuxInfoStage2Input,
+) -> Result<Secp256k1Cggmp21AuxInfoStage2Output, Error> {
+    input.validate()?;
+    let session = session(&input.local_key);
+    let rid = check_decommitments(
+        &session,
+        &input.local_key,
+        &input.paillier_key,
+        &input.commitments,
+        &input.decommitments,
+    )?;
+    let session = session.bytes(&rid);
+    let own = PartyIndex::of(&input.local_key);
+    let mod_proof = ModProof::prove(session.clone().party(own), &input.paillier_key);
+    let fac_proofs = others(&input.local_key)
+        .map(|party| {
+            let aux = &input.decommitments[party.offset()].aux_info;
+            FacProof::prove(session.clone().party(own).party(party), &input.paillier_key, aux)
+        })
+        .collect();
+    Ok(Secp256k1Cggmp21AuxInfoStage2Output { mod_proof, fac_proofs })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21AuxInfoFinalizeInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "PaillierKey"))]
+    pub paillier_key: Secret<PaillierKey>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::BigInt>"))]
+    pub commitments: Vec<BigInt>,
+    pub decommitments: Vec<AuxInfoDecommitment>,
+    /// Every party's Π^mod, by keygen index.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::ModProof>"))]
+    pub mod_proofs: Vec<ModProof>,
+    /// The Π^fac every other party made for this one, by keygen index.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::FacProof>"))]
+    pub fac_proofs: Vec<FacProof>,
+}
+
+/// Checks every party's proofs and assembles the key share to save. The
+/// decommitments are checked again, so a saved key share never holds
+/// unverified parameters.
+pub fn aux_info_finalize(
+    input: &Secp256k1Cggmp21AuxInfoFinalizeInput,
+) -> Result<Secp256k1Cggmp21KeyShare, Error> {
+    input.validate()?;
+    let session = session(&input.local_key);
+    let rid = check_decommitments(
+        &session,
+        &input.local_key,
+        &input.paillier_key,
+        &input.commitments,
+        &input.decommitments,
+    )?;
+    let session = session.bytes(&rid);
+    let own = PartyIndex::of(&input.local_key);
+    let own_aux = &input.decommitments[own.offset()].aux_info;
+
+    let failed = others(&input.local_key)
+        .filter(|&party| {
+            let n = &input.decommitments[party.offset()].aux_info.n;
+            let fac_proof = &input.fac_proofs[slot(own.offset(), party.offset())];
+            !input.mod_proofs[party.offset()].verify(session.clone().party(party), n)
+                || !fac_proof.verify(session.clone().party(party).party(own), n, own_aux)
+        })
+        .collect();
+    blame("Paillier modulus proofs do not verify", failed)?;
+
+    Ok(Secp256k1Cggmp21KeyShare {
+        local_key: input.local_key.clone(),
+        paillier_key: input.paillier_key.clone(),
+        aux_infos: input.decommitments.iter().map(|d| d.aux_info.clone()).collect(),
+    })
+}
@@ -0,0 +1,116 @@
+//! CGGMP21 threshold ECDSA (Canetti, Gennaro, Goldfeder, Makriyannis and
+//! Peled, "UC Non-Interactive, Proactive, Threshold ECDSA with Identifiable
+//! Aborts", ePrint 2021/060) on the GG20 key shares, as stage functions like
+//! the GG20 ones. Figure numbers refer to that paper.
+//!
+//! - Auxiliary info (`aux_info_stage1`, `aux_info_stage2`,
+//!   `aux_info_finalize`) runs once per key. Every party publishes a fresh
+//!   Paillier-Blum modulus and ring-Pedersen parameters and proves them well
+//!   formed. The ECDSA shares of the GG20 `LocalKey` are kept as they are,
+//!   which is how an existing GG20 key moves to CGGMP21.
+//! - Presigning (`presign_stage1` to `presign_stage3`, then
+//!   `presign_output`) does not depend on the message.
+//! - Signing is one round: every party publishes its `sign` output and any
+//!   of them runs `sign_combine`.
+//!
+//! Every presigning message is broadcast in full, the proofs meant for a
+//! single verifier included, so all parties judge a failure from the same
+//! data. The paper proves δ_i and σ_i correct only after a check on them
+//! fails. Here the round 3 message carries the proofs for δ_i from the
+//! start, and commits to χ_i as S_i = χ_i * Γ. `presign_output` then blames
+//! whoever sent a bad δ_i or S_i, and `sign_combine` blames a bad σ_i
+//! without another round. Key refresh is not implemented.
+//!
+//! A presignature must sign one message only: two signatures from the same
+//! presignature reveal the key.
+
+mod aux_info;
+mod paillier;
+mod presign;
+mod sign;
+pub mod zk;
+
+pub use self::aux_info::*;
+pub use self::paillier::PaillierKey;
+pub use self::presign::*;
+pub use self::sign::*;
+pub use self::zk::AuxInfo;
+
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::party::PartyIndex;
+use crate::secret::Secret;
+
+/// A GG20 key share with the auxiliary info of every party, as
+/// `aux_info_finalize` returns it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21KeyShare {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "PaillierKey"))]
+    pub paillier_key: Secret<PaillierKey>,
+    /// Every party's modulus and ring-Pedersen parameters, by keygen index.
+    pub aux_infos: Vec<AuxInfo>,
+}
+
+impl Secp256k1Cggmp21KeyShare {
+    fn party(&self) -> PartyIndex {
+        PartyIndex::of(&self.local_key)
+    }
+
+    fn aux_info(&self, party: PartyIndex) -> &AuxInfo {
+        &self.aux_infos[party.offset()]
+    }
+
+    /// The Lagrange coefficient of `party` in `parties`, which turns its
+    /// Shamir share into an additive one.
+    fn lagrange(party: PartyIndex, parties: &[PartyIndex]) -> Scalar<Secp256k1> {
+        let x = |p: PartyIndex| Scalar::<Secp256k1>::from_bigint(&BigInt::from(u32::from(p.0)));
+        parties
+            .iter()
+            .filter(|&&other| other != party)
+            .fold(Scalar::from_bigint(&BigInt::from(1)), |acc, &other| {
+                let denominator = (x(other) - x(party)).invert().expect("parties are distinct");
+                acc * x(other) * denominator
+            })
+    }
+
+    /// The additive share of `party` of the public key, for the quorum
+    /// `parties`.
+    fn public_share(&self, party: PartyIndex, parties: &[PartyIndex]) -> Point<Secp256k1> {
+        &self.local_key.pk_vec[party.offset()] * &Self::lagrange(party, parties)
+    }
+
+    /// This party's additive share of the private key, for the quorum
+    /// `parties`.
+    fn share(&self, parties: &[PartyIndex]) -> Scalar<Secp256k1> {
+        &self.local_key.keys_linear.x_i * &Self::lagrange(self.party(), parties)
+    }
+}
+
+/// Where a vector of one entry per counterparty of `from` holds the entry
+/// for `to`. Such vectors skip `from` itself, both are positions in the same
+/// ordering.
+fn slot(from: usize, to: usize) -> usize {
+    if to < from {
+        to
+    } else {
+        to - 1
+    }
+}
+
+/// Blames the parties in `failed`, if any.
+fn blame(reason: &str, failed: Vec<PartyIndex>) -> Result<(), Error> {
+    if failed.is_empty() {
+        return Ok(());
+    }
+    Err(Error::Blame {
+        reason: reason.to_string(),
+        parties: failed.iter().map(|p| p.0).collect(),
+    })
+}
@@ -0,0 +1,184 @@
+//! Paillier encryption with signed plaintexts. The CGGMP21 MtA masks with
+//! negative values and every decryption takes the representative in
+//! (-N/2, N/2], so this works on curv's `BigInt` directly instead of the
+//! unsigned scheme GG20 uses.
+
+use curv::arithmetic::{BitManipulation, Modulo, NumberTests, Samplable};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::zk;
+use crate::parameters;
+use crate::secret::Wipe;
+
+/// Bits of each prime factor, so N has 2048 bits.
+pub const PRIME_BITS: usize = 1024;
+
+/// Miller-Rabin rounds per candidate prime.
+const MILLER_RABIN_ROUNDS: usize = 40;
+
+/// A Paillier-Blum secret key: N = pq with p and q both 3 mod 4, as Π^mod
+/// requires.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PaillierKey {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub p: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub q: BigInt,
+}
+
+impl Wipe for PaillierKey {
+    fn wipe(&mut self) {
+        self.p.wipe();
+        self.q.wipe();
+    }
+}
+
+impl PaillierKey {
+    /// Safe primes, as GG20 keygen can use, take minutes to find. Plain
+    /// Blum primes are enough for the proofs and take about a second.
+    pub fn generate(use_safe_prime: bool) -> Self {
+        loop {
+            let p = sample_prime(PRIME_BITS, use_safe_prime);
+            let q = sample_prime(PRIME_BITS, use_safe_prime);
+            if p != q {
+                return PaillierKey { p, q };
+            }
+        }
+    }
+
+    pub fn n(&self) -> BigInt {
+        &self.p * &self.q
+    }
+
+    pub fn phi(&self) -> BigInt {
+        let one = BigInt::from(1);
+        (&self.p - &one) * (&self.q - &one)
+    }
+
+    /// The plaintext m of `c` and the randomness rho with
+    /// c = (1 + N)^m rho^N mod N^2. Opening a ciphertext lets a party prove
+    /// statements about sums of ciphertexts it did not encrypt itself.
+    pub fn open(&self, c: &BigInt) -> (BigInt, BigInt) {
+        let n = self.n();
+        let nn = &n * &n;
+        let phi = self.phi();
+        let l = (BigInt::mod_pow(c, &phi, &nn) - BigInt::from(1)) / &n;
+        let phi_inv = BigInt::mod_inv(&phi, &n).expect("p and q have the same size, so phi is a unit mod N");
+        let m = centered(&BigInt::mod_mul(&l, &phi_inv, &n), &n);
+        let n_inv = BigInt::mod_inv(&n, &phi).expect("N is a unit mod phi");
+        let rho = BigInt::mod_pow(&BigInt::modulus(c, &n), &n_inv, &n);
+        (m, rho)
+    }
+
+    pub fn decrypt(&self, c: &BigInt) -> BigInt {
+        self.open(c).0
+    }
+}
+
+/// (1 + N)^m rho^N mod N^2 for a plaintext of either sign.
+pub fn encrypt_with(n: &BigInt, m: &BigInt, rho: &BigInt) -> BigInt {
+    let nn = n * n;
+    // (1 + N)^m = 1 + mN mod N^2
+    let g_m = BigInt::modulus(&(BigInt::from(1) + m * n), &nn);
+    BigInt::mod_mul(&g_m, &BigInt::mod_pow(rho, n, &nn), &nn)
+}
+
+/// Encrypts `m` under `n` with fresh randomness, which is returned too.
+pub fn encrypt(n: &BigInt, m: &BigInt) -> (BigInt, BigInt) {
+    let rho = zk::sample_unit(n);
+    (encrypt_with(n, m, &rho), rho)
+}
+
+/// c1 * c2, whose plaintext is the sum.
+pub fn add(n: &BigInt, c1: &BigInt, c2: &BigInt) -> BigInt {
+    BigInt::mod_mul(c1, c2, &(n * n))
+}
+
+/// c1 / c2, whose plaintext is the difference. None if c2 is not a unit.
+pub fn sub(n: &BigInt, c1: &BigInt, c2: &BigInt) -> Option<BigInt> {
+    let nn = n * n;
+    Some(BigInt::mod_mul(c1, &BigInt::mod_inv(c2, &nn)?, &nn))
+}
+
+/// c^k, whose plaintext is k times that of c. None if k is negative and c
+/// not a unit.
+pub fn scale(n: &BigInt, c: &BigInt, k: &BigInt) -> Option<BigInt> {
+    zk::pow(c, k, &(n * n))
+}
+
+/// The representative of `x` mod `n` in (-n/2, n/2].
+fn centered(x: &BigInt, n: &BigInt) -> BigInt {
+    let x = BigInt::modulus(x, n);
+    if &x + &x > *n {
+        x - n
+    } else {
+        x
+    }
+}
+
+/// A random prime of exactly `bits` bits that is 3 mod 4. A safe prime
+/// 2p' + 1 is tested through p', which must then be odd.
+fn sample_prime(bits: usize, safe: bool) -> BigInt {
+    let small_primes: Vec<BigInt> = parameters::small_primes().map(BigInt::from).collect();
+    let sieved = |n: &BigInt| {
+        small_primes
+            .iter()
+            .all(|p| n == p || !BigInt::modulus(n, p).is_zero())
+    };
+    loop {
+        let mut p = BigInt::sample(bits);
+        // The top two bits make the product of two primes a full 2 * bits
+        // bits, the bottom two make the prime 3 mod 4
+        p.set_bit(bits - 1, true);
+        p.set_bit(bits - 2, true);
+        p.set_bit(1, true);
+        p.set_bit(0, true);
+        if !sieved(&p) {
+            continue;
+        }
+        if safe {
+            let half = &p >> 1;
+            if !sieved(&half) || !is_probable_prime(&half) {
+                continue;
+            }
+        }
+        if is_probable_prime(&p) {
+            return p;
+        }
+    }
+}
+
+pub(crate) fn is_probable_prime(n: &BigInt) -> bool {
+    let one = BigInt::from(1);
+    let two = BigInt::from(2);
+    if n < &BigInt::from(4) {
+        return n == &two || n == &BigInt::from(3);
+    }
+    if !n.test_bit(0) {
+        return false;
+    }
+    let n_minus_one = n - &one;
+    let mut d = n_minus_one.clone();
+    let mut r = 0;
+    while !d.test_bit(0) {
+        d = d >> 1;
+        r += 1;
+    }
+    'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+        let a = BigInt::sample_range(&two, &n_minus_one);
+        let mut x = BigInt::mod_pow(&a, &d, n);
+        if x == one || x == n_minus_one {
+            continue;
+        }
+        for _ in 1..r {
+            x = BigInt::mod_mul(&x, &x, n);
+            if x == n_minus_one {
+                continue 'witness;
+            }
+        }
+        return false;
+    }
+    true
+}
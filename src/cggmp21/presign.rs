@@ -0,0 +1,683 @@
+//! Three-round presigning (Fig. 7) among a quorum of t + 1 or more parties.
+//!
+//! Vectors of per-party messages follow the order of `parties` and include
+//! this party's own message. Vectors of proofs or MtA messages a party makes
+//! for each counterparty follow the same order and skip the party itself.
+
+use curv::arithmetic::Samplable;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::paillier;
+use super::zk::{
+    self, AffGProof, AffGStatement, AffGWitness, AuxInfo, EncProof, LogStarProof, LogStarStatement, MulProof,
+    MulStarProof, MulStarStatement, MulStatement, Transcript, L, L_PRIME, L_SUM,
+};
+use super::{blame, slot, Secp256k1Cggmp21KeyShare};
+use crate::error::Error;
+use crate::party::{own_position, PartyIndex};
+use crate::secret::{Secret, Wipe};
+use crate::validate::Validate;
+
+/// The transcript every presigning proof starts from, bound to the key and
+/// the quorum.
+fn session(key_share: &Secp256k1Cggmp21KeyShare, parties: &[PartyIndex]) -> Transcript {
+    let local_key = &key_share.local_key;
+    let mut transcript = Transcript::new("cggmp21 presign")
+        .point(&local_key.y_sum_s)
+        .bytes(&local_key.t.to_be_bytes())
+        .bytes(&local_key.n.to_be_bytes());
+    for &party in parties {
+        transcript = transcript.party(party).aux_info(key_share.aux_info(party));
+    }
+    transcript
+}
+
+/// The transcript of a proof `prover` makes for `verifier`.
+fn transcript(session: &Transcript, prover: PartyIndex, verifier: PartyIndex, purpose: &[u8]) -> Transcript {
+    session.clone().party(prover).party(verifier).bytes(purpose)
+}
+
+/// Position and index of every party in `parties` but the one at `own`.
+fn counterparties(parties: &[PartyIndex], own: usize) -> impl Iterator<Item = (usize, PartyIndex)> + '_ {
+    parties.iter().copied().enumerate().filter(move |&(position, _)| position != own)
+}
+
+/// The secret nonces k_i and gamma_i and the randomness of their ciphertexts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresignNonces {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub k: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub gamma: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub rho: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub nu: BigInt,
+}
+
+impl Wipe for PresignNonces {
+    fn wipe(&mut self) {
+        self.k.wipe();
+        self.gamma.wipe();
+        self.rho.wipe();
+        self.nu.wipe();
+    }
+}
+
+impl PresignNonces {
+    /// K_i and G_i, the encryptions of k_i and gamma_i under N_i.
+    fn ciphertexts(&self, n: &BigInt) -> (BigInt, BigInt) {
+        (paillier::encrypt_with(n, &self.k, &self.rho), paillier::encrypt_with(n, &self.gamma, &self.nu))
+    }
+}
+
+/// Broadcast in round 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresignMessage1 {
+    /// K_i, the encryption of k_i under N_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub k: BigInt,
+    /// G_i, the encryption of gamma_i under N_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub g: BigInt,
+    /// Π^enc for K_i, one for every counterparty.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::EncProof>"))]
+    pub enc_proofs: Vec<EncProof>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage1Input {
+    pub parties: Vec<PartyIndex>,
+    pub key_share: Secp256k1Cggmp21KeyShare,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage1Output {
+    #[cfg_attr(feature = "schema", schemars(with = "PresignNonces"))]
+    pub nonces: Secret<PresignNonces>,
+    pub message: PresignMessage1,
+}
+
+pub fn presign_stage1(
+    input: &Secp256k1Cggmp21PresignStage1Input,
+) -> Result<Secp256k1Cggmp21PresignStage1Output, Error> {
+    input.validate()?;
+    let key_share = &input.key_share;
+    let own = key_share.party();
+    let own_position = own_position(&input.parties, &key_share.local_key)?.0;
+    let n = &key_share.aux_info(own).n;
+    let order = Scalar::<Secp256k1>::group_order();
+    let nonces = PresignNonces {
+        k: BigInt::sample_below(order),
+        gamma: BigInt::sample_below(order),
+        rho: zk::sample_unit(n),
+        nu: zk::sample_unit(n),
+    };
+    let (k, g) = nonces.ciphertexts(n);
+
+    let session = session(key_share, &input.parties);
+    let enc_proofs = counterparties(&input.parties, own_position)
+        .map(|(_, party)| {
+            let transcript = transcript(&session, own, party, b"enc");
+            EncProof::prove(transcript, n, &k, key_share.aux_info(party), &nonces.k, &nonces.rho)
+        })
+        .collect();
+    Ok(Secp256k1Cggmp21PresignStage1Output {
+        nonces: Secret::new(nonces),
+        message: PresignMessage1 { k, g, enc_proofs },
+    })
+}
+
+/// A party's MtA responses to one counterparty's K_j, for gamma_i and for
+/// its additive key share w_i. D encrypts k_j times the secret plus a mask
+/// beta under N_j, F encrypts beta under N_i.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct MtaMessage {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub d: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub f: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub d_hat: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub f_hat: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::AffGProof"))]
+    pub aff_g_proof: AffGProof,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::AffGProof"))]
+    pub aff_g_hat_proof: AffGProof,
+    /// Π^log* that G_i encrypts the gamma_i of Gamma_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LogStarProof"))]
+    pub log_star_proof: LogStarProof,
+}
+
+/// Broadcast in round 2.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresignMessage2 {
+    /// Gamma_i = gamma_i * G.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub gamma: Point<Secp256k1>,
+    /// One for every counterparty.
+    pub mtas: Vec<MtaMessage>,
+}
+
+/// One MtA response under the verifier's `n_j`: D = K_j^x enc_j(beta) and
+/// F = enc_i(beta), with the Π^aff-g that ties them to X = x * G.
+fn mta(
+    transcript: Transcript,
+    n_j: &BigInt,
+    n_i: &BigInt,
+    k_j: &BigInt,
+    aux_j: &AuxInfo,
+    x: &BigInt,
+    x_point: &Point<Secp256k1>,
+) -> (BigInt, BigInt, AffGProof) {
+    let beta = zk::sample_signed(L_PRIME);
+    let s = zk::sample_unit(n_j);
+    let (f, r) = paillier::encrypt(n_i, &beta);
+    let product = paillier::scale(n_j, k_j, x).expect("x is not negative");
+    let d = paillier::add(n_j, &product, &paillier::encrypt_with(n_j, &beta, &s));
+    let statement = AffGStatement {
+        n0: n_j,
+        n1: n_i,
+        c: k_j,
+        d: &d,
+        y: &f,
+        x: x_point,
+        aux: aux_j,
+    };
+    let witness = AffGWitness {
+        x,
+        y: &beta,
+        rho: &s,
+        rho_y: &r,
+    };
+    let proof = AffGProof::prove(transcript, &statement, &witness);
+    (d, f, proof)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage2Input {
+    pub parties: Vec<PartyIndex>,
+    pub key_share: Secp256k1Cggmp21KeyShare,
+    #[cfg_attr(feature = "schema", schemars(with = "PresignNonces"))]
+    pub nonces: Secret<PresignNonces>,
+    pub messages1: Vec<PresignMessage1>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage2Output {
+    pub message: PresignMessage2,
+}
+
+/// Checks every counterparty's Π^enc and answers its K_j.
+pub fn presign_stage2(
+    input: &Secp256k1Cggmp21PresignStage2Input,
+) -> Result<Secp256k1Cggmp21PresignStage2Output, Error> {
+    input.validate()?;
+    let key_share = &input.key_share;
+    let parties = &input.parties;
+    let own = key_share.party();
+    let own_position = own_position(parties, &key_share.local_key)?.0;
+    let own_aux = key_share.aux_info(own);
+    let n_i = &own_aux.n;
+    let session = session(key_share, parties);
+
+    let failed = counterparties(parties, own_position)
+        .filter(|&(position, party)| {
+            let message = &input.messages1[position];
+            let proof = &message.enc_proofs[slot(position, own_position)];
+            let n_j = &key_share.aux_info(party).n;
+            !proof.verify(transcript(&session, party, own, b"enc"), n_j, &message.k, own_aux)
+        })
+        .map(|(_, party)| party)
+        .collect();
+    blame("encryption proofs do not verify", failed)?;
+
+    let nonces = &input.nonces;
+    let (_, g_i) = nonces.ciphertexts(n_i);
+    let generator = Point::<Secp256k1>::generator().to_point();
+    let gamma = &generator * &zk::scalar(&nonces.gamma);
+    let w = key_share.share(parties).to_bigint();
+    let w_point = key_share.public_share(own, parties);
+    let mtas = counterparties(parties, own_position)
+        .map(|(position, party)| {
+            let aux_j = key_share.aux_info(party);
+            let k_j = &input.messages1[position].k;
+            let (d, f, aff_g_proof) = mta(
+                transcript(&session, own, party, b"aff-g"),
+                &aux_j.n,
+                n_i,
+                k_j,
+                aux_j,
+                &nonces.gamma,
+                &gamma,
+            );
+            let (d_hat, f_hat, aff_g_hat_proof) = mta(
+                transcript(&session, own, party, b"aff-g hat"),
+                &aux_j.n,
+                n_i,
+                k_j,
+                aux_j,
+                &w,
+                &w_point,
+            );
+            let statement = LogStarStatement {
+                n0: n_i,
+                c: &g_i,
+                x: &gamma,
+                base: &generator,
+                aux: aux_j,
+                bits: L,
+            };
+            let transcript = transcript(&session, own, party, b"log* gamma");
+            MtaMessage {
+                d,
+                f,
+                d_hat,
+                f_hat,
+                aff_g_proof,
+                aff_g_hat_proof,
+                log_star_proof: LogStarProof::prove(transcript, &statement, &nonces.gamma, &nonces.nu),
+            }
+        })
+        .collect();
+    Ok(Secp256k1Cggmp21PresignStage2Output {
+        message: PresignMessage2 { gamma, mtas },
+    })
+}
+
+/// The ciphertext under `n` of the party at `position` that its delta_i
+/// (or, with `hat`, chi_i) is decrypted from: its own product times every
+/// D it received, over every F it sent. None if an F is not a unit.
+fn share_ciphertext(
+    n: &BigInt,
+    product: &BigInt,
+    position: usize,
+    messages2: &[PresignMessage2],
+    hat: bool,
+) -> Option<BigInt> {
+    let mut c = product.clone();
+    for other in (0..messages2.len()).filter(|&other| other != position) {
+        let received = &messages2[other].mtas[slot(other, position)];
+        let sent = &messages2[position].mtas[slot(position, other)];
+        let (d, f) = if hat {
+            (&received.d_hat, &sent.f_hat)
+        } else {
+            (&received.d, &sent.f)
+        };
+        c = paillier::sub(n, &paillier::add(n, &c, d), f)?;
+    }
+    Some(c)
+}
+
+/// Gamma, the sum of every party's Gamma_i.
+fn gamma_sum(messages2: &[PresignMessage2]) -> Point<Secp256k1> {
+    messages2.iter().fold(Point::zero(), |sum, message| sum + &message.gamma)
+}
+
+/// What a party proves to one counterparty in round 3.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresignProofs {
+    /// Π^log* that Delta_i = k_i * Gamma for the k_i of K_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LogStarProof"))]
+    pub delta_point_proof: LogStarProof,
+    /// Π^log* that delta_i is the plaintext of this party's delta
+    /// ciphertext.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LogStarProof"))]
+    pub delta_proof: LogStarProof,
+    /// Π^mul* that H-hat_i encrypts k_i times the additive key share w_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::MulStarProof"))]
+    pub h_hat_proof: MulStarProof,
+    /// Π^log* that S_i = chi_i * Gamma for the plaintext chi_i of this
+    /// party's chi ciphertext.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LogStarProof"))]
+    pub s_proof: LogStarProof,
+}
+
+/// Broadcast in round 3.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PresignMessage3 {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub delta: Scalar<Secp256k1>,
+    /// Delta_i = k_i * Gamma.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub delta_point: Point<Secp256k1>,
+    /// S_i = chi_i * Gamma.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub s: Point<Secp256k1>,
+    /// H_i, the encryption of k_i * gamma_i under N_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub h: BigInt,
+    /// Π^mul for H_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::MulProof"))]
+    pub mul_proof: MulProof,
+    /// H-hat_i, the encryption of k_i * w_i under N_i.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub h_hat: BigInt,
+    /// One for every counterparty.
+    pub proofs: Vec<PresignProofs>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage3Input {
+    pub parties: Vec<PartyIndex>,
+    pub key_share: Secp256k1Cggmp21KeyShare,
+    #[cfg_attr(feature = "schema", schemars(with = "PresignNonces"))]
+    pub nonces: Secret<PresignNonces>,
+    pub messages1: Vec<PresignMessage1>,
+    pub messages2: Vec<PresignMessage2>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignStage3Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub chi: Secret<Scalar<Secp256k1>>,
+    pub message: PresignMessage3,
+}
+
+/// Checks the MtA responses addressed to this party, decrypts delta_i and
+/// chi_i and proves them correct.
+pub fn presign_stage3(
+    input: &Secp256k1Cggmp21PresignStage3Input,
+) -> Result<Secp256k1Cggmp21PresignStage3Output, Error> {
+    input.validate()?;
+    let key_share = &input.key_share;
+    let parties = &input.parties;
+    let own = key_share.party();
+    let own_position = own_position(parties, &key_share.local_key)?.0;
+    let own_aux = key_share.aux_info(own);
+    let n_i = &own_aux.n;
+    let session = session(key_share, parties);
+    let nonces = &input.nonces;
+    let (k_i, g_i) = nonces.ciphertexts(n_i);
+    let generator = Point::<Secp256k1>::generator().to_point();
+
+    let failed = counterparties(parties, own_position)
+        .filter(|&(position, party)| {
+            let message = &input.messages2[position];
+            let mta = &message.mtas[slot(position, own_position)];
+            let n_j = &key_share.aux_info(party).n;
+            let statement = AffGStatement {
+                n0: n_i,
+                n1: n_j,
+                c: &k_i,
+                d: &mta.d,
+                y: &mta.f,
+                x: &message.gamma,
+                aux: own_aux,
+            };
+            let x_hat = key_share.public_share(party, parties);
+            let hat_statement = AffGStatement {
+                d: &mta.d_hat,
+                y: &mta.f_hat,
+                x: &x_hat,
+                ..statement
+            };
+            let log_star_statement = LogStarStatement {
+                n0: n_j,
+                c: &input.messages1[position].g,
+                x: &message.gamma,
+                base: &generator,
+                aux: own_aux,
+                bits: L,
+            };
+            !mta.aff_g_proof.verify(transcript(&session, party, own, b"aff-g"), &statement)
+                || !mta.aff_g_hat_proof.verify(transcript(&session, party, own, b"aff-g hat"), &hat_statement)
+                || !mta.log_star_proof.verify(transcript(&session, party, own, b"log* gamma"), &log_star_statement)
+        })
+        .map(|(_, party)| party)
+        .collect();
+    blame("MtA proofs do not verify", failed)?;
+
+    let paillier_key = &key_share.paillier_key;
+    let gamma = gamma_sum(&input.messages2);
+    let delta_point = &gamma * &zk::scalar(&nonces.k);
+    let zero = BigInt::from(0);
+
+    // H_i = G_i^k_i and H-hat_i = K_i^w_i, each re-randomized
+    let rho_h = zk::sample_unit(n_i);
+    let h = paillier::add(
+        n_i,
+        &paillier::scale(n_i, &g_i, &nonces.k).expect("k_i is not negative"),
+        &paillier::encrypt_with(n_i, &zero, &rho_h),
+    );
+    let mul_statement = MulStatement {
+        n: n_i,
+        x: &k_i,
+        y: &g_i,
+        c: &h,
+    };
+    let mul_transcript = session.clone().party(own).bytes(b"mul");
+    let mul_proof = MulProof::prove(mul_transcript, &mul_statement, &nonces.k, &rho_h, &nonces.rho);
+    let w = key_share.share(parties).to_bigint();
+    let w_point = key_share.public_share(own, parties);
+    let rho_hat = zk::sample_unit(n_i);
+    let h_hat = paillier::add(
+        n_i,
+        &paillier::scale(n_i, &k_i, &w).expect("w_i is not negative"),
+        &paillier::encrypt_with(n_i, &zero, &rho_hat),
+    );
+
+    let u = share_ciphertext(n_i, &h, own_position, &input.messages2, false)
+        .ok_or_else(|| Error::InvalidInput("messages2 holds a ciphertext that is not a unit".to_string()))?;
+    let w_hat = share_ciphertext(n_i, &h_hat, own_position, &input.messages2, true)
+        .ok_or_else(|| Error::InvalidInput("messages2 holds a ciphertext that is not a unit".to_string()))?;
+    let (y_delta, rho_delta) = paillier_key.open(&u);
+    let (y_chi, rho_chi) = paillier_key.open(&w_hat);
+    let delta = zk::scalar(&y_delta);
+    let chi = zk::scalar(&y_chi);
+    let delta_times_g = &generator * &delta;
+    let s = &gamma * &chi;
+
+    let proofs = counterparties(parties, own_position)
+        .map(|(_, party)| {
+            let aux = key_share.aux_info(party);
+            let log_star = |c, x, base, bits| LogStarStatement {
+                n0: n_i,
+                c,
+                x,
+                base,
+                aux,
+                bits,
+            };
+            let h_hat_statement = MulStarStatement {
+                n0: n_i,
+                c: &k_i,
+                d: &h_hat,
+                x: &w_point,
+                aux,
+            };
+            PresignProofs {
+                delta_point_proof: LogStarProof::prove(
+                    transcript(&session, own, party, b"log* delta point"),
+                    &log_star(&k_i, &delta_point, &gamma, L),
+                    &nonces.k,
+                    &nonces.rho,
+                ),
+                delta_proof: LogStarProof::prove(
+                    transcript(&session, own, party, b"log* delta"),
+                    &log_star(&u, &delta_times_g, &generator, L_SUM),
+                    &y_delta,
+                    &rho_delta,
+                ),
+                h_hat_proof: MulStarProof::prove(
+                    transcript(&session, own, party, b"mul*"),
+                    &h_hat_statement,
+                    &w,
+                    &rho_hat,
+                ),
+                s_proof: LogStarProof::prove(
+                    transcript(&session, own, party, b"log* s"),
+                    &log_star(&w_hat, &s, &gamma, L_SUM),
+                    &y_chi,
+                    &rho_chi,
+                ),
+            }
+        })
+        .collect();
+
+    Ok(Secp256k1Cggmp21PresignStage3Output {
+        chi: Secret::new(chi),
+        message: PresignMessage3 {
+            delta,
+            delta_point,
+            s,
+            h,
+            mul_proof,
+            h_hat,
+            proofs,
+        },
+    })
+}
+
+/// A presignature for one message: what `sign` and `sign_combine` need.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21Presignature {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+    /// Gamma, which `sign_combine` checks partial signatures against.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub gamma: Point<Secp256k1>,
+    /// The signature's R = k^-1 * G.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub r: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub k: Secret<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub chi: Secret<Scalar<Secp256k1>>,
+    /// Every party's Delta_i, in the order of `parties`.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub delta_points: Vec<Point<Secp256k1>>,
+    /// Every party's S_i, in the order of `parties`.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub s_points: Vec<Point<Secp256k1>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21PresignOutputInput {
+    pub parties: Vec<PartyIndex>,
+    pub key_share: Secp256k1Cggmp21KeyShare,
+    #[cfg_attr(feature = "schema", schemars(with = "PresignNonces"))]
+    pub nonces: Secret<PresignNonces>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub chi: Secret<Scalar<Secp256k1>>,
+    pub messages1: Vec<PresignMessage1>,
+    pub messages2: Vec<PresignMessage2>,
+    pub messages3: Vec<PresignMessage3>,
+}
+
+/// Checks every counterparty's round 3 proofs and the sums they add up to.
+/// A party whose proofs fail is blamed. Sums that do not add up although
+/// every proof this party could check holds, as when two colluding parties
+/// feed each other inconsistent MtA messages, fail with a protocol error.
+pub fn presign_output(input: &Secp256k1Cggmp21PresignOutputInput) -> Result<Secp256k1Cggmp21Presignature, Error> {
+    input.validate()?;
+    let key_share = &input.key_share;
+    let parties = &input.parties;
+    let own = key_share.party();
+    let own_position = own_position(parties, &key_share.local_key)?.0;
+    let own_aux = key_share.aux_info(own);
+    let session = session(key_share, parties);
+    let gamma = gamma_sum(&input.messages2);
+    let generator = Point::<Secp256k1>::generator().to_point();
+
+    let failed = counterparties(parties, own_position)
+        .filter(|&(position, party)| {
+            let n_j = &key_share.aux_info(party).n;
+            let message1 = &input.messages1[position];
+            let message = &input.messages3[position];
+            let proofs = &message.proofs[slot(position, own_position)];
+            let mul_statement = MulStatement {
+                n: n_j,
+                x: &message1.k,
+                y: &message1.g,
+                c: &message.h,
+            };
+            if !message.mul_proof.verify(session.clone().party(party).bytes(b"mul"), &mul_statement) {
+                return true;
+            }
+            let u = share_ciphertext(n_j, &message.h, position, &input.messages2, false);
+            let w_hat = share_ciphertext(n_j, &message.h_hat, position, &input.messages2, true);
+            let (u, w_hat) = match (u, w_hat) {
+                (Some(u), Some(w_hat)) => (u, w_hat),
+                _ => return true,
+            };
+            let log_star = |c, x, base, bits| LogStarStatement {
+                n0: n_j,
+                c,
+                x,
+                base,
+                aux: own_aux,
+                bits,
+            };
+            let delta_times_g = &generator * &message.delta;
+            let x_hat = key_share.public_share(party, parties);
+            let h_hat_statement = MulStarStatement {
+                n0: n_j,
+                c: &message1.k,
+                d: &message.h_hat,
+                x: &x_hat,
+                aux: own_aux,
+            };
+            !proofs.delta_point_proof.verify(
+                transcript(&session, party, own, b"log* delta point"),
+                &log_star(&message1.k, &message.delta_point, &gamma, L),
+            ) || !proofs.delta_proof.verify(
+                transcript(&session, party, own, b"log* delta"),
+                &log_star(&u, &delta_times_g, &generator, L_SUM),
+            ) || !proofs.h_hat_proof.verify(transcript(&session, party, own, b"mul*"), &h_hat_statement)
+                || !proofs.s_proof.verify(
+                    transcript(&session, party, own, b"log* s"),
+                    &log_star(&w_hat, &message.s, &gamma, L_SUM),
+                )
+        })
+        .map(|(_, party)| party)
+        .collect();
+    blame("presigning proofs do not verify", failed)?;
+
+    let delta = input
+        .messages3
+        .iter()
+        .fold(Scalar::<Secp256k1>::zero(), |sum, message| sum + &message.delta);
+    let delta_points: Vec<_> = input.messages3.iter().map(|m| m.delta_point.clone()).collect();
+    let s_points: Vec<_> = input.messages3.iter().map(|m| m.s.clone()).collect();
+    let public_key = key_share.local_key.y_sum_s.clone();
+    let sum = |points: &[Point<Secp256k1>]| points.iter().fold(Point::zero(), |sum, p| sum + p);
+    if &generator * &delta != sum(&delta_points) {
+        return Err(Error::Protocol("delta does not match the Delta_i of the parties".to_string()));
+    }
+    if &public_key * &delta != sum(&s_points) {
+        return Err(Error::Protocol("the S_i of the parties do not add up to delta * Y".to_string()));
+    }
+    let delta_inv = delta
+        .invert()
+        .ok_or_else(|| Error::Protocol("delta is zero".to_string()))?;
+
+    Ok(Secp256k1Cggmp21Presignature {
+        parties: parties.clone(),
+        public_key,
+        r: &gamma * &delta_inv,
+        gamma,
+        k: Secret::new(zk::scalar(&input.nonces.k)),
+        chi: input.chi.clone(),
+        delta_points,
+        s_points,
+    })
+}
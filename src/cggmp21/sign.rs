@@ -0,0 +1,118 @@
+//! One-round signing (Fig. 8) with a presignature. Every party of the
+//! presignature's quorum publishes its partial signature, any of them
+//! combines them.
+
+use curv::arithmetic::{BitManipulation, Converter};
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+use serde::{Deserialize, Serialize};
+
+use super::blame;
+use super::presign::Secp256k1Cggmp21Presignature;
+use crate::error::Error;
+use crate::message::MessageHashing;
+use crate::signature::{self, EncodedSignature};
+use crate::validate::Validate;
+
+/// r, the x coordinate of R reduced into the group order, and the recovery
+/// id R gives the signature before its s is normalized.
+fn r_and_recid(r_point: &Point<Secp256k1>) -> Result<(Scalar<Secp256k1>, u8), Error> {
+    let (x, y) = match (r_point.x_coord(), r_point.y_coord()) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Err(Error::Protocol("the presignature's R is the point at infinity".to_string())),
+    };
+    let order = Scalar::<Secp256k1>::group_order();
+    let recid = y.test_bit(0) as u8 | ((&x >= order) as u8) << 1;
+    Ok((Scalar::from_bigint(&x), recid))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21SignInput {
+    pub presignature: Secp256k1Cggmp21Presignature,
+    pub message: Vec<u8>,
+    #[serde(default)]
+    pub hashing: MessageHashing,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21SignOutput {
+    /// sigma_i, broadcast to the party that combines the signature.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub partial_signature: Scalar<Secp256k1>,
+    pub message_hash: [u8; 32],
+}
+
+/// This party's sigma_i = k_i m + r chi_i. The presignature must not be used
+/// for another message.
+pub fn sign(input: &Secp256k1Cggmp21SignInput) -> Result<Secp256k1Cggmp21SignOutput, Error> {
+    input.validate()?;
+    let message_hash = input.hashing.digest(&input.message)?;
+    let m = Scalar::<Secp256k1>::from_bigint(&BigInt::from_bytes(&message_hash));
+    let presignature = &input.presignature;
+    let (r, _) = r_and_recid(&presignature.r)?;
+    let partial_signature = &*presignature.k * &m + &r * &*presignature.chi;
+    Ok(Secp256k1Cggmp21SignOutput {
+        partial_signature,
+        message_hash,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21SignCombineInput {
+    pub presignature: Secp256k1Cggmp21Presignature,
+    pub message: Vec<u8>,
+    #[serde(default)]
+    pub hashing: MessageHashing,
+    /// Every party's sigma_i, in the order of the presignature's `parties`.
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub partial_signatures: Vec<Scalar<Secp256k1>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Cggmp21SignCombineOutput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+    pub encoded: EncodedSignature,
+}
+
+/// Adds up the partial signatures. If the sum does not verify, every party
+/// whose sigma_i fails Gamma * sigma_i = m * Delta_i + r * S_i is blamed.
+pub fn sign_combine(
+    input: &Secp256k1Cggmp21SignCombineInput,
+) -> Result<Secp256k1Cggmp21SignCombineOutput, Error> {
+    input.validate()?;
+    let message_hash = BigInt::from_bytes(&input.hashing.digest(&input.message)?);
+    let m = Scalar::<Secp256k1>::from_bigint(&message_hash);
+    let presignature = &input.presignature;
+    let (r, recid) = r_and_recid(&presignature.r)?;
+    let s = input
+        .partial_signatures
+        .iter()
+        .fold(Scalar::<Secp256k1>::zero(), |sum, sigma| sum + sigma);
+    let signature = SignatureRecid { r: r.clone(), s, recid };
+
+    if !signature::verify(&signature, &presignature.public_key, &message_hash) {
+        let failed = presignature
+            .parties
+            .iter()
+            .zip(&input.partial_signatures)
+            .enumerate()
+            .filter(|&(position, (_, sigma))| {
+                let expected =
+                    &presignature.delta_points[position] * &m + &presignature.s_points[position] * &r;
+                &presignature.gamma * sigma != expected
+            })
+            .map(|(_, (&party, _))| party)
+            .collect();
+        blame("partial signatures do not verify", failed)?;
+        return Err(Error::InvalidSignature);
+    }
+    let signature = signature::normalize_s(&signature);
+    let encoded = signature::encode(&signature);
+    Ok(Secp256k1Cggmp21SignCombineOutput { signature, encoded })
+}
@@ -0,0 +1,145 @@
+//! Π^aff-g (Fig. 15): the MtA response D = C^x (1 + N0)^y rho^N0 to the
+//! verifier's ciphertext C uses the x of X = x * G and the y of the
+//! prover's own ciphertext Y under N1, with x in ±2^(ℓ+ε) and y in
+//! ±2^(ℓ'+ε).
+
+use curv::arithmetic::Modulo;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    equal, in_range, is_unit, mul, pow, sample_signed, sample_signed_times, sample_unit, scalar, AuxInfo,
+    Transcript, EPSILON, L, L_PRIME,
+};
+use crate::cggmp21::paillier;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AffGProof {
+    pub a: BigInt,
+    pub b_x: Point<Secp256k1>,
+    pub b_y: BigInt,
+    pub e: BigInt,
+    pub s: BigInt,
+    pub f: BigInt,
+    pub t: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+    pub z4: BigInt,
+    pub w: BigInt,
+    pub w_y: BigInt,
+}
+
+/// The public part: C and D under the verifier's N0, Y under the prover's
+/// N1, X = x * G, and the verifier's parameters.
+pub struct AffGStatement<'a> {
+    pub n0: &'a BigInt,
+    pub n1: &'a BigInt,
+    pub c: &'a BigInt,
+    pub d: &'a BigInt,
+    pub y: &'a BigInt,
+    pub x: &'a Point<Secp256k1>,
+    pub aux: &'a AuxInfo,
+}
+
+/// The witness: x, y and the randomness of D and Y.
+pub struct AffGWitness<'a> {
+    pub x: &'a BigInt,
+    pub y: &'a BigInt,
+    pub rho: &'a BigInt,
+    pub rho_y: &'a BigInt,
+}
+
+fn challenge(transcript: Transcript, statement: &AffGStatement, proof: &AffGProof) -> BigInt {
+    transcript
+        .bytes(b"aff-g")
+        .int(statement.n0)
+        .int(statement.n1)
+        .int(statement.c)
+        .int(statement.d)
+        .int(statement.y)
+        .point(statement.x)
+        .aux_info(statement.aux)
+        .int(&proof.a)
+        .point(&proof.b_x)
+        .int(&proof.b_y)
+        .int(&proof.e)
+        .int(&proof.s)
+        .int(&proof.f)
+        .int(&proof.t)
+        .challenge()
+}
+
+impl AffGProof {
+    pub fn prove(transcript: Transcript, statement: &AffGStatement, witness: &AffGWitness) -> Self {
+        let (n0, n1, aux) = (statement.n0, statement.n1, statement.aux);
+        let nn0 = n0 * n0;
+        let commit = |x: &BigInt, y: &BigInt| aux.commit(x, y).expect("s and t are units");
+        let alpha = sample_signed(L + EPSILON);
+        let beta = sample_signed(L_PRIME + EPSILON);
+        let r = sample_unit(n0);
+        let r_y = sample_unit(n1);
+        let gamma = sample_signed_times(L + EPSILON, &aux.n);
+        let m = sample_signed_times(L, &aux.n);
+        let delta = sample_signed_times(L + EPSILON, &aux.n);
+        let mu = sample_signed_times(L, &aux.n);
+
+        let c_alpha = pow(statement.c, &alpha, &nn0).expect("C is a unit");
+        let mut proof = AffGProof {
+            a: BigInt::mod_mul(&c_alpha, &paillier::encrypt_with(n0, &beta, &r), &nn0),
+            b_x: Point::generator() * &scalar(&alpha),
+            b_y: paillier::encrypt_with(n1, &beta, &r_y),
+            e: commit(&alpha, &gamma),
+            s: commit(witness.x, &m),
+            f: commit(&beta, &delta),
+            t: commit(witness.y, &mu),
+            z1: BigInt::from(0),
+            z2: BigInt::from(0),
+            z3: BigInt::from(0),
+            z4: BigInt::from(0),
+            w: BigInt::from(0),
+            w_y: BigInt::from(0),
+        };
+        let e = challenge(transcript, statement, &proof);
+        proof.z1 = alpha + &e * witness.x;
+        proof.z2 = beta + &e * witness.y;
+        proof.z3 = gamma + &e * &m;
+        proof.z4 = delta + &e * &mu;
+        proof.w = BigInt::mod_mul(&r, &pow(witness.rho, &e, n0).expect("rho is a unit"), n0);
+        proof.w_y = BigInt::mod_mul(&r_y, &pow(witness.rho_y, &e, n1).expect("rho_y is a unit"), n1);
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, statement: &AffGStatement) -> bool {
+        let (n0, n1, aux) = (statement.n0, statement.n1, statement.aux);
+        let (nn0, nn1) = (n0 * n0, n1 * n1);
+        let units = [&self.e, &self.s, &self.f, &self.t];
+        if !units.iter().all(|x| is_unit(x, &aux.n)) || !is_unit(&self.a, &nn0) || !is_unit(&self.b_y, &nn1) {
+            return false;
+        }
+        if !is_unit(statement.c, &nn0) || !is_unit(statement.d, &nn0) || !is_unit(statement.y, &nn1) {
+            return false;
+        }
+        if !in_range(&self.z1, L + EPSILON) || !in_range(&self.z2, L_PRIME + EPSILON) {
+            return false;
+        }
+        let e = challenge(transcript, statement, self);
+        equal(
+            mul(pow(statement.c, &self.z1, &nn0), Some(paillier::encrypt_with(n0, &self.z2, &self.w)), &nn0),
+            mul(Some(self.a.clone()), pow(statement.d, &e, &nn0), &nn0),
+        ) && Point::generator() * &scalar(&self.z1) == &self.b_x + statement.x * &scalar(&e)
+            && equal(
+                Some(paillier::encrypt_with(n1, &self.z2, &self.w_y)),
+                mul(Some(self.b_y.clone()), pow(statement.y, &e, &nn1), &nn1),
+            )
+            && equal(
+                aux.commit(&self.z1, &self.z3),
+                mul(Some(self.e.clone()), pow(&self.s, &e, &aux.n), &aux.n),
+            )
+            && equal(
+                aux.commit(&self.z2, &self.z4),
+                mul(Some(self.f.clone()), pow(&self.t, &e, &aux.n), &aux.n),
+            )
+    }
+}
@@ -0,0 +1,84 @@
+//! Π^enc (Fig. 14): the Paillier ciphertext K under the prover's N0 holds a
+//! plaintext in ±2^(ℓ+ε).
+
+use curv::arithmetic::Modulo;
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    equal, in_range, is_unit, mul, pow, sample_signed, sample_signed_times, sample_unit, AuxInfo, Transcript,
+    EPSILON, L,
+};
+use crate::cggmp21::paillier;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EncProof {
+    pub s: BigInt,
+    pub a: BigInt,
+    pub c: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+}
+
+fn challenge(transcript: Transcript, n0: &BigInt, k: &BigInt, aux: &AuxInfo, proof: &EncProof) -> BigInt {
+    transcript
+        .bytes(b"enc")
+        .int(n0)
+        .int(k)
+        .aux_info(aux)
+        .int(&proof.s)
+        .int(&proof.a)
+        .int(&proof.c)
+        .challenge()
+}
+
+impl EncProof {
+    /// Proves K = (1 + N0)^k rho^N0 mod N0^2.
+    pub fn prove(
+        transcript: Transcript,
+        n0: &BigInt,
+        k_ciphertext: &BigInt,
+        aux: &AuxInfo,
+        k: &BigInt,
+        rho: &BigInt,
+    ) -> Self {
+        let commit = |x: &BigInt, y: &BigInt| aux.commit(x, y).expect("s and t are units");
+        let alpha = sample_signed(L + EPSILON);
+        let mu = sample_signed_times(L, &aux.n);
+        let r = sample_unit(n0);
+        let gamma = sample_signed_times(L + EPSILON, &aux.n);
+
+        let mut proof = EncProof {
+            s: commit(k, &mu),
+            a: paillier::encrypt_with(n0, &alpha, &r),
+            c: commit(&alpha, &gamma),
+            z1: BigInt::from(0),
+            z2: BigInt::from(0),
+            z3: BigInt::from(0),
+        };
+        let e = challenge(transcript, n0, k_ciphertext, aux, &proof);
+        proof.z1 = alpha + &e * k;
+        proof.z2 = BigInt::mod_mul(&r, &pow(rho, &e, n0).expect("rho is a unit"), n0);
+        proof.z3 = gamma + &e * &mu;
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, n0: &BigInt, k_ciphertext: &BigInt, aux: &AuxInfo) -> bool {
+        let nn0 = n0 * n0;
+        if !is_unit(&self.s, &aux.n) || !is_unit(&self.c, &aux.n) || !is_unit(&self.a, &nn0) {
+            return false;
+        }
+        if !is_unit(k_ciphertext, &nn0) || !in_range(&self.z1, L + EPSILON) {
+            return false;
+        }
+        let e = challenge(transcript, n0, k_ciphertext, aux, self);
+        equal(
+            Some(paillier::encrypt_with(n0, &self.z1, &self.z2)),
+            mul(Some(self.a.clone()), pow(k_ciphertext, &e, &nn0), &nn0),
+        ) && equal(
+            aux.commit(&self.z1, &self.z3),
+            mul(Some(self.c.clone()), pow(&self.s, &e, &aux.n), &aux.n),
+        )
+    }
+}
@@ -0,0 +1,112 @@
+//! Π^fac (Fig. 28): both prime factors of N0 are larger than about
+//! 2^-(ℓ+ε) sqrt(N0), so N0 has no small factor a cheater could use to pull
+//! plaintexts out of the range proofs. Proven to each verifier under its own
+//! ring-Pedersen parameters.
+
+use curv::arithmetic::BitManipulation;
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    equal, in_range, is_unit, mul, pow, sample_signed, sample_signed_times, AuxInfo, Transcript, EPSILON, L,
+};
+use crate::cggmp21::paillier::PaillierKey;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FacProof {
+    pub commitment_p: BigInt,
+    pub commitment_q: BigInt,
+    pub a: BigInt,
+    pub b: BigInt,
+    pub t: BigInt,
+    pub sigma: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub w1: BigInt,
+    pub w2: BigInt,
+    pub v: BigInt,
+}
+
+/// Bits of sqrt(N0), rounded up.
+fn root_bits(n0: &BigInt) -> usize {
+    n0.bit_length().div_ceil(2)
+}
+
+fn challenge(transcript: Transcript, n0: &BigInt, aux: &AuxInfo, proof: &FacProof) -> BigInt {
+    transcript
+        .bytes(b"fac")
+        .int(n0)
+        .aux_info(aux)
+        .int(&proof.commitment_p)
+        .int(&proof.commitment_q)
+        .int(&proof.a)
+        .int(&proof.b)
+        .int(&proof.t)
+        .int(&proof.sigma)
+        .challenge()
+}
+
+impl FacProof {
+    pub fn prove(transcript: Transcript, key: &PaillierKey, aux: &AuxInfo) -> Self {
+        let n0 = key.n();
+        let n_hat = &aux.n;
+        let root = root_bits(&n0);
+        let commit = |x: &BigInt, y: &BigInt| aux.commit(x, y).expect("s and t are units");
+
+        let alpha = sample_signed(L + EPSILON + root);
+        let beta = sample_signed(L + EPSILON + root);
+        let mu = sample_signed_times(L, n_hat);
+        let nu = sample_signed_times(L, n_hat);
+        let sigma = sample_signed_times(L, &(&n0 * n_hat));
+        let r = sample_signed_times(L + EPSILON, &(&n0 * n_hat));
+        let x = sample_signed_times(L + EPSILON, n_hat);
+        let y = sample_signed_times(L + EPSILON, n_hat);
+
+        let commitment_q = commit(&key.q, &nu);
+        let t = mul(pow(&commitment_q, &alpha, n_hat), pow(&aux.t, &r, n_hat), n_hat).expect("units");
+        let mut proof = FacProof {
+            commitment_p: commit(&key.p, &mu),
+            commitment_q,
+            a: commit(&alpha, &x),
+            b: commit(&beta, &y),
+            t,
+            sigma: sigma.clone(),
+            z1: BigInt::from(0),
+            z2: BigInt::from(0),
+            w1: BigInt::from(0),
+            w2: BigInt::from(0),
+            v: BigInt::from(0),
+        };
+        let e = challenge(transcript, &n0, aux, &proof);
+        let sigma_hat = sigma - &nu * &key.p;
+        proof.z1 = alpha + &e * &key.p;
+        proof.z2 = beta + &e * &key.q;
+        proof.w1 = x + &e * &mu;
+        proof.w2 = y + &e * &nu;
+        proof.v = r + &e * &sigma_hat;
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, n0: &BigInt, aux: &AuxInfo) -> bool {
+        let n_hat = &aux.n;
+        let units = [&self.commitment_p, &self.commitment_q, &self.a, &self.b, &self.t];
+        if !units.iter().all(|x| is_unit(x, n_hat)) {
+            return false;
+        }
+        let root = root_bits(n0);
+        if !in_range(&self.z1, L + EPSILON + root) || !in_range(&self.z2, L + EPSILON + root) {
+            return false;
+        }
+        let e = challenge(transcript, n0, aux, self);
+        let r = aux.commit(n0, &self.sigma);
+        equal(aux.commit(&self.z1, &self.w1), mul(Some(self.a.clone()), pow(&self.commitment_p, &e, n_hat), n_hat))
+            && equal(
+                aux.commit(&self.z2, &self.w2),
+                mul(Some(self.b.clone()), pow(&self.commitment_q, &e, n_hat), n_hat),
+            )
+            && equal(
+                mul(pow(&self.commitment_q, &self.z1, n_hat), pow(&aux.t, &self.v, n_hat), n_hat),
+                mul(Some(self.t.clone()), r.and_then(|r| pow(&r, &e, n_hat)), n_hat),
+            )
+    }
+}
@@ -0,0 +1,99 @@
+//! Π^log* (Fig. 25): the Paillier ciphertext C under the prover's N0 and the
+//! point X = x * B hold the same x, which is in ±2^(bits+ε). Presigning uses
+//! it with `bits` = ℓ for nonces and `bits` = [`L_SUM`](super::L_SUM) for the
+//! sums δ_i and χ_i are decrypted from.
+
+use curv::arithmetic::Modulo;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    equal, in_range, is_unit, mul, pow, sample_signed, sample_signed_times, sample_unit, scalar, AuxInfo,
+    Transcript, EPSILON,
+};
+use crate::cggmp21::paillier;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LogStarProof {
+    pub s: BigInt,
+    pub a: BigInt,
+    pub y: Point<Secp256k1>,
+    pub d: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub z3: BigInt,
+}
+
+/// The public part: C under N0, X = x * B, and the verifier's parameters.
+pub struct LogStarStatement<'a> {
+    pub n0: &'a BigInt,
+    pub c: &'a BigInt,
+    pub x: &'a Point<Secp256k1>,
+    pub base: &'a Point<Secp256k1>,
+    pub aux: &'a AuxInfo,
+    pub bits: usize,
+}
+
+fn challenge(transcript: Transcript, statement: &LogStarStatement, proof: &LogStarProof) -> BigInt {
+    transcript
+        .bytes(b"log*")
+        .int(statement.n0)
+        .int(statement.c)
+        .point(statement.x)
+        .point(statement.base)
+        .aux_info(statement.aux)
+        .bytes(&(statement.bits as u64).to_be_bytes())
+        .int(&proof.s)
+        .int(&proof.a)
+        .point(&proof.y)
+        .int(&proof.d)
+        .challenge()
+}
+
+impl LogStarProof {
+    /// Proves C = (1 + N0)^x rho^N0 mod N0^2 and X = x * B.
+    pub fn prove(transcript: Transcript, statement: &LogStarStatement, x: &BigInt, rho: &BigInt) -> Self {
+        let aux = statement.aux;
+        let commit = |x: &BigInt, y: &BigInt| aux.commit(x, y).expect("s and t are units");
+        let alpha = sample_signed(statement.bits + EPSILON);
+        let mu = sample_signed_times(statement.bits, &aux.n);
+        let r = sample_unit(statement.n0);
+        let gamma = sample_signed_times(statement.bits + EPSILON, &aux.n);
+
+        let mut proof = LogStarProof {
+            s: commit(x, &mu),
+            a: paillier::encrypt_with(statement.n0, &alpha, &r),
+            y: statement.base * &scalar(&alpha),
+            d: commit(&alpha, &gamma),
+            z1: BigInt::from(0),
+            z2: BigInt::from(0),
+            z3: BigInt::from(0),
+        };
+        let e = challenge(transcript, statement, &proof);
+        proof.z1 = alpha + &e * x;
+        proof.z2 = BigInt::mod_mul(&r, &pow(rho, &e, statement.n0).expect("rho is a unit"), statement.n0);
+        proof.z3 = gamma + &e * &mu;
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, statement: &LogStarStatement) -> bool {
+        let (n0, aux) = (statement.n0, statement.aux);
+        let nn0 = n0 * n0;
+        if !is_unit(&self.s, &aux.n) || !is_unit(&self.d, &aux.n) || !is_unit(&self.a, &nn0) {
+            return false;
+        }
+        if !is_unit(statement.c, &nn0) || !in_range(&self.z1, statement.bits + EPSILON) {
+            return false;
+        }
+        let e = challenge(transcript, statement, self);
+        equal(
+            Some(paillier::encrypt_with(n0, &self.z1, &self.z2)),
+            mul(Some(self.a.clone()), pow(statement.c, &e, &nn0), &nn0),
+        ) && statement.base * &scalar(&self.z1) == &self.y + statement.x * &scalar(&e)
+            && equal(
+                aux.commit(&self.z1, &self.z3),
+                mul(Some(self.d.clone()), pow(&self.s, &e, &aux.n), &aux.n),
+            )
+    }
+}
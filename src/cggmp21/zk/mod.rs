@@ -0,0 +1,220 @@
+//! The zero-knowledge proofs of CGGMP21, made non-interactive with a
+//! SHA-256 Fiat-Shamir transcript. Each proof module follows the figure of
+//! the paper it is named after. A proof's `verify` returns whether it holds,
+//! the stages turn a false into a blame of the prover.
+//!
+//! Every transcript starts from the session and the prover and verifier
+//! indices, so a proof cannot be replayed in another session or to another
+//! party.
+
+mod aff_g;
+mod enc;
+mod fac;
+mod log_star;
+mod modulus;
+mod mul;
+mod mul_star;
+mod prm;
+
+pub use self::aff_g::{AffGProof, AffGStatement, AffGWitness};
+pub use self::enc::EncProof;
+pub use self::fac::FacProof;
+pub use self::log_star::{LogStarProof, LogStarStatement};
+pub use self::modulus::ModProof;
+pub use self::mul::{MulProof, MulStatement};
+pub use self::mul_star::{MulStarProof, MulStarStatement};
+pub use self::prm::PrmProof;
+
+use curv::arithmetic::{BitManipulation, Converter, Modulo, Samplable, EGCD};
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::party::PartyIndex;
+
+/// Bits of the secp256k1 group order, the paper's ℓ.
+pub const L: usize = 256;
+/// Slack of the range proofs, ε = ℓ + 128: the masks hide a challenge times
+/// a witness, both below 2^ℓ, up to a statistical distance of 2^-128.
+pub const EPSILON: usize = L + 128;
+/// Range of the MtA masks, ℓ' = 2ℓ + ε + 128, so they hide the product of a
+/// nonce and the largest share the range proofs let through.
+pub const L_PRIME: usize = 2 * L + EPSILON + 128;
+/// Range of the plaintexts δ_i and χ_i are decrypted from: a sum over the
+/// quorum of MtA results a cheating sender can push to 2^(ℓ'+ε), with 32
+/// bits of headroom for the quorum size. 2^(L_SUM+ε+1) stays below N/4 for
+/// a 2048-bit N, so a Π^log* over this range pins the plaintext down as an
+/// integer, not just mod N.
+pub const L_SUM: usize = L_PRIME + EPSILON + 32;
+/// Repetitions of Π^prm and Π^mod, whose challenges are single bits.
+pub const M: usize = 128;
+
+/// A party's public Paillier modulus N and ring-Pedersen parameters s and t
+/// for the proofs it verifies.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AuxInfo {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub n: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub s: BigInt,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub t: BigInt,
+}
+
+impl AuxInfo {
+    /// s^x t^y mod N, None if a negative exponent meets a non-unit.
+    fn commit(&self, x: &BigInt, y: &BigInt) -> Option<BigInt> {
+        Some(BigInt::mod_mul(&pow(&self.s, x, &self.n)?, &pow(&self.t, y, &self.n)?, &self.n))
+    }
+}
+
+/// Fiat-Shamir transcript: a hash of everything the challenge depends on,
+/// every item length-prefixed.
+#[derive(Clone)]
+pub struct Transcript(Sha256);
+
+impl Transcript {
+    pub fn new(label: &str) -> Self {
+        Transcript(Sha256::new()).bytes(label.as_bytes())
+    }
+
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.0.update((bytes.len() as u64).to_be_bytes());
+        self.0.update(bytes);
+        self
+    }
+
+    pub fn int(self, n: &BigInt) -> Self {
+        let sign = [(n < &BigInt::from(0)) as u8];
+        self.bytes(&sign).bytes(&n.to_bytes())
+    }
+
+    pub fn ints<'a>(self, ns: impl IntoIterator<Item = &'a BigInt>) -> Self {
+        ns.into_iter().fold(self, Transcript::int)
+    }
+
+    pub fn point(self, p: &Point<Secp256k1>) -> Self {
+        if p.is_zero() {
+            return self.bytes(&[0]);
+        }
+        self.bytes(&p.to_bytes(true))
+    }
+
+    pub fn party(self, party: PartyIndex) -> Self {
+        self.bytes(&party.0.to_be_bytes())
+    }
+
+    pub fn aux_info(self, aux: &AuxInfo) -> Self {
+        self.int(&aux.n).int(&aux.s).int(&aux.t)
+    }
+
+    /// `len` bytes of hash output, in counter mode.
+    fn expand(&self, len: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(len + 32);
+        let mut counter = 0u32;
+        while out.len() < len {
+            let mut hasher = self.0.clone();
+            hasher.update(counter.to_be_bytes());
+            out.extend_from_slice(&hasher.finalize());
+            counter += 1;
+        }
+        out.truncate(len);
+        out
+    }
+
+    /// The 32 bytes the transcript hashes to.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut out = [0; 32];
+        out.copy_from_slice(&self.expand(32));
+        out
+    }
+
+    /// A challenge in ±q, the range of every proof with a scalar challenge.
+    pub fn challenge(&self) -> BigInt {
+        let bytes = self.expand(L / 8 * 2 + 1);
+        let e = BigInt::modulus(&BigInt::from_bytes(&bytes[1..]), Scalar::<Secp256k1>::group_order());
+        if bytes[0] & 1 == 1 {
+            BigInt::from(0) - e
+        } else {
+            e
+        }
+    }
+
+    /// `m` single-bit challenges.
+    pub fn challenge_bits(&self, m: usize) -> Vec<bool> {
+        let bytes = self.expand(m.div_ceil(8));
+        (0..m).map(|i| bytes[i / 8] >> (i % 8) & 1 == 1).collect()
+    }
+
+    /// `m` challenges below `n`, each drawn from 128 bits more than n has.
+    pub fn challenges_below(&self, n: &BigInt, m: usize) -> Vec<BigInt> {
+        let len = (n.bit_length() + 128).div_ceil(8);
+        let bytes = self.expand(len * m);
+        bytes
+            .chunks(len)
+            .map(|chunk| BigInt::modulus(&BigInt::from_bytes(chunk), n))
+            .collect()
+    }
+}
+
+/// base^exp mod `modulus` for an exponent of either sign. None if the
+/// exponent is negative and the base not a unit.
+pub fn pow(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> Option<BigInt> {
+    let zero = BigInt::from(0);
+    if exp < &zero {
+        let inverse = BigInt::mod_inv(&BigInt::modulus(base, modulus), modulus)?;
+        Some(BigInt::mod_pow(&inverse, &(zero - exp), modulus))
+    } else {
+        Some(BigInt::mod_pow(&BigInt::modulus(base, modulus), exp, modulus))
+    }
+}
+
+/// Uniform in ±2^bits.
+pub fn sample_signed(bits: usize) -> BigInt {
+    BigInt::sample(bits + 1) - (BigInt::from(1) << bits)
+}
+
+/// Uniform in ±2^bits * n.
+pub fn sample_signed_times(bits: usize, n: &BigInt) -> BigInt {
+    let bound = (BigInt::from(1) << bits) * n;
+    BigInt::sample_below(&(&bound + &bound)) - bound
+}
+
+/// Whether |z| <= 2^bits.
+pub fn in_range(z: &BigInt, bits: usize) -> bool {
+    let bound = BigInt::from(1) << bits;
+    z <= &bound && z >= &(BigInt::from(0) - &bound)
+}
+
+/// Uniform in Z*_n.
+pub fn sample_unit(n: &BigInt) -> BigInt {
+    loop {
+        let r = BigInt::sample_below(n);
+        if is_unit(&r, n) {
+            return r;
+        }
+    }
+}
+
+/// Whether `x` is in 1..n and coprime to `n`, as every group element a
+/// proof carries must be.
+pub fn is_unit(x: &BigInt, n: &BigInt) -> bool {
+    x > &BigInt::from(0) && x < n && BigInt::egcd(x, n).0 == BigInt::from(1)
+}
+
+/// `z` reduced into the group order.
+pub fn scalar(z: &BigInt) -> Scalar<Secp256k1> {
+    Scalar::from_bigint(&BigInt::modulus(z, Scalar::<Secp256k1>::group_order()))
+}
+
+/// Whether both sides of a check could be computed and agree.
+fn equal(lhs: Option<BigInt>, rhs: Option<BigInt>) -> bool {
+    matches!((lhs, rhs), (Some(lhs), Some(rhs)) if lhs == rhs)
+}
+
+/// a * b mod n for two values that may be missing.
+fn mul(a: Option<BigInt>, b: Option<BigInt>, n: &BigInt) -> Option<BigInt> {
+    Some(BigInt::mod_mul(&a?, &b?, n))
+}
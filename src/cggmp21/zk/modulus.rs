@@ -0,0 +1,118 @@
+//! Π^mod (Fig. 16): N is a Paillier-Blum modulus, the product of two primes
+//! that are both 3 mod 4, so every y has a fourth root up to sign and a
+//! factor w of Jacobi symbol -1.
+
+use curv::arithmetic::{BitManipulation, Modulo, Samplable};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{is_unit, Transcript, M};
+use crate::cggmp21::paillier::{self, PaillierKey};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ModProof {
+    pub w: BigInt,
+    pub x: Vec<BigInt>,
+    pub a: Vec<bool>,
+    pub b: Vec<bool>,
+    pub z: Vec<BigInt>,
+}
+
+fn challenges(transcript: Transcript, n: &BigInt, w: &BigInt) -> Vec<BigInt> {
+    transcript.bytes(b"mod").int(n).int(w).challenges_below(n, M)
+}
+
+/// x^((p - 1) / 2) mod p, which is 1 for a quadratic residue.
+fn is_residue(x: &BigInt, p: &BigInt) -> bool {
+    let one = BigInt::from(1);
+    BigInt::mod_pow(x, &((p - &one) / BigInt::from(2)), p) == one
+}
+
+/// A fourth root of a quadratic residue `y` mod p = 3 mod 4:
+/// y^(((p + 1) / 4)^2).
+fn fourth_root(y: &BigInt, p: &BigInt) -> BigInt {
+    let e = (p + BigInt::from(1)) / BigInt::from(4);
+    BigInt::mod_pow(&BigInt::modulus(y, p), &(&e * &e), p)
+}
+
+impl ModProof {
+    pub fn prove(transcript: Transcript, key: &PaillierKey) -> Self {
+        let n = key.n();
+        let (p, q) = (&key.p, &key.q);
+        // Exactly one of the Legendre symbols mod p and q is -1
+        let w = loop {
+            let w = BigInt::sample_below(&n);
+            if is_unit(&w, &n) && is_residue(&w, p) != is_residue(&w, q) {
+                break w;
+            }
+        };
+        let ys = challenges(transcript, &n, &w);
+        let n_inv = BigInt::mod_inv(&n, &key.phi()).expect("N is a unit mod phi");
+        let p_inv = BigInt::mod_inv(p, q).expect("p and q are distinct primes");
+        let q_inv = BigInt::mod_inv(q, p).expect("p and q are distinct primes");
+        let minus_one = &n - BigInt::from(1);
+
+        let mut proof = ModProof {
+            w: w.clone(),
+            x: Vec::with_capacity(M),
+            a: Vec::with_capacity(M),
+            b: Vec::with_capacity(M),
+            z: Vec::with_capacity(M),
+        };
+        for y in ys {
+            // -1 is a non-residue mod both primes and w mod exactly one, so
+            // exactly one of y, -y, wy and -wy is a residue mod both
+            let (a, b, y_ab) = [(false, false), (true, false), (false, true), (true, true)]
+                .iter()
+                .map(|&(a, b)| {
+                    let mut y_ab = y.clone();
+                    if a {
+                        y_ab = BigInt::mod_mul(&y_ab, &minus_one, &n);
+                    }
+                    if b {
+                        y_ab = BigInt::mod_mul(&y_ab, &w, &n);
+                    }
+                    (a, b, y_ab)
+                })
+                .find(|(_, _, y_ab)| is_residue(y_ab, p) && is_residue(y_ab, q))
+                .expect("challenges are units of N");
+            let root_p = fourth_root(&y_ab, p);
+            let root_q = fourth_root(&y_ab, q);
+            let x = BigInt::modulus(&(root_p * q * &q_inv + root_q * p * &p_inv), &n);
+            proof.x.push(x);
+            proof.a.push(a);
+            proof.b.push(b);
+            proof.z.push(BigInt::mod_pow(&y, &n_inv, &n));
+        }
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, n: &BigInt) -> bool {
+        let lengths = [self.x.len(), self.a.len(), self.b.len(), self.z.len()];
+        if lengths.iter().any(|&len| len != M) {
+            return false;
+        }
+        if !n.test_bit(0) || paillier::is_probable_prime(n) || !is_unit(&self.w, n) {
+            return false;
+        }
+        let zero = BigInt::from(0);
+        let minus_one = n - BigInt::from(1);
+        let ys = challenges(transcript, n, &self.w);
+        ys.iter().enumerate().all(|(i, y)| {
+            let mut y_ab = y.clone();
+            if self.a[i] {
+                y_ab = BigInt::mod_mul(&y_ab, &minus_one, n);
+            }
+            if self.b[i] {
+                y_ab = BigInt::mod_mul(&y_ab, &self.w, n);
+            }
+            let x_squared = BigInt::mod_mul(&self.x[i], &self.x[i], n);
+            self.z[i] >= zero
+                && &self.z[i] < n
+                && self.x[i] >= zero
+                && &self.x[i] < n
+                && BigInt::mod_pow(&self.z[i], n, n) == *y
+                && BigInt::mod_mul(&x_squared, &x_squared, n) == y_ab
+        })
+    }
+}
@@ -0,0 +1,88 @@
+//! Π^mul (Fig. 29): C = Y^x rho^N mod N^2 for the plaintext x of X, all
+//! under the prover's N, so C encrypts the product of the plaintexts of X
+//! and Y. Needs no ring-Pedersen parameters, one proof serves every
+//! verifier.
+
+use curv::arithmetic::{Modulo, Samplable};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{equal, is_unit, mul, pow, sample_unit, Transcript};
+use crate::cggmp21::paillier;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MulProof {
+    pub a: BigInt,
+    pub b: BigInt,
+    pub z: BigInt,
+    pub u: BigInt,
+    pub v: BigInt,
+}
+
+/// The public part: X, Y and C under N.
+pub struct MulStatement<'a> {
+    pub n: &'a BigInt,
+    pub x: &'a BigInt,
+    pub y: &'a BigInt,
+    pub c: &'a BigInt,
+}
+
+fn challenge(transcript: Transcript, statement: &MulStatement, proof: &MulProof) -> BigInt {
+    transcript
+        .bytes(b"mul")
+        .int(statement.n)
+        .int(statement.x)
+        .int(statement.y)
+        .int(statement.c)
+        .int(&proof.a)
+        .int(&proof.b)
+        .challenge()
+}
+
+impl MulProof {
+    /// Proves C = Y^x rho^N with X = (1 + N)^x rho_x^N.
+    pub fn prove(
+        transcript: Transcript,
+        statement: &MulStatement,
+        x: &BigInt,
+        rho: &BigInt,
+        rho_x: &BigInt,
+    ) -> Self {
+        let n = statement.n;
+        let nn = n * n;
+        let alpha = BigInt::sample_below(n);
+        let r = sample_unit(n);
+        let s = sample_unit(n);
+
+        let y_alpha = BigInt::mod_pow(statement.y, &alpha, &nn);
+        let mut proof = MulProof {
+            a: BigInt::mod_mul(&y_alpha, &BigInt::mod_pow(&r, n, &nn), &nn),
+            b: paillier::encrypt_with(n, &alpha, &s),
+            z: BigInt::from(0),
+            u: BigInt::from(0),
+            v: BigInt::from(0),
+        };
+        let e = challenge(transcript, statement, &proof);
+        proof.z = alpha + &e * x;
+        proof.u = BigInt::mod_mul(&r, &pow(rho, &e, n).expect("rho is a unit"), n);
+        proof.v = BigInt::mod_mul(&s, &pow(rho_x, &e, n).expect("rho_x is a unit"), n);
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, statement: &MulStatement) -> bool {
+        let n = statement.n;
+        let nn = n * n;
+        let units = [&self.a, &self.b, statement.x, statement.y, statement.c];
+        if !units.iter().all(|x| is_unit(x, &nn)) || !is_unit(&self.u, n) || !is_unit(&self.v, n) {
+            return false;
+        }
+        let e = challenge(transcript, statement, self);
+        equal(
+            mul(pow(statement.y, &self.z, &nn), Some(BigInt::mod_pow(&self.u, n, &nn)), &nn),
+            mul(Some(self.a.clone()), pow(statement.c, &e, &nn), &nn),
+        ) && equal(
+            Some(paillier::encrypt_with(n, &self.z, &self.v)),
+            mul(Some(self.b.clone()), pow(statement.x, &e, &nn), &nn),
+        )
+    }
+}
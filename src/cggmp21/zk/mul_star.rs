@@ -0,0 +1,99 @@
+//! Π^mul* (Fig. 31): D = C^x rho^N0 mod N0^2 for the x of X = x * G, with
+//! x in ±2^(ℓ+ε), so D encrypts x times the plaintext of C.
+
+use curv::arithmetic::Modulo;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    equal, in_range, is_unit, mul, pow, sample_signed, sample_signed_times, sample_unit, scalar, AuxInfo,
+    Transcript, EPSILON, L,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MulStarProof {
+    pub a: BigInt,
+    pub b_x: Point<Secp256k1>,
+    pub e: BigInt,
+    pub s: BigInt,
+    pub z1: BigInt,
+    pub z2: BigInt,
+    pub w: BigInt,
+}
+
+/// The public part: C and D under the prover's N0, X = x * G, and the
+/// verifier's parameters.
+pub struct MulStarStatement<'a> {
+    pub n0: &'a BigInt,
+    pub c: &'a BigInt,
+    pub d: &'a BigInt,
+    pub x: &'a Point<Secp256k1>,
+    pub aux: &'a AuxInfo,
+}
+
+fn challenge(transcript: Transcript, statement: &MulStarStatement, proof: &MulStarProof) -> BigInt {
+    transcript
+        .bytes(b"mul*")
+        .int(statement.n0)
+        .int(statement.c)
+        .int(statement.d)
+        .point(statement.x)
+        .aux_info(statement.aux)
+        .int(&proof.a)
+        .point(&proof.b_x)
+        .int(&proof.e)
+        .int(&proof.s)
+        .challenge()
+}
+
+impl MulStarProof {
+    pub fn prove(transcript: Transcript, statement: &MulStarStatement, x: &BigInt, rho: &BigInt) -> Self {
+        let (n0, aux) = (statement.n0, statement.aux);
+        let nn0 = n0 * n0;
+        let commit = |x: &BigInt, y: &BigInt| aux.commit(x, y).expect("s and t are units");
+        let alpha = sample_signed(L + EPSILON);
+        let r = sample_unit(n0);
+        let gamma = sample_signed_times(L + EPSILON, &aux.n);
+        let m = sample_signed_times(L, &aux.n);
+
+        let c_alpha = pow(statement.c, &alpha, &nn0).expect("C is a unit");
+        let mut proof = MulStarProof {
+            a: BigInt::mod_mul(&c_alpha, &BigInt::mod_pow(&r, n0, &nn0), &nn0),
+            b_x: Point::generator() * &scalar(&alpha),
+            e: commit(&alpha, &gamma),
+            s: commit(x, &m),
+            z1: BigInt::from(0),
+            z2: BigInt::from(0),
+            w: BigInt::from(0),
+        };
+        let e = challenge(transcript, statement, &proof);
+        proof.z1 = alpha + &e * x;
+        proof.z2 = gamma + &e * &m;
+        proof.w = BigInt::mod_mul(&r, &pow(rho, &e, n0).expect("rho is a unit"), n0);
+        proof
+    }
+
+    pub fn verify(&self, transcript: Transcript, statement: &MulStarStatement) -> bool {
+        let (n0, aux) = (statement.n0, statement.aux);
+        let nn0 = n0 * n0;
+        if !is_unit(&self.e, &aux.n) || !is_unit(&self.s, &aux.n) || !is_unit(&self.a, &nn0) {
+            return false;
+        }
+        if !is_unit(statement.c, &nn0) || !is_unit(statement.d, &nn0) || !is_unit(&self.w, n0) {
+            return false;
+        }
+        if !in_range(&self.z1, L + EPSILON) {
+            return false;
+        }
+        let e = challenge(transcript, statement, self);
+        equal(
+            mul(pow(statement.c, &self.z1, &nn0), Some(BigInt::mod_pow(&self.w, n0, &nn0)), &nn0),
+            mul(Some(self.a.clone()), pow(statement.d, &e, &nn0), &nn0),
+        ) && Point::generator() * &scalar(&self.z1) == &self.b_x + statement.x * &scalar(&e)
+            && equal(
+                aux.commit(&self.z1, &self.z2),
+                mul(Some(self.e.clone()), pow(&self.s, &e, &aux.n), &aux.n),
+            )
+    }
+}
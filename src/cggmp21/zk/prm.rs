@@ -0,0 +1,44 @@
+//! Π^prm (Fig. 17): s is in the subgroup generated by t, so the ring-Pedersen
+//! commitments s^x t^y the prover asks others to use are hiding.
+
+use curv::arithmetic::{Modulo, Samplable};
+use curv::BigInt;
+use serde::{Deserialize, Serialize};
+
+use super::{equal, is_unit, pow, AuxInfo, Transcript, M};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PrmProof {
+    pub a: Vec<BigInt>,
+    pub z: Vec<BigInt>,
+}
+
+fn challenge(transcript: Transcript, aux: &AuxInfo, a: &[BigInt]) -> Vec<bool> {
+    transcript.bytes(b"prm").aux_info(aux).ints(a).challenge_bits(M)
+}
+
+impl PrmProof {
+    /// Proves s = t^lambda mod N, knowing phi(N).
+    pub fn prove(transcript: Transcript, aux: &AuxInfo, lambda: &BigInt, phi: &BigInt) -> Self {
+        let alphas: Vec<BigInt> = (0..M).map(|_| BigInt::sample_below(phi)).collect();
+        let a: Vec<BigInt> = alphas.iter().map(|alpha| BigInt::mod_pow(&aux.t, alpha, &aux.n)).collect();
+        let e = challenge(transcript, aux, &a);
+        let z = alphas
+            .iter()
+            .zip(e)
+            .map(|(alpha, e)| if e { BigInt::modulus(&(alpha + lambda), phi) } else { alpha.clone() })
+            .collect();
+        PrmProof { a, z }
+    }
+
+    pub fn verify(&self, transcript: Transcript, aux: &AuxInfo) -> bool {
+        if self.a.len() != M || self.z.len() != M || !self.a.iter().all(|a| is_unit(a, &aux.n)) {
+            return false;
+        }
+        let e = challenge(transcript, aux, &self.a);
+        self.a.iter().zip(&self.z).zip(e).all(|((a, z), e)| {
+            let expected = if e { BigInt::mod_mul(a, &aux.s, &aux.n) } else { a.clone() };
+            equal(pow(&aux.t, z, &aux.n), Some(expected))
+        })
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::Error;
+use crate::ethereum::{keccak256, parse_hex};
+
+/// Typed data in the `eth_signTypedData_v4` JSON layout, which is why the
+/// field names are camelCase here unlike the rest of the API.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    pub types: BTreeMap<String, Vec<TypedField>>,
+    pub primary_type: String,
+    pub domain: Value,
+    pub message: Value,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct TypedField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+}
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+impl TypedData {
+    /// keccak256(0x19 0x01 || domainSeparator || hashStruct(message)), the
+    /// digest to sign as a prehashed message.
+    pub fn signing_hash(&self) -> Result<[u8; 32], Error> {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != DOMAIN_TYPE {
+            data.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        Ok(keccak256(&data))
+    }
+
+    pub fn domain_separator(&self) -> Result<[u8; 32], Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    pub fn hash_struct(&self, kind: &str, value: &Value) -> Result<[u8; 32], Error> {
+        let fields = self.fields(kind)?;
+        let object = value
+            .as_object()
+            .ok_or_else(|| invalid(format!("{} must be an object", kind)))?;
+        let mut data = self.type_hash(kind)?.to_vec();
+        for field in fields.iter() {
+            match object.get(&field.name) {
+                Some(value) => data.extend_from_slice(&self.encode_value(&field.kind, value)?),
+                None => return Err(invalid(format!("{}.{} is missing", kind, field.name))),
+            }
+        }
+        Ok(keccak256(&data))
+    }
+
+    pub fn type_hash(&self, kind: &str) -> Result<[u8; 32], Error> {
+        Ok(keccak256(self.encode_type(kind)?.as_bytes()))
+    }
+
+    /// `Mail(Person from,Person to,string contents)Person(string name,address wallet)`:
+    /// the primary type followed by every referenced struct type, sorted by name.
+    pub fn encode_type(&self, kind: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.collect_dependencies(kind, &mut dependencies)?;
+        dependencies.remove(kind);
+        let mut encoded = String::new();
+        for name in std::iter::once(kind).chain(dependencies.iter().map(String::as_str)) {
+            let fields = self.fields(name)?;
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{} {}", field.kind, field.name))
+                .collect();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn fields(&self, kind: &str) -> Result<Vec<TypedField>, Error> {
+        if let Some(fields) = self.types.get(kind) {
+            return Ok(fields.clone());
+        }
+        if kind != DOMAIN_TYPE {
+            return Err(invalid(format!("unknown type {}", kind)));
+        }
+        // Older payloads leave EIP712Domain out of `types`, infer it from the domain
+        let domain = self.domain.as_object();
+        let known = [
+            ("name", "string"),
+            ("version", "string"),
+            ("chainId", "uint256"),
+            ("verifyingContract", "address"),
+            ("salt", "bytes32"),
+        ];
+        Ok(known
+            .iter()
+            .filter(|(name, _)| domain.map_or(false, |domain| domain.contains_key(*name)))
+            .map(|(name, kind)| TypedField {
+                name: name.to_string(),
+                kind: kind.to_string(),
+            })
+            .collect())
+    }
+
+    fn collect_dependencies(&self, kind: &str, found: &mut BTreeSet<String>) -> Result<(), Error> {
+        let kind = base_type(kind);
+        if found.contains(kind) || !self.types.contains_key(kind) {
+            return Ok(());
+        }
+        found.insert(kind.to_string());
+        for field in self.types[kind].iter() {
+            self.collect_dependencies(&field.kind, found)?;
+        }
+        Ok(())
+    }
+
+    fn encode_value(&self, kind: &str, value: &Value) -> Result<[u8; 32], Error> {
+        if let Some(open) = kind.rfind('[') {
+            if !kind.ends_with(']') {
+                return Err(invalid(format!("unknown type {}", kind)));
+            }
+            let element = &kind[..open];
+            let length = &kind[open + 1..kind.len() - 1];
+            let items = value
+                .as_array()
+                .ok_or_else(|| invalid(format!("{} must be an array", kind)))?;
+            if !length.is_empty() && length.parse::<usize>().ok() != Some(items.len()) {
+                return Err(invalid(format!("{} has {} items", kind, items.len())));
+            }
+            let mut data = Vec::with_capacity(32 * items.len());
+            for item in items {
+                data.extend_from_slice(&self.encode_value(element, item)?);
+            }
+            return Ok(keccak256(&data));
+        }
+        if self.types.contains_key(kind) {
+            return self.hash_struct(kind, value);
+        }
+
+        let mut word = [0; 32];
+        match kind {
+            "string" => {
+                let s = value.as_str().ok_or_else(|| invalid("string expected".to_string()))?;
+                word = keccak256(s.as_bytes());
+            }
+            "bytes" => word = keccak256(&parse_hex(as_str(kind, value)?)?),
+            "bool" => {
+                let b = value.as_bool().ok_or_else(|| invalid("bool expected".to_string()))?;
+                word[31] = b as u8;
+            }
+            "address" => {
+                let address = parse_hex(as_str(kind, value)?)?;
+                if address.len() != 20 {
+                    return Err(invalid(format!("invalid address {}", value)));
+                }
+                word[12..].copy_from_slice(&address);
+            }
+            _ if kind.starts_with("bytes") => {
+                let size = parse_size(kind, "bytes", 32)?;
+                let bytes = parse_hex(as_str(kind, value)?)?;
+                if bytes.len() != size {
+                    return Err(invalid(format!("{} has {} bytes", kind, bytes.len())));
+                }
+                word[..size].copy_from_slice(&bytes);
+            }
+            _ if kind.starts_with("uint") => {
+                let bits = parse_size(kind, "uint", 256)?;
+                word = parse_integer(kind, value, bits, false)?;
+            }
+            _ if kind.starts_with("int") => {
+                let bits = parse_size(kind, "int", 256)?;
+                word = parse_integer(kind, value, bits, true)?;
+            }
+            _ => return Err(invalid(format!("unknown type {}", kind))),
+        }
+        Ok(word)
+    }
+}
+
+fn base_type(kind: &str) -> &str {
+    kind.find('[').map_or(kind, |open| &kind[..open])
+}
+
+fn as_str<'a>(kind: &str, value: &'a Value) -> Result<&'a str, Error> {
+    value
+        .as_str()
+        .ok_or_else(|| invalid(format!("{} must be a hex string", kind)))
+}
+
+fn parse_size(kind: &str, prefix: &str, max: usize) -> Result<usize, Error> {
+    match kind[prefix.len()..].parse::<usize>() {
+        Ok(size) if size > 0 && size <= max => Ok(size),
+        _ if kind == prefix && prefix != "bytes" => Ok(max),
+        _ => Err(invalid(format!("unknown type {}", kind))),
+    }
+}
+
+/// Integers come as JSON numbers, decimal strings or `0x` hex strings and are
+/// encoded as 256-bit big-endian two's complement. They must fit the `bits`
+/// of their `uintN` or `intN` type.
+fn parse_integer(kind: &str, value: &Value, bits: usize, signed: bool) -> Result<[u8; 32], Error> {
+    let text = match value {
+        Value::Number(number) => number.to_string(),
+        Value::String(text) => text.clone(),
+        _ => return Err(invalid(format!("integer expected, got {}", value))),
+    };
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(digits) if signed => (true, digits),
+        _ => (false, text.as_str()),
+    };
+    let (radix, digits) = match digits.strip_prefix("0x") {
+        Some(hex) => (16, hex),
+        None => (10, digits),
+    };
+    if digits.is_empty() {
+        return Err(invalid(format!("invalid integer {}", text)));
+    }
+    let mut word = [0u8; 32];
+    for c in digits.chars() {
+        let digit = c
+            .to_digit(radix)
+            .ok_or_else(|| invalid(format!("invalid integer {}", text)))?;
+        // word = word * radix + digit
+        let mut carry = digit;
+        for byte in word.iter_mut().rev() {
+            let v = u32::from(*byte) * radix + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+        if carry != 0 {
+            return Err(invalid(format!("integer {} overflows 256 bits", text)));
+        }
+    }
+    // intN holds -2^(N-1) up to 2^(N-1) - 1
+    let magnitude_bits = bit_length(&word);
+    let fits = match (signed, negative) {
+        (false, _) => magnitude_bits <= bits,
+        (true, false) => magnitude_bits < bits,
+        (true, true) => magnitude_bits < bits || (magnitude_bits == bits && bit_length(&decrement(word)) < bits),
+    };
+    if !fits {
+        return Err(invalid(format!("{} is out of range for {}", text, kind)));
+    }
+    if negative {
+        // Two's complement: invert and add one
+        let mut carry = 1u16;
+        for byte in word.iter_mut().rev() {
+            let v = u16::from(!*byte) + carry;
+            *byte = v as u8;
+            carry = v >> 8;
+        }
+    }
+    Ok(word)
+}
+
+fn bit_length(word: &[u8; 32]) -> usize {
+    match word.iter().position(|&byte| byte != 0) {
+        Some(i) => 8 * (31 - i) + (8 - word[i].leading_zeros() as usize),
+        None => 0,
+    }
+}
+
+fn decrement(mut word: [u8; 32]) -> [u8; 32] {
+    for byte in word.iter_mut().rev() {
+        let borrow = *byte == 0;
+        *byte = byte.wrapping_sub(1);
+        if !borrow {
+            break;
+        }
+    }
+    word
+}
+
+fn invalid(reason: String) -> Error {
+    Error::InvalidTypedData(reason)
+}
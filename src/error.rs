@@ -0,0 +1,98 @@
+use std::fmt;
+
+use curv::cryptographic_primitives::proofs::ProofError;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::ErrorType;
+
+use crate::party::PartyIndex;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    InvalidExtendedKey(&'static str),
+    InvalidDerivationPath(String),
+    HardenedDerivation(u32),
+    InvalidChildKey(u32),
+    InvalidSignature,
+    InvalidRecoveryId(u8),
+    InvalidMessageLength(usize),
+    InvalidTransaction(String),
+    InvalidTypedData(String),
+    InvalidPsbt(&'static str),
+    InvalidPsbtInput(usize, &'static str),
+    InvalidAddressFormat(String),
+    /// The stage input is malformed: a party index out of range, a
+    /// duplicate party, a quorum below t + 1 or a vector of the wrong length.
+    InvalidInput(String),
+    /// A verification step failed because of data sent by the listed
+    /// parties (1-based keygen indices), who should be excluded on retry.
+    Blame { reason: String, parties: Vec<u16> },
+    /// A verification step of the underlying GG20 implementation failed.
+    Protocol(String),
+    /// A batch stage failed in the session at this position.
+    Session(usize, Box<Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidExtendedKey(reason) => write!(f, "invalid extended key: {}", reason),
+            Error::InvalidDerivationPath(path) => write!(f, "invalid derivation path: {}", path),
+            Error::HardenedDerivation(index) => {
+                write!(f, "cannot derive hardened child {} from a public key", index)
+            }
+            Error::InvalidChildKey(index) => write!(f, "child {} is not a valid key", index),
+            Error::InvalidSignature => write!(f, "invalid signature"),
+            Error::InvalidRecoveryId(recid) => write!(f, "invalid recovery id {}", recid),
+            Error::InvalidMessageLength(len) => {
+                write!(f, "prehashed message must be 32 bytes, got {}", len)
+            }
+            Error::InvalidTransaction(reason) => write!(f, "invalid transaction: {}", reason),
+            Error::InvalidTypedData(reason) => write!(f, "invalid typed data: {}", reason),
+            Error::InvalidPsbt(reason) => write!(f, "invalid PSBT: {}", reason),
+            Error::InvalidPsbtInput(input, reason) => {
+                write!(f, "invalid PSBT input {}: {}", input, reason)
+            }
+            Error::InvalidAddressFormat(reason) => write!(f, "invalid address format: {}", reason),
+            Error::InvalidInput(reason) => write!(f, "invalid input: {}", reason),
+            Error::Blame { reason, parties } => {
+                write!(f, "{}, blamed parties {:?}", reason, parties)
+            }
+            Error::Protocol(reason) => write!(f, "protocol error: {}", reason),
+            Error::Session(index, e) => write!(f, "session {}: {}", index, e),
+        }
+    }
+}
+
+impl From<multi_party_ecdsa::Error> for Error {
+    fn from(e: multi_party_ecdsa::Error) -> Self {
+        Error::Protocol(format!("{:?}", e))
+    }
+}
+
+impl From<ErrorType> for Error {
+    fn from(e: ErrorType) -> Self {
+        Error::Protocol(format!("{:?}", e))
+    }
+}
+
+impl From<ProofError> for Error {
+    fn from(e: ProofError) -> Self {
+        Error::Protocol(format!("{:?}", e))
+    }
+}
+
+impl Error {
+    /// Attributes a GG20 error to the parties it names. `bad_actors` are
+    /// positions in the vectors passed to the failing step, `parties` maps
+    /// them back to party indices.
+    pub(crate) fn blame(e: ErrorType, parties: &[PartyIndex]) -> Self {
+        if e.bad_actors.is_empty() {
+            return Error::Protocol(e.error_type);
+        }
+        Error::Blame {
+            reason: e.error_type,
+            parties: e.bad_actors.iter().map(|&i| parties[i].0).collect(),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,284 @@
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::error::Error;
+use crate::signature;
+
+/// An unsigned EVM transaction. Quantities are `0x`-prefixed hex or decimal
+/// strings, addresses and data are `0x`-prefixed hex.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Transaction {
+    Legacy(LegacyTransaction),
+    Eip2930(Eip2930Transaction),
+    Eip1559(Eip1559Transaction),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct LegacyTransaction {
+    /// EIP-155 replay protection is applied unless this is left out.
+    pub chain_id: Option<u64>,
+    pub nonce: String,
+    pub gas_price: String,
+    pub gas_limit: String,
+    pub to: Option<String>,
+    pub value: String,
+    #[serde(default)]
+    pub data: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Eip2930Transaction {
+    pub chain_id: u64,
+    pub nonce: String,
+    pub gas_price: String,
+    pub gas_limit: String,
+    pub to: Option<String>,
+    pub value: String,
+    #[serde(default)]
+    pub data: String,
+    #[serde(default)]
+    pub access_list: Vec<AccessListItem>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Eip1559Transaction {
+    pub chain_id: u64,
+    pub nonce: String,
+    pub max_priority_fee_per_gas: String,
+    pub max_fee_per_gas: String,
+    pub gas_limit: String,
+    pub to: Option<String>,
+    pub value: String,
+    #[serde(default)]
+    pub data: String,
+    #[serde(default)]
+    pub access_list: Vec<AccessListItem>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct AccessListItem {
+    pub address: String,
+    pub storage_keys: Vec<String>,
+}
+
+impl Transaction {
+    /// The bytes whose Keccak-256 hash gets signed.
+    pub fn signing_payload(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            Transaction::Legacy(tx) => {
+                let mut fields = tx.fields()?;
+                if let Some(chain_id) = tx.chain_id {
+                    fields.push(rlp_u64(chain_id));
+                    fields.push(rlp_bytes(&[]));
+                    fields.push(rlp_bytes(&[]));
+                }
+                Ok(rlp_list(&fields))
+            }
+            Transaction::Eip2930(tx) => Ok(typed_payload(0x01, &tx.fields()?)),
+            Transaction::Eip1559(tx) => Ok(typed_payload(0x02, &tx.fields()?)),
+        }
+    }
+
+    pub fn signing_hash(&self) -> Result<[u8; 32], Error> {
+        Ok(keccak256(&self.signing_payload()?))
+    }
+
+    /// Raw signed transaction, ready for eth_sendRawTransaction.
+    pub fn encode_signed(&self, signature: &SignatureRecid) -> Result<Vec<u8>, Error> {
+        let signature = signature::normalize_s(signature);
+        if signature.recid > 1 {
+            return Err(Error::InvalidRecoveryId(signature.recid));
+        }
+        let r = rlp_bytes(&signature.r.to_bigint().to_bytes());
+        let s = rlp_bytes(&signature.s.to_bigint().to_bytes());
+        let y_parity = u64::from(signature.recid);
+        match self {
+            Transaction::Legacy(tx) => {
+                let v = match tx.chain_id {
+                    Some(chain_id) => chain_id
+                        .checked_mul(2)
+                        .and_then(|v| v.checked_add(35 + y_parity))
+                        .ok_or_else(|| Error::InvalidTransaction(format!("chain id {} is too large", chain_id)))?,
+                    None => 27 + y_parity,
+                };
+                let mut fields = tx.fields()?;
+                fields.extend(vec![rlp_u64(v), r, s]);
+                Ok(rlp_list(&fields))
+            }
+            Transaction::Eip2930(tx) => {
+                let mut fields = tx.fields()?;
+                fields.extend(vec![rlp_u64(y_parity), r, s]);
+                Ok(typed_payload(0x01, &fields))
+            }
+            Transaction::Eip1559(tx) => {
+                let mut fields = tx.fields()?;
+                fields.extend(vec![rlp_u64(y_parity), r, s]);
+                Ok(typed_payload(0x02, &fields))
+            }
+        }
+    }
+}
+
+impl LegacyTransaction {
+    fn fields(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(vec![
+            rlp_quantity(&self.nonce)?,
+            rlp_quantity(&self.gas_price)?,
+            rlp_quantity(&self.gas_limit)?,
+            rlp_to(&self.to)?,
+            rlp_quantity(&self.value)?,
+            rlp_bytes(&parse_hex(&self.data)?),
+        ])
+    }
+}
+
+impl Eip2930Transaction {
+    fn fields(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(vec![
+            rlp_u64(self.chain_id),
+            rlp_quantity(&self.nonce)?,
+            rlp_quantity(&self.gas_price)?,
+            rlp_quantity(&self.gas_limit)?,
+            rlp_to(&self.to)?,
+            rlp_quantity(&self.value)?,
+            rlp_bytes(&parse_hex(&self.data)?),
+            rlp_access_list(&self.access_list)?,
+        ])
+    }
+}
+
+impl Eip1559Transaction {
+    fn fields(&self) -> Result<Vec<Vec<u8>>, Error> {
+        Ok(vec![
+            rlp_u64(self.chain_id),
+            rlp_quantity(&self.nonce)?,
+            rlp_quantity(&self.max_priority_fee_per_gas)?,
+            rlp_quantity(&self.max_fee_per_gas)?,
+            rlp_quantity(&self.gas_limit)?,
+            rlp_to(&self.to)?,
+            rlp_quantity(&self.value)?,
+            rlp_bytes(&parse_hex(&self.data)?),
+            rlp_access_list(&self.access_list)?,
+        ])
+    }
+}
+
+/// Last 20 bytes of the Keccak-256 hash of the uncompressed public key.
+pub fn address(public_key: &Point<Secp256k1>) -> [u8; 20] {
+    let hash = keccak256(&public_key.to_bytes(false)[1..]);
+    let mut address = [0; 20];
+    address.copy_from_slice(&hash[12..]);
+    address
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&Keccak256::digest(data));
+    hash
+}
+
+pub fn parse_hex(s: &str) -> Result<Vec<u8>, Error> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s).map_err(|_| Error::InvalidTransaction(format!("invalid hex string {}", s)))
+}
+
+fn typed_payload(tx_type: u8, fields: &[Vec<u8>]) -> Vec<u8> {
+    let mut payload = vec![tx_type];
+    payload.extend(rlp_list(fields));
+    payload
+}
+
+fn rlp_quantity(quantity: &str) -> Result<Vec<u8>, Error> {
+    let parsed = match quantity.strip_prefix("0x") {
+        Some(hex) => BigInt::from_str_radix(hex, 16),
+        None => BigInt::from_str_radix(quantity, 10),
+    };
+    let value = parsed
+        .map_err(|_| Error::InvalidTransaction(format!("invalid quantity {}", quantity)))?;
+    if value < BigInt::from(0) {
+        return Err(Error::InvalidTransaction(format!("negative quantity {}", quantity)));
+    }
+    if value == BigInt::from(0) {
+        return Ok(rlp_bytes(&[]));
+    }
+    Ok(rlp_bytes(&value.to_bytes()))
+}
+
+fn rlp_u64(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    rlp_bytes(&bytes[start..])
+}
+
+fn rlp_to(to: &Option<String>) -> Result<Vec<u8>, Error> {
+    match to {
+        Some(to) => Ok(rlp_bytes(&parse_address(to)?)),
+        None => Ok(rlp_bytes(&[])),
+    }
+}
+
+fn rlp_access_list(access_list: &[AccessListItem]) -> Result<Vec<u8>, Error> {
+    let items = access_list
+        .iter()
+        .map(|item| {
+            let storage_keys = item
+                .storage_keys
+                .iter()
+                .map(|key| match parse_hex(key)? {
+                    key if key.len() == 32 => Ok(rlp_bytes(&key)),
+                    _ => Err(Error::InvalidTransaction(format!("invalid storage key {}", key))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(rlp_list(&[
+                rlp_bytes(&parse_address(&item.address)?),
+                rlp_list(&storage_keys),
+            ]))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(rlp_list(&items))
+}
+
+fn parse_address(address: &str) -> Result<Vec<u8>, Error> {
+    match parse_hex(address)? {
+        bytes if bytes.len() == 20 => Ok(bytes),
+        _ => Err(Error::InvalidTransaction(format!("invalid address {}", address))),
+    }
+}
+
+pub fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_length(bytes.len(), 0x80);
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// RLP list of already encoded items.
+pub fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = rlp_length(payload.len(), 0xc0);
+    out.extend(payload);
+    out
+}
+
+fn rlp_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let bytes = (len as u64).to_be_bytes();
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let mut out = vec![offset + 55 + (bytes.len() - start) as u8];
+    out.extend_from_slice(&bytes[start..]);
+    out
+}
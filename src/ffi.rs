@@ -0,0 +1,130 @@
+//! C ABI over the JSON stage API, for hosts that cannot load the Node addon.
+//!
+//! Every function takes a NUL-terminated UTF-8 JSON input and stores a JSON
+//! string in `*output`: the stage output on `MPC_STATUS_OK`, otherwise
+//! `{"error": "...", "blamed_parties": [...]}`. Strings stored in `*output`
+//! belong to the caller and must be released with `mpc_string_free`.
+//!
+//! # Safety
+//!
+//! The `mpc_secp256k1_*` stage functions are sound as long as the caller
+//! upholds these rules:
+//!
+//! - `input` is NULL or points to a NUL-terminated string that stays valid
+//!   and unmodified for the duration of the call.
+//! - `output` is NULL or points to writable, properly aligned storage for
+//!   one `char *`. Its previous value is overwritten, not freed.
+//! - A string stored in `*output` is released exactly once, with
+//!   `mpc_string_free` and never with `free`, and is not used afterwards.
+
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::json;
+use zeroize::Zeroize;
+
+use crate::error::Error;
+use crate::*;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MpcStatus {
+    Ok = 0,
+    /// `input` or `output` is NULL.
+    NullPointer = 1,
+    /// `input` is not UTF-8, not valid JSON for the stage, or describes an
+    /// impossible quorum or message set.
+    InvalidInput = 2,
+    /// The stage aborted because of the parties in `blamed_parties`.
+    Blame = 3,
+    /// The stage failed for another reason, see `error`.
+    Failed = 4,
+    /// The library panicked. This is a bug.
+    Panic = 5,
+}
+
+fn error_json(error: &str, blamed_parties: &[u16]) -> String {
+    json!({ "error": error, "blamed_parties": blamed_parties }).to_string()
+}
+
+fn run<I, O>(input: *const c_char, f: fn(&I) -> Result<O, Error>) -> (MpcStatus, String)
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    let input = match unsafe { CStr::from_ptr(input) }.to_str() {
+        Ok(input) => input,
+        Err(e) => return (MpcStatus::InvalidInput, error_json(&e.to_string(), &[])),
+    };
+    let input: I = match serde_json::from_str(input) {
+        Ok(input) => input,
+        Err(e) => return (MpcStatus::InvalidInput, error_json(&e.to_string(), &[])),
+    };
+    match f(&input) {
+        Ok(output) => (MpcStatus::Ok, serde_json::to_string(&output).unwrap()),
+        Err(Error::Blame { reason, parties }) => (MpcStatus::Blame, error_json(&reason, &parties)),
+        Err(e @ Error::InvalidInput(_)) => (MpcStatus::InvalidInput, error_json(&e.to_string(), &[])),
+        Err(e) => (MpcStatus::Failed, error_json(&e.to_string(), &[])),
+    }
+}
+
+unsafe fn call<I, O>(
+    input: *const c_char,
+    output: *mut *mut c_char,
+    f: fn(&I) -> Result<O, Error>,
+) -> MpcStatus
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    if output.is_null() {
+        return MpcStatus::NullPointer;
+    }
+    *output = ptr::null_mut();
+    if input.is_null() {
+        return MpcStatus::NullPointer;
+    }
+    let (status, json) = panic::catch_unwind(AssertUnwindSafe(|| run(input, f)))
+        .unwrap_or_else(|_| (MpcStatus::Panic, error_json("panic", &[])));
+    // serde_json escapes control characters, so the JSON has no interior NUL
+    *output = CString::new(json).unwrap().into_raw();
+    status
+}
+
+/// Wipes and releases a string returned through `output`. Passing NULL is
+/// a no-op.
+///
+/// # Safety
+///
+/// `s` is NULL or a string stored in `*output` by this library that has not
+/// been released yet. It must not be used after this call.
+#[no_mangle]
+pub unsafe extern "C" fn mpc_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        CString::from_raw(s).into_bytes_with_nul().zeroize();
+    }
+}
+
+/// Defines one entry point per stage of `ffi_exports.rs`, each passing
+/// its arguments to `call`.
+macro_rules! exports {
+    ($($(#[doc = $doc:literal])* $name:ident => $stage:path;)*) => {
+        $(
+            $(#[doc = $doc])*
+            ///
+            /// # Safety
+            ///
+            /// See the module documentation.
+            #[no_mangle]
+            pub unsafe extern "C" fn $name(input: *const c_char, output: *mut *mut c_char) -> MpcStatus {
+                call(input, output, $stage)
+            }
+        )*
+    };
+}
+
+include!("ffi_exports.rs");
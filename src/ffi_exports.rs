@@ -0,0 +1,56 @@
+// The stage entry points of the C ABI. src/ffi.rs defines them from this
+// list and build.rs declares them in include/mpc.h, so the two cannot drift.
+exports! {
+    /// Keygen round 1: this party's Paillier key and its commitments.
+    mpc_secp256k1_keygen_stage1 => keygen_stage1;
+    /// Keygen round 2: opens the round 1 commitments and deals VSS shares.
+    mpc_secp256k1_keygen_stage2 => keygen_stage2;
+    /// Keygen round 3: checks the received shares and proves the key share.
+    mpc_secp256k1_keygen_stage3 => keygen_stage3;
+    /// Keygen round 4: checks every party's key share proof.
+    mpc_secp256k1_keygen_stage4 => keygen_stage4;
+    /// The key share to save once keygen round 4 succeeded.
+    mpc_secp256k1_keygen_finalize => keygen_finalize;
+    /// Signing round 1: the nonce shares, their commitment and MtA message A.
+    mpc_secp256k1_sign_stage1 => sign_stage1;
+    /// Signing round 2: the MtA responses to every counterparty.
+    mpc_secp256k1_sign_stage2 => sign_stage2;
+    /// Signing round 3: delta_i and sigma_i from the MtA results.
+    mpc_secp256k1_sign_stage3 => sign_stage3;
+    /// Signing round 4: the inverse of the summed deltas.
+    mpc_secp256k1_sign_stage4 => sign_stage4;
+    /// Signing round 5: R, R_i and their consistency proofs.
+    mpc_secp256k1_sign_stage5 => sign_stage5;
+    /// Signing round 6: checks the round 5 proofs and publishes S_i.
+    mpc_secp256k1_sign_stage6 => sign_stage6;
+    /// Signing round 7: checks that the S_i sum to the public key.
+    mpc_secp256k1_sign_stage7 => sign_stage7;
+    /// Signing round 8: this party's share of s.
+    mpc_secp256k1_sign_stage8 => sign_stage8;
+    /// Signing round 9: the combined, verified signature.
+    mpc_secp256k1_sign_stage9 => sign_stage9;
+    /// A key share shifted by a BIP32 tweak.
+    mpc_secp256k1_tweak_key => tweak_key;
+    /// The private key interpolated from t + 1 shares.
+    mpc_secp256k1_construct_private_key => construct_private_key;
+    /// Whether a signature verifies.
+    mpc_secp256k1_verify_signature => verify_signature;
+    /// CGGMP21 aux info round 1: a Paillier key, s and t, and their commitment.
+    mpc_secp256k1_cggmp21_aux_info_stage1 => cggmp21::aux_info_stage1;
+    /// CGGMP21 aux info round 2: opens the commitments and proves the modulus.
+    mpc_secp256k1_cggmp21_aux_info_stage2 => cggmp21::aux_info_stage2;
+    /// The CGGMP21 key share to save once every modulus proof verified.
+    mpc_secp256k1_cggmp21_aux_info_finalize => cggmp21::aux_info_finalize;
+    /// CGGMP21 presigning round 1: the encrypted nonces k_i and gamma_i.
+    mpc_secp256k1_cggmp21_presign_stage1 => cggmp21::presign_stage1;
+    /// CGGMP21 presigning round 2: the MtA responses to every counterparty.
+    mpc_secp256k1_cggmp21_presign_stage2 => cggmp21::presign_stage2;
+    /// CGGMP21 presigning round 3: delta_i, Delta_i and S_i with their proofs.
+    mpc_secp256k1_cggmp21_presign_stage3 => cggmp21::presign_stage3;
+    /// The presignature once every round 3 proof verified.
+    mpc_secp256k1_cggmp21_presign_output => cggmp21::presign_output;
+    /// CGGMP21 signing: this party's share of s.
+    mpc_secp256k1_cggmp21_sign => cggmp21::sign;
+    /// The combined signature, or the parties whose shares are wrong.
+    mpc_secp256k1_cggmp21_sign_combine => cggmp21::sign_combine;
+}
@@ -0,0 +1,311 @@
+use curv::cryptographic_primitives::proofs::sigma_dlog::DLogProof;
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::*;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::bip32::{self, ChainCodeDecommitment};
+use crate::error::Error;
+use crate::parameters;
+use crate::party::PartyIndex;
+use crate::secret::Secret;
+use crate::validate::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage1Input {
+    pub index: PartyIndex,
+    pub use_safe_prime: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage1Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Keys"))]
+    pub key: Secret<Keys>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::KeyGenBroadcastMessage1"))]
+    pub bc1: KeyGenBroadcastMessage1,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::KeyGenDecommitMessage1"))]
+    pub decom1: KeyGenDecommitMessage1,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::BigInt"))]
+    pub chain_code_com: BigInt,
+    pub chain_code_decom: ChainCodeDecommitment,
+}
+
+pub fn keygen_stage1(input: &Secp256k1KeyGenStage1Input) -> Result<Secp256k1KeyGenStage1Output, Error> {
+    input.validate()?;
+    let key = if input.use_safe_prime {
+        Keys::create_safe_prime(usize::from(input.index.0))
+    } else {
+        Keys::create(usize::from(input.index.0))
+    };
+    let (bc1, decom1) =
+        key.phase1_broadcast_phase3_proof_of_correct_key_proof_of_correct_h1h2();
+    let (chain_code_com, chain_code_decom) = bip32::commit_chain_code_share();
+    Ok(Secp256k1KeyGenStage1Output {
+        key: Secret::new(key),
+        bc1,
+        decom1,
+        chain_code_com,
+        chain_code_decom,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage2Input {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Keys"))]
+    pub key: Secret<Keys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::KeyGenBroadcastMessage1>"))]
+    pub bc1s: Vec<KeyGenBroadcastMessage1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::KeyGenDecommitMessage1>"))]
+    pub decom1s: Vec<KeyGenDecommitMessage1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::BigInt>"))]
+    pub chain_code_coms: Vec<BigInt>,
+    pub chain_code_decoms: Vec<ChainCodeDecommitment>,
+    pub threshold: u16,
+    pub share_count: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage2Output {
+    #[cfg_attr(feature = "schema", schemars(with = "(crate::schema::VerifiableSS, Vec<crate::schema::Scalar>, usize)"))]
+    pub vss: Secret<(VerifiableSS<Secp256k1>, Vec<Scalar<Secp256k1>>, usize)>,
+    pub chain_code: [u8; 32],
+}
+
+pub fn keygen_stage2(input: &Secp256k1KeyGenStage2Input) -> Result<Secp256k1KeyGenStage2Output, Error> {
+    input.validate()?;
+    let params = Parameters {
+        threshold: input.threshold,
+        share_count: input.share_count,
+    };
+    // The ring-Pedersen proofs and the Paillier key proof of every bc1 are
+    // verified by the GG20 call below, the parameters themselves are not
+    parameters::check(
+        input
+            .bc1s
+            .iter()
+            .zip(keygen_parties(input.share_count))
+            .map(|(bc1, party)| (party, parameters::broadcast_issues(bc1)))
+            .collect(),
+    )?;
+    let vss = input
+        .key
+        .phase1_verify_com_phase3_verify_correct_key_verify_dlog_phase2_distribute(&params, &input.decom1s, &input.bc1s)
+        .map_err(|e| Error::blame(e, &keygen_parties(input.share_count)))?;
+    let chain_code = bip32::combine_chain_code_shares(&input.chain_code_coms, &input.chain_code_decoms)?;
+    Ok(Secp256k1KeyGenStage2Output {
+        vss: Secret::new(vss),
+        chain_code,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage3Input {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Keys"))]
+    pub key: Secret<Keys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub ys: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::VerifiableSS>"))]
+    pub vss_schemes: Vec<VerifiableSS<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub party_shares: Secret<Vec<Scalar<Secp256k1>>>,
+    pub threshold: u16,
+    pub share_count: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage3Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SharedKeys"))]
+    pub shared_key: Secret<SharedKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::DLogProof"))]
+    pub dlog_proof: DLogProof<Secp256k1, Sha256>,
+}
+
+pub fn keygen_stage3(input: &Secp256k1KeyGenStage3Input) -> Result<Secp256k1KeyGenStage3Output, Error> {
+    input.validate()?;
+    let params = Parameters {
+        threshold: input.threshold,
+        share_count: input.share_count,
+    };
+    let (shared_key, dlog_proof) = input
+        .key
+        .phase2_verify_vss_construct_keypair_phase3_pok_dlog(
+            &params,
+            &input.ys,
+            &input.party_shares,
+            &input.vss_schemes,
+            input.key.party_index,
+        )
+        .map_err(|e| Error::blame(e, &keygen_parties(input.share_count)))?;
+    Ok(Secp256k1KeyGenStage3Output {
+        shared_key: Secret::new(shared_key),
+        dlog_proof,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage4Input {
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub ys: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::VerifiableSS>"))]
+    pub vss_schemes: Vec<VerifiableSS<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::DLogProof>"))]
+    pub dlog_proofs: Vec<DLogProof<Secp256k1, Sha256>>,
+    pub threshold: u16,
+    pub share_count: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenStage4Output {}
+
+pub fn keygen_stage4(input: &Secp256k1KeyGenStage4Input) -> Result<Secp256k1KeyGenStage4Output, Error> {
+    input.validate()?;
+    let params = Parameters {
+        threshold: input.threshold,
+        share_count: input.share_count,
+    };
+    Keys::verify_dlog_proofs_check_against_vss(
+        &params,
+        &input.dlog_proofs,
+        &input.ys,
+        &input.vss_schemes,
+    )
+    .map_err(|e| Error::blame(e, &keygen_parties(input.share_count)))?;
+    Ok(Secp256k1KeyGenStage4Output {})
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyGenFinalizeInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Keys"))]
+    pub key: Secret<Keys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::KeyGenBroadcastMessage1>"))]
+    pub bc1s: Vec<KeyGenBroadcastMessage1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::VerifiableSS"))]
+    pub vss_scheme: VerifiableSS<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SharedKeys"))]
+    pub shared_key: Secret<SharedKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::DLogProof>"))]
+    pub dlog_proofs: Vec<DLogProof<Secp256k1, Sha256>>,
+    pub chain_code: [u8; 32],
+    pub threshold: u16,
+    pub share_count: u16,
+}
+
+/// What a party saves at the end of keygen: its `LocalKey`, the chain code
+/// of the shared key, which is needed for BIP32 derivation, and the round 1
+/// broadcasts, whose h1/h2 proofs `audit_key_share` re-verifies.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1KeyShare {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub chain_code: [u8; 32],
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::KeyGenBroadcastMessage1>"))]
+    pub bc1s: Vec<KeyGenBroadcastMessage1>,
+}
+
+/// Assembles the key share from this party's stage 1 key, its own VSS
+/// scheme from stage 2 and its shared key from stage 3, plus every
+/// party's bc1 and dlog proof. The parameters and h1/h2 proofs of every bc1
+/// are checked again, so a saved key share never holds unverified ones.
+pub fn keygen_finalize(input: &Secp256k1KeyGenFinalizeInput) -> Result<Secp256k1KeyShare, Error> {
+    input.validate()?;
+    parameters::check(
+        input
+            .bc1s
+            .iter()
+            .zip(keygen_parties(input.share_count))
+            .map(|(bc1, party)| {
+                let mut issues = parameters::broadcast_issues(bc1);
+                if !parameters::verify_ring_pedersen_proofs(bc1) {
+                    issues.push("h1/h2 proofs do not verify".to_string());
+                }
+                (party, issues)
+            })
+            .collect(),
+    )?;
+    let local_key = LocalKey {
+        paillier_dk: input.key.dk.clone(),
+        pk_vec: input.dlog_proofs.iter().map(|proof| proof.pk.clone()).collect(),
+        keys_linear: (*input.shared_key).clone(),
+        paillier_key_vec: input.bc1s.iter().map(|bc1| bc1.e.clone()).collect(),
+        y_sum_s: input.shared_key.y.clone(),
+        h1_h2_n_tilde_vec: input.bc1s.iter().map(|bc1| bc1.dlog_statement.clone()).collect(),
+        vss_scheme: input.vss_scheme.clone(),
+        i: input.key.party_index as u16,
+        t: input.threshold,
+        n: input.share_count,
+    };
+    Ok(Secp256k1KeyShare {
+        local_key: Secret::new(local_key),
+        chain_code: input.chain_code,
+        bc1s: input.bc1s.clone(),
+    })
+}
+
+/// Keygen indexes parties 1..=n, in the order of the broadcast vectors.
+fn keygen_parties(share_count: u16) -> Vec<PartyIndex> {
+    (1..=share_count).map(PartyIndex).collect()
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1ConstructPrivateKeyInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::VerifiableSS"))]
+    pub vss_scheme: VerifiableSS<Secp256k1>,
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub xs: Secret<Vec<Scalar<Secp256k1>>>,
+}
+
+pub fn construct_private_key(input: &Secp256k1ConstructPrivateKeyInput) -> Result<Secret<Scalar<Secp256k1>>, Error> {
+    input.validate()?;
+    let parties: Vec<u16> = input.parties.iter().map(|p| p.0 - 1).collect();
+    Ok(Secret::new(input.vss_scheme.reconstruct(&parties, &input.xs)))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1TweakKeyInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub il: Scalar<Secp256k1>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1TweakKeyOutput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub new_local_key: Secret<LocalKey<Secp256k1>>,
+}
+
+pub fn tweak_key(input: &Secp256k1TweakKeyInput) -> Result<Secp256k1TweakKeyOutput, Error> {
+    input.validate()?;
+    let mut new_local_key = input.local_key.clone();
+    let il_point = Point::generator() * input.il.clone();
+    // Adding il to the constant term of the sharing polynomial adds it to
+    // every share, so every party applies the same tweak to its own share
+    // and to every public key share.
+    new_local_key.keys_linear.x_i = new_local_key.keys_linear.x_i.clone() + input.il.clone();
+    new_local_key.keys_linear.y = new_local_key.keys_linear.y.clone() + il_point.clone();
+    new_local_key.vss_scheme.commitments[0] = new_local_key.vss_scheme.commitments[0].clone() + il_point.clone();
+    for pk in new_local_key.pk_vec.iter_mut() {
+        *pk = pk.clone() + il_point.clone();
+    }
+    new_local_key.y_sum_s = new_local_key.y_sum_s.clone() + il_point.clone();
+    Ok(Secp256k1TweakKeyOutput {
+        new_local_key,
+    })
+}
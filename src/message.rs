@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+
+use crate::error::Error;
+
+/// How the `message` handed to secp256k1_sign_stage8 turns into the 32-byte
+/// digest that actually gets signed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum MessageHashing {
+    /// The message already is a 32-byte digest and is signed as is.
+    Prehashed,
+    Sha256,
+    /// SHA-256 applied twice, as in Bitcoin.
+    DoubleSha256,
+    Keccak256,
+    /// Keccak-256 over the `personal_sign` prefix, the decimal message
+    /// length and the message (EIP-191 version 0x45).
+    EthereumPersonal,
+}
+
+impl Default for MessageHashing {
+    fn default() -> Self {
+        MessageHashing::Prehashed
+    }
+}
+
+impl MessageHashing {
+    pub fn digest(self, message: &[u8]) -> Result<[u8; 32], Error> {
+        let mut digest = [0; 32];
+        match self {
+            MessageHashing::Prehashed => {
+                if message.len() != 32 {
+                    return Err(Error::InvalidMessageLength(message.len()));
+                }
+                digest.copy_from_slice(message);
+            }
+            MessageHashing::Sha256 => digest.copy_from_slice(&Sha256::digest(message)),
+            MessageHashing::DoubleSha256 => {
+                digest.copy_from_slice(&Sha256::digest(&Sha256::digest(message)))
+            }
+            MessageHashing::Keccak256 => digest.copy_from_slice(&Keccak256::digest(message)),
+            MessageHashing::EthereumPersonal => {
+                let mut hasher = Keccak256::new();
+                hasher.update(b"\x19Ethereum Signed Message:\n");
+                hasher.update(message.len().to_string().as_bytes());
+                hasher.update(message);
+                digest.copy_from_slice(&hasher.finalize());
+            }
+        }
+        Ok(digest)
+    }
+}
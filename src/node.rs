@@ -0,0 +1,280 @@
+//! Neon bindings: every export takes the stage input as a JSON string and
+//! returns the output as a JSON string, throwing on error.
+
+use neon::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use zeroize::Zeroizing;
+
+use crate::error::Error;
+use crate::*;
+
+fn call<I, O, F>(mut cx: FunctionContext, f: F) -> JsResult<JsString>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+    F: Fn(&I) -> Result<O, Error>,
+{
+    let json: String = cx.argument::<JsString>(0)?.value(&mut cx);
+    let input: I = secret::from_json(json).or_else(|e| cx.throw_error(e.to_string()))?;
+    let output = f(&input).or_else(|e| cx.throw_error(e.to_string()))?;
+    let json = Zeroizing::new(serde_json::to_string(&output).unwrap());
+    Ok(cx.string(&*json))
+}
+
+fn hello(mut cx: FunctionContext) -> JsResult<JsString> {
+    let data = cx.argument::<JsString>(0)?;
+    let s = &data.value(&mut cx);
+    Ok(cx.string(&s))
+}
+
+fn secp256k1_keygen_stage1(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, keygen_stage1)
+}
+
+fn secp256k1_keygen_stage2(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, keygen_stage2)
+}
+
+fn secp256k1_keygen_stage3(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, keygen_stage3)
+}
+
+fn secp256k1_keygen_stage4(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, keygen_stage4)
+}
+
+fn secp256k1_keygen_finalize(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, keygen_finalize)
+}
+
+fn secp256k1_construct_private_key(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, construct_private_key)
+}
+
+fn secp256k1_sign_stage1(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage1)
+}
+
+fn secp256k1_sign_stage2(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage2)
+}
+
+fn secp256k1_sign_stage3(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage3)
+}
+
+fn secp256k1_sign_stage4(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage4)
+}
+
+fn secp256k1_sign_stage5(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage5)
+}
+
+fn secp256k1_sign_stage6(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage6)
+}
+
+fn secp256k1_sign_stage7(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage7)
+}
+
+fn secp256k1_sign_stage8(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage8)
+}
+
+fn secp256k1_sign_stage9(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, sign_stage9)
+}
+
+fn secp256k1_sign_batch_stage1(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage1))
+}
+
+fn secp256k1_sign_batch_stage2(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage2))
+}
+
+fn secp256k1_sign_batch_stage3(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage3))
+}
+
+fn secp256k1_sign_batch_stage4(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage4))
+}
+
+fn secp256k1_sign_batch_stage5(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage5))
+}
+
+fn secp256k1_sign_batch_stage6(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage6))
+}
+
+fn secp256k1_sign_batch_stage7(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage7))
+}
+
+fn secp256k1_sign_batch_stage8(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage8))
+}
+
+fn secp256k1_sign_batch_stage9(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, |batch| sign_batch(batch, sign_stage9))
+}
+
+fn secp256k1_cggmp21_aux_info_stage1(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::aux_info_stage1)
+}
+
+fn secp256k1_cggmp21_aux_info_stage2(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::aux_info_stage2)
+}
+
+fn secp256k1_cggmp21_aux_info_finalize(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::aux_info_finalize)
+}
+
+fn secp256k1_cggmp21_presign_stage1(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::presign_stage1)
+}
+
+fn secp256k1_cggmp21_presign_stage2(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::presign_stage2)
+}
+
+fn secp256k1_cggmp21_presign_stage3(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::presign_stage3)
+}
+
+fn secp256k1_cggmp21_presign_output(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::presign_output)
+}
+
+fn secp256k1_cggmp21_sign(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::sign)
+}
+
+fn secp256k1_cggmp21_sign_combine(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, cggmp21::sign_combine)
+}
+
+fn secp256k1_tweak_key(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, tweak_key)
+}
+
+fn secp256k1_export_xpub(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, export_xpub)
+}
+
+fn secp256k1_parse_xpub(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, parse_xpub)
+}
+
+fn secp256k1_derive_xpub(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, derive_xpub)
+}
+
+fn secp256k1_verify_signature(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, verify_signature)
+}
+
+fn secp256k1_recover_public_key(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, recover_public_key)
+}
+
+fn secp256k1_encode_signature(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, encode_signature)
+}
+
+fn secp256k1_eth_tx_hash(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, eth_tx_hash)
+}
+
+fn secp256k1_eth_tx_encode_signed(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, eth_tx_encode_signed)
+}
+
+fn secp256k1_eip712_hash(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, eip712_hash)
+}
+
+fn secp256k1_eip712_recover(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, eip712_recover)
+}
+
+fn secp256k1_psbt_sighashes(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, psbt_sighashes)
+}
+
+fn secp256k1_psbt_add_signatures(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, psbt_add_signatures)
+}
+
+fn secp256k1_address(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, address)
+}
+
+fn secp256k1_inspect_key_share(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, inspect_key_share)
+}
+
+fn secp256k1_audit_key_share(cx: FunctionContext) -> JsResult<JsString> {
+    call(cx, audit_key_share)
+}
+
+#[neon::main]
+fn main(mut cx: ModuleContext) -> NeonResult<()> {
+    cx.export_function("hello", hello)?;
+    cx.export_function("secp256k1_keygen_stage1", secp256k1_keygen_stage1)?;
+    cx.export_function("secp256k1_keygen_stage2", secp256k1_keygen_stage2)?;
+    cx.export_function("secp256k1_keygen_stage3", secp256k1_keygen_stage3)?;
+    cx.export_function("secp256k1_keygen_stage4", secp256k1_keygen_stage4)?;
+    cx.export_function("secp256k1_keygen_finalize", secp256k1_keygen_finalize)?;
+    cx.export_function("secp256k1_construct_private_key", secp256k1_construct_private_key)?;
+    cx.export_function("secp256k1_sign_stage1", secp256k1_sign_stage1)?;
+    cx.export_function("secp256k1_sign_stage2", secp256k1_sign_stage2)?;
+    cx.export_function("secp256k1_sign_stage3", secp256k1_sign_stage3)?;
+    cx.export_function("secp256k1_sign_stage4", secp256k1_sign_stage4)?;
+    cx.export_function("secp256k1_sign_stage5", secp256k1_sign_stage5)?;
+    cx.export_function("secp256k1_sign_stage6", secp256k1_sign_stage6)?;
+    cx.export_function("secp256k1_sign_stage7", secp256k1_sign_stage7)?;
+    cx.export_function("secp256k1_sign_stage8", secp256k1_sign_stage8)?;
+    cx.export_function("secp256k1_sign_stage9", secp256k1_sign_stage9)?;
+    cx.export_function("secp256k1_sign_batch_stage1", secp256k1_sign_batch_stage1)?;
+    cx.export_function("secp256k1_sign_batch_stage2", secp256k1_sign_batch_stage2)?;
+    cx.export_function("secp256k1_sign_batch_stage3", secp256k1_sign_batch_stage3)?;
+    cx.export_function("secp256k1_sign_batch_stage4", secp256k1_sign_batch_stage4)?;
+    cx.export_function("secp256k1_sign_batch_stage5", secp256k1_sign_batch_stage5)?;
+    cx.export_function("secp256k1_sign_batch_stage6", secp256k1_sign_batch_stage6)?;
+    cx.export_function("secp256k1_sign_batch_stage7", secp256k1_sign_batch_stage7)?;
+    cx.export_function("secp256k1_sign_batch_stage8", secp256k1_sign_batch_stage8)?;
+    cx.export_function("secp256k1_sign_batch_stage9", secp256k1_sign_batch_stage9)?;
+    cx.export_function("secp256k1_cggmp21_aux_info_stage1", secp256k1_cggmp21_aux_info_stage1)?;
+    cx.export_function("secp256k1_cggmp21_aux_info_stage2", secp256k1_cggmp21_aux_info_stage2)?;
+    cx.export_function("secp256k1_cggmp21_aux_info_finalize", secp256k1_cggmp21_aux_info_finalize)?;
+    cx.export_function("secp256k1_cggmp21_presign_stage1", secp256k1_cggmp21_presign_stage1)?;
+    cx.export_function("secp256k1_cggmp21_presign_stage2", secp256k1_cggmp21_presign_stage2)?;
+    cx.export_function("secp256k1_cggmp21_presign_stage3", secp256k1_cggmp21_presign_stage3)?;
+    cx.export_function("secp256k1_cggmp21_presign_output", secp256k1_cggmp21_presign_output)?;
+    cx.export_function("secp256k1_cggmp21_sign", secp256k1_cggmp21_sign)?;
+    cx.export_function("secp256k1_cggmp21_sign_combine", secp256k1_cggmp21_sign_combine)?;
+    cx.export_function("secp256k1_tweak_key", secp256k1_tweak_key)?;
+    cx.export_function("secp256k1_export_xpub", secp256k1_export_xpub)?;
+    cx.export_function("secp256k1_parse_xpub", secp256k1_parse_xpub)?;
+    cx.export_function("secp256k1_derive_xpub", secp256k1_derive_xpub)?;
+    cx.export_function("secp256k1_verify_signature", secp256k1_verify_signature)?;
+    cx.export_function("secp256k1_recover_public_key", secp256k1_recover_public_key)?;
+    cx.export_function("secp256k1_encode_signature", secp256k1_encode_signature)?;
+    cx.export_function("secp256k1_eth_tx_hash", secp256k1_eth_tx_hash)?;
+    cx.export_function("secp256k1_eth_tx_encode_signed", secp256k1_eth_tx_encode_signed)?;
+    cx.export_function("secp256k1_eip712_hash", secp256k1_eip712_hash)?;
+    cx.export_function("secp256k1_eip712_recover", secp256k1_eip712_recover)?;
+    cx.export_function("secp256k1_psbt_sighashes", secp256k1_psbt_sighashes)?;
+    cx.export_function("secp256k1_psbt_add_signatures", secp256k1_psbt_add_signatures)?;
+    cx.export_function("secp256k1_address", secp256k1_address)?;
+    cx.export_function("secp256k1_inspect_key_share", secp256k1_inspect_key_share)?;
+    cx.export_function("secp256k1_audit_key_share", secp256k1_audit_key_share)?;
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Checks on the Paillier moduli and ring-Pedersen parameters (N-tilde, h1,
+//! h2) every party publishes in keygen round 1. The MtA range proofs of
+//! signing are only sound if these are well formed, and the signing stages
+//! take them straight from the `LocalKey`.
+
+use curv::arithmetic::{BitManipulation, Modulo, NumberTests, EGCD};
+use curv::elliptic::curves::secp256_k1::Secp256k1;
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::KeyGenBroadcastMessage1;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+
+use crate::error::Error;
+use crate::party::PartyIndex;
+
+/// Keygen multiplies two 1024-bit primes, which gives at least 2047 bits.
+pub const MIN_MODULUS_BITS: usize = 2047;
+
+/// Moduli must not have a prime factor below this bound.
+const SMALL_FACTOR_BOUND: u32 = 1 << 12;
+
+pub(crate) fn small_primes() -> impl Iterator<Item = u32> {
+    (2..SMALL_FACTOR_BOUND).filter(|&n| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+}
+
+pub(crate) fn modulus_issues(name: &str, n: &BigInt, issues: &mut Vec<String>) {
+    let bits = n.bit_length();
+    if bits < MIN_MODULUS_BITS {
+        issues.push(format!("{} has {} bits, at least {} are required", name, bits, MIN_MODULUS_BITS));
+    }
+    if let Some(p) = small_primes().find(|&p| BigInt::modulus(n, &BigInt::from(p)).is_zero()) {
+        issues.push(format!("{} is divisible by {}", name, p));
+    }
+}
+
+/// What is wrong with a party's Paillier modulus `n` and ring-Pedersen
+/// parameters, empty if nothing is.
+pub fn parameter_issues(n: &BigInt, n_tilde: &BigInt, h1: &BigInt, h2: &BigInt) -> Vec<String> {
+    let mut issues = Vec::new();
+    modulus_issues("Paillier modulus", n, &mut issues);
+    modulus_issues("N-tilde", n_tilde, &mut issues);
+    ring_pedersen_issues(n_tilde, [("h1", h1), ("h2", h2)], &mut issues);
+    issues
+}
+
+/// The two ring-Pedersen generators must be distinct units of N-tilde other
+/// than 1 and -1.
+pub(crate) fn ring_pedersen_issues(n_tilde: &BigInt, generators: [(&str, &BigInt); 2], issues: &mut Vec<String>) {
+    let one = BigInt::from(1);
+    for &(name, h) in generators.iter() {
+        if h <= &one || h >= &(n_tilde - &one) {
+            issues.push(format!("{} is not in 2..N-tilde - 1", name));
+        } else if BigInt::egcd(h, n_tilde).0 != one {
+            issues.push(format!("{} shares a factor with N-tilde", name));
+        }
+    }
+    if generators[0].1 == generators[1].1 {
+        issues.push(format!("{} equals {}", generators[0].0, generators[1].0));
+    }
+}
+
+/// What is wrong with the parameters a keygen round 1 broadcast publishes.
+pub fn broadcast_issues(bc1: &KeyGenBroadcastMessage1) -> Vec<String> {
+    let statement = &bc1.dlog_statement;
+    parameter_issues(&bc1.e.n, &statement.N, &statement.g, &statement.ni)
+}
+
+/// Verifies the proofs in a keygen round 1 broadcast that its sender knows
+/// the discrete logs of h2 to base h1 and of h1 to base h2, so neither
+/// generates a small subgroup of N-tilde.
+pub fn verify_ring_pedersen_proofs(bc1: &KeyGenBroadcastMessage1) -> bool {
+    let mut base_h2 = bc1.dlog_statement.clone();
+    std::mem::swap(&mut base_h2.g, &mut base_h2.ni);
+    bc1.composite_dlog_proof_base_h1.verify(&bc1.dlog_statement).is_ok()
+        && bc1.composite_dlog_proof_base_h2.verify(&base_h2).is_ok()
+}
+
+/// What is wrong with the parameters `local_key` holds for each party.
+pub fn local_key_issues(local_key: &LocalKey<Secp256k1>) -> Vec<(PartyIndex, Vec<String>)> {
+    local_key
+        .paillier_key_vec
+        .iter()
+        .zip(&local_key.h1_h2_n_tilde_vec)
+        .enumerate()
+        .map(|(i, (ek, statement))| {
+            let party = PartyIndex(i as u16 + 1);
+            (party, parameter_issues(&ek.n, &statement.N, &statement.g, &statement.ni))
+        })
+        .collect()
+}
+
+/// Blames the parties whose published parameters have issues.
+pub(crate) fn check(issues: Vec<(PartyIndex, Vec<String>)>) -> Result<(), Error> {
+    let bad: Vec<_> = issues.into_iter().filter(|(_, issues)| !issues.is_empty()).collect();
+    match bad.first() {
+        None => Ok(()),
+        Some((party, issues)) => Err(Error::Blame {
+            reason: format!("party {}: {}", party, issues.join(", ")),
+            parties: bad.iter().map(|(party, _)| party.0).collect(),
+        }),
+    }
+}
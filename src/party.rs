@@ -0,0 +1,64 @@
+//! How parties are addressed.
+//!
+//! Keygen gives every key share a 1-based [`PartyIndex`] (`local_key.i`)
+//! that stays fixed for the life of the key. A signing session is run by a
+//! quorum of those parties, listed in `parties`, and every per-party vector
+//! of the session is ordered like that list. A [`Position`] is the 0-based
+//! offset of a party in it. Stage inputs only name parties by index, the
+//! stages derive positions themselves.
+
+use std::fmt;
+
+use curv::elliptic::curves::secp256_k1::Secp256k1;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// 1-based index of a key share, as assigned in keygen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct PartyIndex(pub u16);
+
+/// 0-based offset of a party in a session's `parties`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position(pub usize);
+
+impl PartyIndex {
+    /// The party holding `local_key`.
+    pub fn of(local_key: &LocalKey<Secp256k1>) -> Self {
+        PartyIndex(local_key.i)
+    }
+
+    /// 0-based offset into per-key vectors such as `pk_vec` and
+    /// `paillier_key_vec`, which are ordered by party index.
+    pub fn offset(self) -> usize {
+        usize::from(self.0) - 1
+    }
+
+    /// Position of this party in `parties`.
+    pub fn position(self, parties: &[PartyIndex]) -> Option<Position> {
+        parties.iter().position(|&p| p == self).map(Position)
+    }
+}
+
+impl From<u16> for PartyIndex {
+    fn from(index: u16) -> Self {
+        PartyIndex(index)
+    }
+}
+
+impl fmt::Display for PartyIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Position of the party holding `local_key` in `parties`.
+pub(crate) fn own_position(parties: &[PartyIndex], local_key: &LocalKey<Secp256k1>) -> Result<Position, Error> {
+    let party = PartyIndex::of(local_key);
+    party
+        .position(parties)
+        .ok_or_else(|| Error::InvalidInput(format!("parties does not include this key's party {}", party)))
+}
@@ -0,0 +1,457 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::bip32::hash160;
+use crate::error::Error;
+
+const MAGIC: &[u8] = b"psbt\xff";
+const PSBT_GLOBAL_UNSIGNED_TX: u8 = 0x00;
+const PSBT_IN_NON_WITNESS_UTXO: u8 = 0x00;
+const PSBT_IN_WITNESS_UTXO: u8 = 0x01;
+const PSBT_IN_PARTIAL_SIG: u8 = 0x02;
+const PSBT_IN_SIGHASH_TYPE: u8 = 0x03;
+const PSBT_IN_REDEEM_SCRIPT: u8 = 0x04;
+
+const SIGHASH_ALL: u32 = 0x01;
+const SIGHASH_NONE: u32 = 0x02;
+const SIGHASH_SINGLE: u32 = 0x03;
+const SIGHASH_ANYONECANPAY: u32 = 0x80;
+/// The bits a defined sighash type may set.
+const SIGHASH_MASK: u32 = 0x9f;
+
+type Map = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// A BIP174 PSBT kept as raw key-value maps, so that fields this crate does
+/// not understand survive a parse/serialize round trip untouched.
+#[derive(Clone, Debug)]
+pub struct Psbt {
+    global: Map,
+    inputs: Vec<Map>,
+    outputs: Vec<Map>,
+    tx: Transaction,
+}
+
+#[derive(Clone, Debug)]
+struct Transaction {
+    version: [u8; 4],
+    inputs: Vec<TxIn>,
+    outputs: Vec<TxOut>,
+    lock_time: [u8; 4],
+}
+
+#[derive(Clone, Debug)]
+struct TxIn {
+    outpoint: [u8; 36],
+    script_sig: Vec<u8>,
+    sequence: [u8; 4],
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct TxOut {
+    value: u64,
+    script_pubkey: Vec<u8>,
+}
+
+/// BIP143 digest of one input spendable by our key.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct InputSighash {
+    pub input: usize,
+    pub sighash: [u8; 32],
+    pub sighash_type: u32,
+}
+
+impl Psbt {
+    pub fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(invalid("missing magic bytes"));
+        }
+        let global = reader.map()?;
+        let tx = global
+            .iter()
+            .find(|(key, _)| key.as_slice() == [PSBT_GLOBAL_UNSIGNED_TX])
+            .ok_or_else(|| invalid("missing unsigned transaction"))
+            .and_then(|(_, value)| Transaction::parse(value))?;
+        let inputs = (0..tx.inputs.len())
+            .map(|_| reader.map())
+            .collect::<Result<Vec<_>, _>>()?;
+        let outputs = (0..tx.outputs.len())
+            .map(|_| reader.map())
+            .collect::<Result<Vec<_>, _>>()?;
+        reader.finish()?;
+        Ok(Psbt {
+            global,
+            inputs,
+            outputs,
+            tx,
+        })
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = MAGIC.to_vec();
+        for map in std::iter::once(&self.global).chain(&self.inputs).chain(&self.outputs) {
+            for (key, value) in map {
+                write_var_bytes(&mut out, key);
+                write_var_bytes(&mut out, value);
+            }
+            out.push(0x00);
+        }
+        out
+    }
+
+    /// Sighashes of every P2WPKH or P2SH-P2WPKH input locked to
+    /// `public_key` (33-byte compressed). Other inputs are skipped. Inputs
+    /// asking for a sighash type other than SIGHASH_ALL are refused unless
+    /// the type is in `allowed_sighash_types`, so the PSBT creator cannot
+    /// make the quorum sign a weaker commitment.
+    pub fn segwit_v0_sighashes(
+        &self,
+        public_key: &[u8],
+        allowed_sighash_types: &[u32],
+    ) -> Result<Vec<InputSighash>, Error> {
+        let key_hash = hash160(public_key);
+        let mut witness_program = vec![0x00, 0x14];
+        witness_program.extend_from_slice(&key_hash);
+
+        let mut sighashes = Vec::new();
+        for (index, input) in self.inputs.iter().enumerate() {
+            let utxo = match self.spent_output(index)? {
+                Some(utxo) => utxo,
+                None => continue,
+            };
+            let spends_our_key = utxo.script_pubkey == witness_program
+                || utxo.script_pubkey == p2sh_script_pubkey(&witness_program);
+            if !spends_our_key {
+                continue;
+            }
+            let sighash_type = match find(input, PSBT_IN_SIGHASH_TYPE) {
+                Some(value) if value.len() == 4 => {
+                    u32::from_le_bytes([value[0], value[1], value[2], value[3]])
+                }
+                Some(_) => return Err(invalid("bad sighash type")),
+                None => SIGHASH_ALL,
+            };
+            if sighash_type & !SIGHASH_MASK != 0 {
+                return Err(Error::InvalidPsbtInput(index, "undefined sighash type"));
+            }
+            if sighash_type != SIGHASH_ALL && !allowed_sighash_types.contains(&sighash_type) {
+                return Err(Error::InvalidPsbtInput(index, "sighash type not allowed"));
+            }
+            let sighash = self.bip143_sighash(index, &key_hash, utxo.value, sighash_type)?;
+            sighashes.push(InputSighash {
+                input: index,
+                sighash,
+                sighash_type,
+            });
+        }
+        Ok(sighashes)
+    }
+
+    /// Adds a PSBT_IN_PARTIAL_SIG entry, plus the redeem script for
+    /// P2SH-P2WPKH inputs when the creator left it out.
+    pub fn insert_partial_signature(
+        &mut self,
+        input: usize,
+        public_key: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        let utxo = self
+            .spent_output(input)?
+            .ok_or_else(|| invalid("input has no UTXO information"))?;
+        let mut witness_program = vec![0x00, 0x14];
+        witness_program.extend_from_slice(&hash160(public_key));
+        let map = &mut self.inputs[input];
+        if utxo.script_pubkey == p2sh_script_pubkey(&witness_program)
+            && find(map, PSBT_IN_REDEEM_SCRIPT).is_none()
+        {
+            map.push((vec![PSBT_IN_REDEEM_SCRIPT], witness_program));
+        }
+        let mut key = vec![PSBT_IN_PARTIAL_SIG];
+        key.extend_from_slice(public_key);
+        map.retain(|(k, _)| *k != key);
+        map.push((key, signature.to_vec()));
+        Ok(())
+    }
+
+    /// The output an input spends. The full previous transaction is
+    /// preferred over the witness UTXO, since only its amount can be checked
+    /// against the outpoint.
+    fn spent_output(&self, index: usize) -> Result<Option<TxOut>, Error> {
+        let input = self.inputs.get(index).ok_or_else(|| invalid("no such input"))?;
+        let witness_utxo = find(input, PSBT_IN_WITNESS_UTXO)
+            .map(|value| {
+                let mut reader = Reader::new(value);
+                let utxo = TxOut::parse(&mut reader)?;
+                reader.finish()?;
+                Ok(utxo)
+            })
+            .transpose()?;
+        let value = match find(input, PSBT_IN_NON_WITNESS_UTXO) {
+            Some(value) => value,
+            None => return Ok(witness_utxo),
+        };
+        let prev = Transaction::parse(value)?;
+        let outpoint = &self.tx.inputs[index].outpoint;
+        if prev.txid() != outpoint[..32] {
+            return Err(Error::InvalidPsbtInput(index, "previous transaction does not match the outpoint"));
+        }
+        let vout = u32::from_le_bytes([outpoint[32], outpoint[33], outpoint[34], outpoint[35]]);
+        let utxo = prev
+            .outputs
+            .get(vout as usize)
+            .cloned()
+            .ok_or(Error::InvalidPsbtInput(index, "previous output index out of range"))?;
+        if witness_utxo.map_or(false, |witness_utxo| witness_utxo != utxo) {
+            return Err(Error::InvalidPsbtInput(index, "witness UTXO differs from the previous transaction"));
+        }
+        Ok(Some(utxo))
+    }
+
+    fn bip143_sighash(
+        &self,
+        index: usize,
+        key_hash: &[u8; 20],
+        amount: u64,
+        sighash_type: u32,
+    ) -> Result<[u8; 32], Error> {
+        let base_type = sighash_type & 0x1f;
+        let anyone_can_pay = sighash_type & SIGHASH_ANYONECANPAY != 0;
+        if ![SIGHASH_ALL, SIGHASH_NONE, SIGHASH_SINGLE].contains(&base_type) {
+            return Err(invalid("unsupported sighash type"));
+        }
+        let tx = &self.tx;
+
+        let hash_prevouts = if anyone_can_pay {
+            [0; 32]
+        } else {
+            double_sha256(&tx.inputs.iter().flat_map(|i| i.outpoint.to_vec()).collect::<Vec<_>>())
+        };
+        let hash_sequence = if anyone_can_pay || base_type != SIGHASH_ALL {
+            [0; 32]
+        } else {
+            double_sha256(&tx.inputs.iter().flat_map(|i| i.sequence.to_vec()).collect::<Vec<_>>())
+        };
+        let hash_outputs = if base_type == SIGHASH_ALL {
+            let mut outputs = Vec::new();
+            tx.outputs.iter().for_each(|o| o.write(&mut outputs));
+            double_sha256(&outputs)
+        } else if base_type == SIGHASH_SINGLE && index < tx.outputs.len() {
+            let mut output = Vec::new();
+            tx.outputs[index].write(&mut output);
+            double_sha256(&output)
+        } else {
+            [0; 32]
+        };
+
+        let mut preimage = Vec::new();
+        preimage.extend_from_slice(&tx.version);
+        preimage.extend_from_slice(&hash_prevouts);
+        preimage.extend_from_slice(&hash_sequence);
+        preimage.extend_from_slice(&tx.inputs[index].outpoint);
+        // scriptCode of P2WPKH is the P2PKH script of the key hash
+        preimage.extend_from_slice(&[0x19, 0x76, 0xa9, 0x14]);
+        preimage.extend_from_slice(key_hash);
+        preimage.extend_from_slice(&[0x88, 0xac]);
+        preimage.extend_from_slice(&amount.to_le_bytes());
+        preimage.extend_from_slice(&tx.inputs[index].sequence);
+        preimage.extend_from_slice(&hash_outputs);
+        preimage.extend_from_slice(&tx.lock_time);
+        preimage.extend_from_slice(&sighash_type.to_le_bytes());
+        Ok(double_sha256(&preimage))
+    }
+}
+
+impl Transaction {
+    /// Accepts both the legacy and the BIP144 witness serialization, since
+    /// non-witness UTXOs are full previous transactions.
+    fn parse(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let reader = &mut reader;
+        let mut version = [0; 4];
+        version.copy_from_slice(reader.take(4)?);
+        let mut input_count = reader.var_int()?;
+        let segwit = input_count == 0;
+        if segwit {
+            if reader.take(1)? != [0x01] {
+                return Err(invalid("bad segwit flag"));
+            }
+            input_count = reader.var_int()?;
+        }
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            let mut outpoint = [0; 36];
+            outpoint.copy_from_slice(reader.take(36)?);
+            let script_sig = reader.var_bytes()?.to_vec();
+            let mut sequence = [0; 4];
+            sequence.copy_from_slice(reader.take(4)?);
+            inputs.push(TxIn {
+                outpoint,
+                script_sig,
+                sequence,
+            });
+        }
+        let output_count = reader.var_int()?;
+        let outputs = (0..output_count)
+            .map(|_| TxOut::parse(reader))
+            .collect::<Result<Vec<_>, _>>()?;
+        if segwit {
+            for _ in 0..inputs.len() {
+                for _ in 0..reader.var_int()? {
+                    reader.var_bytes()?;
+                }
+            }
+        }
+        let mut lock_time = [0; 4];
+        lock_time.copy_from_slice(reader.take(4)?);
+        reader.finish()?;
+        Ok(Transaction {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+        })
+    }
+
+    /// Double SHA-256 of the legacy serialization, without witnesses.
+    fn txid(&self) -> [u8; 32] {
+        let mut out = self.version.to_vec();
+        write_var_int(&mut out, self.inputs.len() as u64);
+        for input in &self.inputs {
+            out.extend_from_slice(&input.outpoint);
+            write_var_bytes(&mut out, &input.script_sig);
+            out.extend_from_slice(&input.sequence);
+        }
+        write_var_int(&mut out, self.outputs.len() as u64);
+        self.outputs.iter().for_each(|o| o.write(&mut out));
+        out.extend_from_slice(&self.lock_time);
+        double_sha256(&out)
+    }
+}
+
+impl TxOut {
+    fn parse(reader: &mut Reader) -> Result<Self, Error> {
+        let mut value = [0; 8];
+        value.copy_from_slice(reader.take(8)?);
+        Ok(TxOut {
+            value: u64::from_le_bytes(value),
+            script_pubkey: reader.var_bytes()?.to_vec(),
+        })
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.value.to_le_bytes());
+        write_var_bytes(out, &self.script_pubkey);
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn finish(&self) -> Result<(), Error> {
+        if !self.is_empty() {
+            return Err(invalid("trailing data"));
+        }
+        Ok(())
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(invalid("unexpected end of data"));
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+
+    fn var_int(&mut self) -> Result<u64, Error> {
+        let n = match self.take(1)?[0] {
+            0xfd => 2,
+            0xfe => 4,
+            0xff => 8,
+            n => return Ok(u64::from(n)),
+        };
+        let mut bytes = [0; 8];
+        bytes[..n].copy_from_slice(self.take(n)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn var_bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.var_int()?;
+        if len > self.data.len() as u64 {
+            return Err(invalid("unexpected end of data"));
+        }
+        self.take(len as usize)
+    }
+
+    fn map(&mut self) -> Result<Map, Error> {
+        let mut map = Map::new();
+        loop {
+            let key = self.var_bytes()?;
+            if key.is_empty() {
+                return Ok(map);
+            }
+            let value = self.var_bytes()?;
+            if map.iter().any(|(k, _)| k.as_slice() == key) {
+                return Err(invalid("duplicate key"));
+            }
+            map.push((key.to_vec(), value.to_vec()));
+        }
+    }
+}
+
+fn find(map: &Map, key_type: u8) -> Option<&[u8]> {
+    map.iter()
+        .find(|(key, _)| key.as_slice() == [key_type])
+        .map(|(_, value)| value.as_slice())
+}
+
+fn p2sh_script_pubkey(redeem_script: &[u8]) -> Vec<u8> {
+    let mut script = vec![0xa9, 0x14];
+    script.extend_from_slice(&hash160(redeem_script));
+    script.push(0x87);
+    script
+}
+
+fn write_var_int(out: &mut Vec<u8>, n: u64) {
+    match n {
+        n if n < 0xfd => out.push(n as u8),
+        n if n <= 0xffff => {
+            out.push(0xfd);
+            out.extend_from_slice(&(n as u16).to_le_bytes());
+        }
+        n if n <= 0xffff_ffff => {
+            out.push(0xfe);
+            out.extend_from_slice(&(n as u32).to_le_bytes());
+        }
+        n => {
+            out.push(0xff);
+            out.extend_from_slice(&n.to_le_bytes());
+        }
+    }
+}
+
+fn write_var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_var_int(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn double_sha256(data: &[u8]) -> [u8; 32] {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&Sha256::digest(&Sha256::digest(data)));
+    hash
+}
+
+fn invalid(reason: &'static str) -> Error {
+    Error::InvalidPsbt(reason)
+}
@@ -0,0 +1,209 @@
+//! PyO3 bindings with the same JSON-in, JSON-out functions as the Neon
+//! module. Stages release the GIL while they run.
+//!
+//! Malformed inputs raise `ValueError`. Failures raise `MpcError`, or its
+//! subclass `BlameError` whose args are `(reason, blamed_parties)`.
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::Error;
+use crate::*;
+
+create_exception!(mpc, MpcError, PyException);
+create_exception!(mpc, BlameError, MpcError);
+
+enum CallError {
+    Input(serde_json::Error),
+    Stage(Error),
+}
+
+fn call<I, O>(py: Python, json: &str, f: fn(&I) -> Result<O, Error>) -> PyResult<String>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+{
+    let result = py.allow_threads(|| {
+        let input: I = serde_json::from_str(json).map_err(CallError::Input)?;
+        let output = f(&input).map_err(CallError::Stage)?;
+        Ok(serde_json::to_string(&output).unwrap())
+    });
+    result.map_err(|e| match e {
+        CallError::Input(e) => PyValueError::new_err(e.to_string()),
+        CallError::Stage(e @ Error::InvalidInput(_)) => PyValueError::new_err(e.to_string()),
+        CallError::Stage(Error::Blame { reason, parties }) => BlameError::new_err((reason, parties)),
+        CallError::Stage(e) => MpcError::new_err(e.to_string()),
+    })
+}
+
+#[pyfunction]
+fn secp256k1_keygen_stage1(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, keygen_stage1)
+}
+
+#[pyfunction]
+fn secp256k1_keygen_stage2(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, keygen_stage2)
+}
+
+#[pyfunction]
+fn secp256k1_keygen_stage3(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, keygen_stage3)
+}
+
+#[pyfunction]
+fn secp256k1_keygen_stage4(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, keygen_stage4)
+}
+
+#[pyfunction]
+fn secp256k1_keygen_finalize(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, keygen_finalize)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage1(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage1)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage2(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage2)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage3(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage3)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage4(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage4)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage5(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage5)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage6(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage6)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage7(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage7)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage8(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage8)
+}
+
+#[pyfunction]
+fn secp256k1_sign_stage9(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, sign_stage9)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_aux_info_stage1(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::aux_info_stage1)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_aux_info_stage2(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::aux_info_stage2)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_aux_info_finalize(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::aux_info_finalize)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_presign_stage1(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::presign_stage1)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_presign_stage2(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::presign_stage2)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_presign_stage3(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::presign_stage3)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_presign_output(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::presign_output)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_sign(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::sign)
+}
+
+#[pyfunction]
+fn secp256k1_cggmp21_sign_combine(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, cggmp21::sign_combine)
+}
+
+#[pyfunction]
+fn secp256k1_verify_signature(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, verify_signature)
+}
+
+#[pyfunction]
+fn secp256k1_recover_public_key(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, recover_public_key)
+}
+
+#[pyfunction]
+fn secp256k1_inspect_key_share(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, inspect_key_share)
+}
+
+#[pyfunction]
+fn secp256k1_audit_key_share(py: Python, input: &str) -> PyResult<String> {
+    call(py, input, audit_key_share)
+}
+
+#[pymodule]
+fn mpc(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("MpcError", py.get_type::<MpcError>())?;
+    m.add("BlameError", py.get_type::<BlameError>())?;
+    m.add_function(wrap_pyfunction!(secp256k1_keygen_stage1, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_keygen_stage2, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_keygen_stage3, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_keygen_stage4, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_keygen_finalize, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage1, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage2, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage3, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage4, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage5, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage6, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage7, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage8, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_sign_stage9, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_aux_info_stage1, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_aux_info_stage2, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_aux_info_finalize, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_presign_stage1, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_presign_stage2, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_presign_stage3, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_presign_output, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_sign, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_cggmp21_sign_combine, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_verify_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_recover_public_key, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_inspect_key_share, m)?)?;
+    m.add_function(wrap_pyfunction!(secp256k1_audit_key_share, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,427 @@
+//! JSON Schemas and TypeScript declarations for every stage input and
+//! output, generated from the Rust structs by the `mpc-schema` binary.
+//!
+//! curv and multi-party-ecdsa types have no schemas of their own. Points,
+//! scalars, big integers, key shares and signatures are described by the
+//! shadow types below, which mirror their serde layout. Protocol messages
+//! that callers only pass from one stage to the next are opaque objects.
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{
+    ArrayValidation, InstanceType, Metadata, ObjectValidation, RootSchema, Schema, SchemaObject,
+    SingleOrVec,
+};
+use schemars::{JsonSchema, Map};
+
+use crate::cggmp21::*;
+use crate::keygen::*;
+use crate::sign::*;
+use crate::wallet::*;
+
+fn described(instance_type: InstanceType, description: &str) -> Schema {
+    SchemaObject {
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_string()),
+            ..Default::default()
+        })),
+        instance_type: Some(instance_type.into()),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// A secp256k1 point as serialized by curv.
+#[derive(JsonSchema)]
+pub struct Point {
+    pub curve: String,
+    /// SEC1 encoding of the point.
+    pub point: Vec<u8>,
+}
+
+/// A secp256k1 scalar as serialized by curv.
+#[derive(JsonSchema)]
+pub struct Scalar {
+    pub curve: String,
+    /// Big-endian bytes of the scalar.
+    pub scalar: Vec<u8>,
+}
+
+pub struct BigInt;
+
+impl JsonSchema for BigInt {
+    fn schema_name() -> String {
+        "BigInt".to_string()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+        described(InstanceType::String, "A big integer as serialized by curv, in hexadecimal.")
+    }
+}
+
+/// A final signature with its recovery id.
+#[derive(JsonSchema)]
+pub struct SignatureRecid {
+    pub r: Scalar,
+    pub s: Scalar,
+    pub recid: u8,
+}
+
+/// Feldman VSS commitments to a party's polynomial.
+#[derive(JsonSchema)]
+pub struct VerifiableSS {
+    pub parameters: ShamirSecretSharing,
+    pub commitments: Vec<Point>,
+}
+
+#[derive(JsonSchema)]
+pub struct ShamirSecretSharing {
+    pub threshold: u16,
+    pub share_count: u16,
+}
+
+/// Keygen decommitment broadcast in stage 1.
+#[derive(JsonSchema)]
+pub struct KeyGenDecommitMessage1 {
+    pub blind_factor: BigInt,
+    pub y_i: Point,
+}
+
+/// A party's share of the joint key.
+#[derive(JsonSchema)]
+pub struct SharedKeys {
+    pub y: Point,
+    pub x_i: Scalar,
+}
+
+/// The key share a party keeps after keygen.
+#[derive(JsonSchema)]
+pub struct LocalKey {
+    pub paillier_dk: DecryptionKey,
+    /// Public key shares of all parties, by keygen index.
+    pub pk_vec: Vec<Point>,
+    pub keys_linear: SharedKeys,
+    pub paillier_key_vec: Vec<EncryptionKey>,
+    /// The joint public key.
+    pub y_sum_s: Point,
+    pub h1_h2_n_tilde_vec: Vec<DLogStatement>,
+    pub vss_scheme: VerifiableSS,
+    /// Keygen index of this party, 1-based.
+    pub i: u16,
+    pub t: u16,
+    pub n: u16,
+}
+
+macro_rules! opaque {
+    ($($name:ident: $description:expr,)*) => {
+        $(
+            pub struct $name;
+
+            impl JsonSchema for $name {
+                fn schema_name() -> String {
+                    stringify!($name).to_string()
+                }
+
+                fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                    described(InstanceType::Object, $description)
+                }
+            }
+        )*
+    };
+}
+
+opaque! {
+    Keys: "A party's keygen secrets. Keep it until keygen stage 3.",
+    KeyGenBroadcastMessage1: "Keygen commitment broadcast in stage 1.",
+    DLogProof: "Proof of knowledge of a key share's discrete log.",
+    EncryptionKey: "A Paillier public key.",
+    DecryptionKey: "A Paillier secret key.",
+    DLogStatement: "A party's h1, h2 and N-tilde for range proofs.",
+    SignKeys: "A party's signing secrets for one signature.",
+    MessageA: "MtA message from the initiator.",
+    MessageB: "MtA response to a counterparty.",
+    SignBroadcastPhase1: "Commitment to g^gamma_i broadcast in sign stage 1.",
+    SignDecommitPhase1: "Decommitment of g^gamma_i broadcast in sign stage 1.",
+    PedersenProof: "Proof that T_i is a valid Pedersen commitment.",
+    PDLwSlackProof: "Proof that R_i and the MtA ciphertext share a discrete log.",
+    HomoELGamalProof: "Proof that S_i is consistent with T_i.",
+    LocalSignature: "A party's signing state for the online stages.",
+    PrmProof: "CGGMP21 proof that s and t generate the same subgroup.",
+    ModProof: "CGGMP21 proof that a Paillier modulus is a Paillier-Blum one.",
+    FacProof: "CGGMP21 proof that a Paillier modulus has no small factors.",
+    EncProof: "CGGMP21 proof that K_i encrypts a value in range.",
+    AffGProof: "CGGMP21 proof that an MtA response matches a public point.",
+    LogStarProof: "CGGMP21 proof that a ciphertext and a point share a discrete log.",
+    MulProof: "CGGMP21 proof that H_i encrypts the product of two plaintexts.",
+    MulStarProof: "CGGMP21 proof that H-hat_i encrypts k_i times a public point's scalar.",
+}
+
+fn settings() -> SchemaSettings {
+    SchemaSettings::draft07()
+}
+
+macro_rules! roots {
+    ($($ty:ty,)*) => {
+        /// The JSON Schema of every stage input and output, by type name.
+        pub fn schemas() -> Vec<(String, RootSchema)> {
+            vec![$(
+                (<$ty>::schema_name(), settings().into_generator().into_root_schema_for::<$ty>()),
+            )*]
+        }
+
+        /// TypeScript declarations of every stage input and output and the
+        /// types they contain.
+        pub fn typescript() -> String {
+            let mut gen = settings().into_generator();
+            $(gen.subschema_for::<$ty>();)*
+            declarations(gen.definitions())
+        }
+    };
+}
+
+roots! {
+    Secp256k1KeyGenStage1Input,
+    Secp256k1KeyGenStage1Output,
+    Secp256k1KeyGenStage2Input,
+    Secp256k1KeyGenStage2Output,
+    Secp256k1KeyGenStage3Input,
+    Secp256k1KeyGenStage3Output,
+    Secp256k1KeyGenStage4Input,
+    Secp256k1KeyGenStage4Output,
+    Secp256k1KeyGenFinalizeInput,
+    Secp256k1KeyShare,
+    Secp256k1ConstructPrivateKeyInput,
+    Secp256k1TweakKeyInput,
+    Secp256k1TweakKeyOutput,
+    Secp256k1SignStage1Input,
+    Secp256k1SignStage1Output,
+    Secp256k1SignStage2Input,
+    Secp256k1SignStage2Output,
+    Secp256k1SignStage3Input,
+    Secp256k1SignStage3Output,
+    Secp256k1SignStage4Input,
+    Secp256k1SignStage4Output,
+    Secp256k1SignStage5Input,
+    Secp256k1SignStage5Output,
+    Secp256k1SignStage6Input,
+    Secp256k1SignStage6Output,
+    Secp256k1SignStage7Input,
+    Secp256k1SignStage7Output,
+    Secp256k1SignStage8Input,
+    Secp256k1SignStage8Output,
+    Secp256k1SignStage9Input,
+    Secp256k1SignStage9Output,
+    Secp256k1ExportXpubInput,
+    Secp256k1ExportXpubOutput,
+    Secp256k1ParseXpubInput,
+    Secp256k1ParseXpubOutput,
+    Secp256k1DeriveXpubInput,
+    Secp256k1DeriveXpubOutput,
+    Secp256k1VerifySignatureInput,
+    Secp256k1VerifySignatureOutput,
+    Secp256k1RecoverPublicKeyInput,
+    Secp256k1RecoverPublicKeyOutput,
+    Secp256k1EncodeSignatureInput,
+    Secp256k1EthTxHashInput,
+    Secp256k1EthTxHashOutput,
+    Secp256k1EthTxEncodeSignedInput,
+    Secp256k1EthTxEncodeSignedOutput,
+    Secp256k1Eip712HashInput,
+    Secp256k1Eip712HashOutput,
+    Secp256k1Eip712RecoverInput,
+    Secp256k1Eip712RecoverOutput,
+    Secp256k1PsbtSighashesInput,
+    Secp256k1PsbtSighashesOutput,
+    Secp256k1PsbtAddSignaturesInput,
+    Secp256k1PsbtAddSignaturesOutput,
+    Secp256k1AddressInput,
+    Secp256k1AddressOutput,
+    Secp256k1InspectKeyShareInput,
+    Secp256k1InspectKeyShareOutput,
+    Secp256k1AuditKeyShareOutput,
+    Secp256k1Cggmp21AuxInfoStage1Input,
+    Secp256k1Cggmp21AuxInfoStage1Output,
+    Secp256k1Cggmp21AuxInfoStage2Input,
+    Secp256k1Cggmp21AuxInfoStage2Output,
+    Secp256k1Cggmp21AuxInfoFinalizeInput,
+    Secp256k1Cggmp21KeyShare,
+    Secp256k1Cggmp21PresignStage1Input,
+    Secp256k1Cggmp21PresignStage1Output,
+    Secp256k1Cggmp21PresignStage2Input,
+    Secp256k1Cggmp21PresignStage2Output,
+    Secp256k1Cggmp21PresignStage3Input,
+    Secp256k1Cggmp21PresignStage3Output,
+    Secp256k1Cggmp21PresignOutputInput,
+    Secp256k1Cggmp21Presignature,
+    Secp256k1Cggmp21SignInput,
+    Secp256k1Cggmp21SignOutput,
+    Secp256k1Cggmp21SignCombineInput,
+    Secp256k1Cggmp21SignCombineOutput,
+    // Outputs of construct_private_key and encode_signature
+    Scalar,
+    crate::signature::EncodedSignature,
+}
+
+fn declarations(definitions: &Map<String, Schema>) -> String {
+    let mut out = String::from("// Generated by mpc-schema from the Rust structs, do not edit.\n");
+    for (name, schema) in definitions {
+        out.push('\n');
+        if let Schema::Object(object) = schema {
+            doc(&mut out, object, "");
+        }
+        let ty = typescript_type(schema, "");
+        if is_interface(schema) {
+            out.push_str(&format!("export interface {} {}\n", name, ty));
+        } else {
+            out.push_str(&format!("export type {} = {};\n", name, ty));
+        }
+    }
+    out
+}
+
+fn is_interface(schema: &Schema) -> bool {
+    match schema {
+        Schema::Object(object) => {
+            object.instance_type == Some(InstanceType::Object.into())
+                && object.subschemas.is_none()
+                && object.object.as_ref().map_or(false, |o| !o.properties.is_empty())
+        }
+        Schema::Bool(_) => false,
+    }
+}
+
+fn doc(out: &mut String, object: &SchemaObject, indent: &str) {
+    let description = match object.metadata.as_ref().and_then(|m| m.description.as_ref()) {
+        Some(description) => description,
+        None => return,
+    };
+    let lines: Vec<&str> = description.lines().collect();
+    if lines.len() == 1 {
+        out.push_str(&format!("{}/** {} */\n", indent, lines[0]));
+        return;
+    }
+    out.push_str(&format!("{}/**\n", indent));
+    for line in lines {
+        out.push_str(&format!("{} * {}\n", indent, line).replace(" * \n", " *\n"));
+    }
+    out.push_str(&format!("{} */\n", indent));
+}
+
+fn typescript_type(schema: &Schema, indent: &str) -> String {
+    let object = match schema {
+        Schema::Bool(true) => return "unknown".to_string(),
+        Schema::Bool(false) => return "never".to_string(),
+        Schema::Object(object) => object,
+    };
+    if let Some(reference) = &object.reference {
+        return reference.trim_start_matches("#/definitions/").to_string();
+    }
+    if let Some(value) = &object.const_value {
+        return value.to_string();
+    }
+    if let Some(values) = &object.enum_values {
+        return union(values.iter().map(|v| v.to_string()).collect());
+    }
+
+    let mut parts = Vec::new();
+    match &object.instance_type {
+        Some(SingleOrVec::Single(t)) => parts.push(instance_type(t, object, indent)),
+        Some(SingleOrVec::Vec(types)) => parts.push(union(
+            types.iter().map(|t| instance_type(t, object, indent)).collect(),
+        )),
+        None => {}
+    }
+    if let Some(subschemas) = &object.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            parts.extend(all_of.iter().map(|s| typescript_type(s, indent)));
+        }
+        for alternatives in subschemas.one_of.iter().chain(subschemas.any_of.iter()) {
+            parts.push(union(alternatives.iter().map(|s| typescript_type(s, indent)).collect()));
+        }
+    }
+    match parts.len() {
+        0 => "unknown".to_string(),
+        1 => parts.remove(0),
+        _ => parts.into_iter().map(group).collect::<Vec<_>>().join(" & "),
+    }
+}
+
+fn instance_type(t: &InstanceType, object: &SchemaObject, indent: &str) -> String {
+    match t {
+        InstanceType::Null => "null".to_string(),
+        InstanceType::Boolean => "boolean".to_string(),
+        InstanceType::Integer | InstanceType::Number => "number".to_string(),
+        InstanceType::String => "string".to_string(),
+        InstanceType::Array => array_type(object.array.as_deref(), indent),
+        InstanceType::Object => object_type(object.object.as_deref(), indent),
+    }
+}
+
+fn array_type(array: Option<&ArrayValidation>, indent: &str) -> String {
+    match array.and_then(|a| a.items.as_ref()) {
+        Some(SingleOrVec::Single(item)) => format!("{}[]", group(typescript_type(item, indent))),
+        Some(SingleOrVec::Vec(items)) => format!(
+            "[{}]",
+            items
+                .iter()
+                .map(|item| typescript_type(item, indent))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        None => "unknown[]".to_string(),
+    }
+}
+
+fn object_type(object: Option<&ObjectValidation>, indent: &str) -> String {
+    let object = match object {
+        Some(object) => object,
+        None => return "Record<string, unknown>".to_string(),
+    };
+    if object.properties.is_empty() {
+        return match &object.additional_properties {
+            Some(value) => format!("{{ [key: string]: {} }}", typescript_type(value, indent)),
+            None => "Record<string, unknown>".to_string(),
+        };
+    }
+
+    let inner = format!("{}  ", indent);
+    let mut out = String::from("{\n");
+    for (name, property) in &object.properties {
+        if let Schema::Object(property) = property {
+            doc(&mut out, property, &inner);
+        }
+        let optional = if object.required.contains(name) { "" } else { "?" };
+        out.push_str(&format!(
+            "{}{}{}: {};\n",
+            inner,
+            name,
+            optional,
+            typescript_type(property, &inner)
+        ));
+    }
+    out.push_str(indent);
+    out.push('}');
+    out
+}
+
+fn union(types: Vec<String>) -> String {
+    types.join(" | ")
+}
+
+/// Parenthesizes a union or intersection so it can be nested.
+fn group(ty: String) -> String {
+    let mut depth = 0;
+    let mut top_level_operator = false;
+    for c in ty.chars() {
+        match c {
+            '{' | '[' | '(' | '<' => depth += 1,
+            '}' | ']' | ')' | '>' => depth -= 1,
+            '|' | '&' if depth == 0 => top_level_operator = true,
+            _ => {}
+        }
+    }
+    if top_level_operator {
+        format!("({})", ty)
+    } else {
+        ty
+    }
+}
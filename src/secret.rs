@@ -0,0 +1,131 @@
+//! Secret material in stage inputs and outputs.
+//!
+//! Key shares, Paillier decryption keys and per-session nonces are held in
+//! [`Secret`], which wipes them when dropped and hides them from `Debug`.
+//! Stage messages serialize exactly as before, the wrapper is transparent.
+
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use curv::cryptographic_primitives::secret_sharing::feldman_vss::VerifiableSS;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::{Keys, SharedKeys, SignKeys};
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+/// Overwrites the secret parts of a value in place.
+pub trait Wipe {
+    fn wipe(&mut self);
+}
+
+impl Wipe for Scalar<Secp256k1> {
+    fn wipe(&mut self) {
+        // curv clears the replaced scalar when it is dropped
+        *self = Scalar::zero();
+    }
+}
+
+impl Wipe for BigInt {
+    fn wipe(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<T: Wipe> Wipe for Vec<T> {
+    fn wipe(&mut self) {
+        self.iter_mut().for_each(Wipe::wipe);
+    }
+}
+
+/// The keygen stage 2 VSS: the public scheme, the shares dealt to every
+/// party and this party's index.
+impl Wipe for (VerifiableSS<Secp256k1>, Vec<Scalar<Secp256k1>>, usize) {
+    fn wipe(&mut self) {
+        self.1.wipe();
+    }
+}
+
+impl Wipe for Keys {
+    fn wipe(&mut self) {
+        self.u_i.wipe();
+        self.dk.p.wipe();
+        self.dk.q.wipe();
+        self.xhi.wipe();
+        self.xhi_inv.wipe();
+    }
+}
+
+impl Wipe for SharedKeys {
+    fn wipe(&mut self) {
+        self.x_i.wipe();
+    }
+}
+
+impl Wipe for SignKeys {
+    fn wipe(&mut self) {
+        self.w_i.wipe();
+        self.k_i.wipe();
+        self.gamma_i.wipe();
+    }
+}
+
+impl Wipe for LocalKey<Secp256k1> {
+    fn wipe(&mut self) {
+        self.paillier_dk.p.wipe();
+        self.paillier_dk.q.wipe();
+        self.keys_linear.wipe();
+    }
+}
+
+/// A secret value, wiped on drop and redacted in `Debug` output.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T: Wipe>(T);
+
+impl<T: Wipe> Secret<T> {
+    pub fn new(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: Wipe> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+impl<T: Wipe> Deref for Secret<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Wipe> DerefMut for Secret<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Wipe> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.wipe();
+    }
+}
+
+impl<T: Wipe> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+/// Parses a stage input and wipes the JSON text, which holds the same
+/// secrets as the parsed value.
+pub fn from_json<T: DeserializeOwned>(json: String) -> serde_json::Result<T> {
+    let json = Zeroizing::new(json);
+    serde_json::from_str(&json)
+}
@@ -0,0 +1,576 @@
+use std::iter;
+
+use curv::arithmetic::Converter;
+use curv::cryptographic_primitives::proofs::sigma_correct_homomorphic_elgamal_enc::HomoELGamalProof;
+use curv::cryptographic_primitives::proofs::sigma_valid_pedersen::PedersenProof;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::*;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use multi_party_ecdsa::utilities::mta::{MessageA, MessageB};
+use multi_party_ecdsa::utilities::zk_pdl_with_slack::PDLwSlackProof;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::error::Error;
+use crate::message::MessageHashing;
+use crate::parameters;
+use crate::party::{own_position, PartyIndex, Position};
+use crate::secret::Secret;
+use crate::signature::{self, EncodedSignature};
+use crate::validate::Validate;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage1Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage1Output {
+    #[cfg_attr(feature = "schema", schemars(with = "(crate::schema::MessageA, crate::schema::BigInt)"))]
+    pub m_a: (MessageA, BigInt),
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignKeys"))]
+    pub sign_key: Secret<SignKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignBroadcastPhase1"))]
+    pub bc1: SignBroadcastPhase1,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignDecommitPhase1"))]
+    pub decom1: SignDecommitPhase1,
+}
+
+pub fn sign_stage1(input: &Secp256k1SignStage1Input) -> Result<Secp256k1SignStage1Output, Error> {
+    input.validate()?;
+    parameters::check(parameters::local_key_issues(&input.local_key))?;
+    let parties: Vec<usize> = input.parties.iter().map(|p| p.offset()).collect();
+    let sign_key = SignKeys::create(
+        &input.local_key.keys_linear.x_i,
+        &input.local_key.vss_scheme,
+        PartyIndex::of(&input.local_key).offset(),
+        &parties,
+    );
+    let (bc1, decom1) = sign_key.phase1_broadcast();
+
+    let party_ek = &input.local_key.paillier_key_vec[PartyIndex::of(&input.local_key).offset()].clone();
+    let m_a = MessageA::a(&sign_key.k_i, &party_ek, &input.local_key.h1_h2_n_tilde_vec);
+
+    Ok(Secp256k1SignStage1Output {
+        sign_key: Secret::new(sign_key),
+        m_a,
+        bc1,
+        decom1,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage2Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageA>"))]
+    pub m_as: Vec<MessageA>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignKeys"))]
+    pub sign_key: Secret<SignKeys>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage2Output {
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub betas: Secret<Vec<Scalar<Secp256k1>>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub nis: Secret<Vec<Scalar<Secp256k1>>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageB>"))]
+    pub m_b_gammas: Vec<MessageB>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageB>"))]
+    pub m_b_ws: Vec<MessageB>,
+}
+
+pub fn sign_stage2(input: &Secp256k1SignStage2Input) -> Result<Secp256k1SignStage2Output, Error> {
+    input.validate()?;
+    let ttag = input.parties.len();
+    let l_s: Vec<_> = input
+        .parties
+        .iter()
+        .map(|p| p.offset())
+        .collect();
+    let Position(i) = own_position(&input.parties, &input.local_key)?;
+    let counterparties: Vec<usize> = (0..ttag - 1)
+        .map(|j| if j < i { j } else { j + 1 })
+        .collect();
+
+    #[cfg(feature = "parallel")]
+    let counterparties = counterparties.into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let counterparties = counterparties.into_iter();
+
+    let responses = counterparties
+        .map(|ind| -> Result<_, Error> {
+            let (m_b_gamma, beta_gamma, _beta_randomness, _beta_tag) = MessageB::b(
+                &input.sign_key.gamma_i,
+                &input.local_key.paillier_key_vec[l_s[ind]],
+                input.m_as[ind].clone(),
+                &input.local_key.h1_h2_n_tilde_vec,
+            )?;
+
+            let (m_b_w, beta_wi, _, _) = MessageB::b(
+                &input.sign_key.w_i,
+                &input.local_key.paillier_key_vec[l_s[ind]],
+                input.m_as[ind].clone(),
+                &input.local_key.h1_h2_n_tilde_vec,
+            )?;
+
+            Ok((m_b_gamma, beta_gamma, m_b_w, beta_wi))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    let mut m_b_gammas = Vec::with_capacity(responses.len());
+    let mut betas = Vec::with_capacity(responses.len());
+    let mut m_b_ws = Vec::with_capacity(responses.len());
+    let mut nis = Vec::with_capacity(responses.len());
+    for (m_b_gamma, beta_gamma, m_b_w, beta_wi) in responses {
+        m_b_gammas.push(m_b_gamma);
+        betas.push(beta_gamma);
+        m_b_ws.push(m_b_w);
+        nis.push(beta_wi);
+    }
+
+    Ok(Secp256k1SignStage2Output {
+        nis: Secret::new(nis),
+        betas: Secret::new(betas),
+        // Send P2P
+        m_b_gammas,
+        m_b_ws,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage3Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignKeys"))]
+    pub sign_key: Secret<SignKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub nis: Secret<Vec<Scalar<Secp256k1>>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub betas: Secret<Vec<Scalar<Secp256k1>>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageB>"))]
+    pub m_b_gammas: Vec<MessageB>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageB>"))]
+    pub m_b_ws: Vec<MessageB>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage3Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub delta_i: Scalar<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub t_i: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub l_i: Secret<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub sigma_i: Secret<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::PedersenProof"))]
+    pub t_i_proof: PedersenProof<Secp256k1, Sha256>,
+}
+
+pub fn sign_stage3(input: &Secp256k1SignStage3Input) -> Result<Secp256k1SignStage3Output, Error> {
+    input.validate()?;
+    let mut alpha_vec = Vec::new();
+    let mut miu_vec = Vec::new();
+
+    let ttag = input.parties.len();
+    let Position(index) = own_position(&input.parties, &input.local_key)?;
+    let l_s: Vec<_> = input
+        .parties
+        .iter()
+        .map(|p| p.offset())
+        .collect();
+    let g_w_vec = SignKeys::g_w_vec(
+        &input.local_key.pk_vec[..],
+        &l_s[..],
+        &input.local_key.vss_scheme,
+    );
+    for j in 0..ttag - 1 {
+        let ind = if j < index { j } else { j + 1 };
+        let blame = |reason: &str| Error::Blame {
+            reason: reason.to_string(),
+            parties: vec![input.parties[ind].0],
+        };
+        let m_b = input.m_b_gammas[j].clone();
+
+        let alpha_ij_gamma = m_b
+            .verify_proofs_get_alpha(&input.local_key.paillier_dk, &input.sign_key.k_i)
+            .map_err(|_| blame("invalid MtA proof for gamma_i"))?;
+        let m_b = input.m_b_ws[j].clone();
+        let alpha_ij_wi = m_b
+            .verify_proofs_get_alpha(&input.local_key.paillier_dk, &input.sign_key.k_i)
+            .map_err(|_| blame("invalid MtA proof for w_i"))?;
+        // How to add BIP32
+        if m_b.b_proof.pk != g_w_vec[ind] {
+            return Err(blame("MtA w_i does not match the public key share"));
+        }
+
+        alpha_vec.push(alpha_ij_gamma.0);
+        miu_vec.push(alpha_ij_wi.0);
+    }
+
+    let delta_i = input.sign_key.phase2_delta_i(&alpha_vec, &input.betas);
+
+    let sigma_i = input.sign_key.phase2_sigma_i(&miu_vec, &input.nis);
+    let (t_i, l_i, t_i_proof) = SignKeys::phase3_compute_t_i(&sigma_i);
+
+    Ok(Secp256k1SignStage3Output {
+        delta_i,
+        t_i,
+        l_i: Secret::new(l_i),
+        sigma_i: Secret::new(sigma_i),
+        t_i_proof,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage4Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub deltas: Vec<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub ts: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::PedersenProof>"))]
+    pub t_proofs: Vec<PedersenProof<Secp256k1, Sha256>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage4Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub delta_inv: Scalar<Secp256k1>,
+}
+
+pub fn sign_stage4(input: &Secp256k1SignStage4Input) -> Result<Secp256k1SignStage4Output, Error> {
+    input.validate()?;
+    let ttag = input.parties.len();
+    for i in 0..ttag {
+        let blame = |reason: &str| Error::Blame {
+            reason: reason.to_string(),
+            parties: vec![input.parties[i].0],
+        };
+        if input.ts[i] != input.t_proofs[i].com {
+            return Err(blame("T_i does not match its Pedersen proof"));
+        }
+        PedersenProof::verify(&input.t_proofs[i])
+            .map_err(|_| blame("invalid Pedersen proof for T_i"))?;
+    }
+
+    let delta_inv = SignKeys::phase3_reconstruct_delta(&input.deltas);
+
+    Ok(Secp256k1SignStage4Output {
+        delta_inv,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage5Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignKeys"))]
+    pub sign_key: Secret<SignKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "(crate::schema::MessageA, crate::schema::BigInt)"))]
+    pub m_a: (MessageA, BigInt),
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageB>"))]
+    pub m_b_gammas: Vec<MessageB>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::SignBroadcastPhase1>"))]
+    pub bc1s: Vec<SignBroadcastPhase1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub delta_inv: Scalar<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::SignDecommitPhase1>"))]
+    pub decom1s: Vec<SignDecommitPhase1>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage5Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub r: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub r_dash: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::PDLwSlackProof>"))]
+    pub phase5_proofs: Vec<PDLwSlackProof>,
+}
+
+pub fn sign_stage5(input: &Secp256k1SignStage5Input) -> Result<Secp256k1SignStage5Output, Error> {
+    input.validate()?;
+    let ttag = input.parties.len();
+    let Position(index) = own_position(&input.parties, &input.local_key)?;
+    let b_proof_vec: Vec<_> = (0..ttag - 1).map(|i| &input.m_b_gammas[i].b_proof).collect();
+    let r = SignKeys::phase4(
+        &input.delta_inv,
+        &b_proof_vec[..],
+        input.decom1s.clone(),
+        &input.bc1s,
+        index,
+    )
+    .map_err(|e| Error::blame(e, &input.parties))?;
+
+    let r_dash = &r * &input.sign_key.k_i;
+
+    // each party sends first message to all other parties
+    let mut phase5_proofs = Vec::new();
+    let l_s: Vec<_> = input
+        .parties
+        .iter()
+        .map(|p| p.offset())
+        .collect();
+    for j in 0..ttag - 1 {
+        let ind = if j < index { j } else { j + 1 };
+        let proof = LocalSignature::phase5_proof_pdl(
+            &r_dash,
+            &r,
+            &input.m_a.0.c,
+            &input.local_key.paillier_key_vec[l_s[index]],
+            &input.sign_key.k_i,
+            &input.m_a.1,
+            &input.local_key.h1_h2_n_tilde_vec[l_s[ind]],
+        );
+
+        phase5_proofs.push(proof);
+    }
+
+    Ok(Secp256k1SignStage5Output {
+        r,
+        r_dash,
+        phase5_proofs,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage6Input {
+    pub parties: Vec<PartyIndex>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::MessageA>"))]
+    pub m_as: Vec<MessageA>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub t_i: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub l_i: Secret<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub sigma_i: Secret<Scalar<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub r: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub r_dashes: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<Vec<crate::schema::PDLwSlackProof>>"))]
+    pub phase5_proofss: Vec<Vec<PDLwSlackProof>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage6Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub s_i:Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::HomoELGamalProof"))]
+    pub homo_elgamal_proof: HomoELGamalProof<Secp256k1, Sha256>,
+}
+
+pub fn sign_stage6(input: &Secp256k1SignStage6Input) -> Result<Secp256k1SignStage6Output, Error> {
+    input.validate()?;
+    let l_s: Vec<_> = input
+        .parties
+        .iter()
+        .map(|p| p.offset())
+        .collect();
+    let ttag = input.parties.len();
+
+    #[cfg(feature = "parallel")]
+    let parties = (0..ttag).into_par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let mut parties = 0..ttag;
+
+    parties.try_for_each(|i| {
+        LocalSignature::phase5_verify_pdl(
+            &input.phase5_proofss[i],
+            &input.r_dashes[i],
+            &input.r,
+            &input.m_as[i].c,
+            &input.local_key.paillier_key_vec[l_s[i]],
+            &input.local_key.h1_h2_n_tilde_vec,
+            &l_s,
+            i,
+        )
+        .map_err(|e| Error::blame(e, &input.parties))
+    })?;
+    LocalSignature::phase5_check_R_dash_sum(&input.r_dashes)?;
+
+    let (s_i, homo_elgamal_proof) = LocalSignature::phase6_compute_S_i_and_proof_of_consistency(
+        &input.r,
+        &input.t_i,
+        &input.sigma_i,
+        &input.l_i,
+    );
+
+    Ok(Secp256k1SignStage6Output {
+        s_i,
+        homo_elgamal_proof,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CompletedOfflineStage {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignKeys"))]
+    pub sign_key: Secret<SignKeys>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub ts: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub r: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub sigma_i: Secret<Scalar<Secp256k1>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage7Input {
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Point>"))]
+    pub ss: Vec<Point<Secp256k1>>,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::HomoELGamalProof>"))]
+    pub homo_elgamal_proofs: Vec<HomoELGamalProof<Secp256k1, Sha256>>,
+    pub parties: Vec<PartyIndex>,
+    pub completed_offline_stage: CompletedOfflineStage,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage7Output {}
+
+pub fn sign_stage7(input: &Secp256k1SignStage7Input) -> Result<Secp256k1SignStage7Output, Error> {
+    input.validate()?;
+    let r_vec: Vec<_> = iter::repeat(input.completed_offline_stage.r.clone())
+        .take(input.parties.len())
+        .collect();
+
+    LocalSignature::phase6_verify_proof(
+        &input.ss,
+        &input.homo_elgamal_proofs,
+        &r_vec,
+        &input.completed_offline_stage.ts,
+    )
+    .map_err(|e| Error::blame(e, &input.parties))?;
+    // TODO: BIP32 check
+    LocalSignature::phase6_check_S_i_sum(&input.completed_offline_stage.local_key.y_sum_s, &input.ss)?;
+
+    Ok(Secp256k1SignStage7Output {})
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage8Input {
+    pub completed_offline_stage: CompletedOfflineStage,
+    pub message: Vec<u8>,
+    #[serde(default)]
+    pub hashing: MessageHashing,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage8Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalSignature"))]
+    pub local_signature: LocalSignature,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub partial_signature: Scalar<Secp256k1>,
+    pub message_hash: [u8; 32],
+}
+
+pub fn sign_stage8(input: &Secp256k1SignStage8Input) -> Result<Secp256k1SignStage8Output, Error> {
+    input.validate()?;
+    let message_hash = input.hashing.digest(&input.message)?;
+
+    let local_signature = LocalSignature::phase7_local_sig(
+        &input.completed_offline_stage.sign_key.k_i,
+        &BigInt::from_bytes(&message_hash),
+        &input.completed_offline_stage.r,
+        &input.completed_offline_stage.sigma_i,
+        &input.completed_offline_stage.local_key.y_sum_s,
+    );
+    let partial_signature = local_signature.s_i.clone();
+    
+    Ok(Secp256k1SignStage8Output {
+        local_signature,
+        partial_signature,
+        message_hash,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage9Input {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalSignature"))]
+    pub local_signature: LocalSignature,
+    #[cfg_attr(feature = "schema", schemars(with = "Vec<crate::schema::Scalar>"))]
+    pub partial_signatures: Vec<Scalar<Secp256k1>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignStage9Output {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+    pub encoded: EncodedSignature,
+}
+
+pub fn sign_stage9(input: &Secp256k1SignStage9Input) -> Result<Secp256k1SignStage9Output, Error> {
+    input.validate()?;
+    let signature = input.local_signature.output_signature(&input.partial_signatures)?;
+    let signature = signature::normalize_s(&signature);
+    let encoded = signature::encode(&signature);
+    Ok(Secp256k1SignStage9Output {
+        signature,
+        encoded,
+    })
+}
+
+/// N independent signing sessions run by the same quorum. Every batch stage
+/// takes and returns one entry per session, in the same order, so a batch of
+/// N signatures needs as many network rounds as a single one.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1SignBatch<T> {
+    pub sessions: Vec<T>,
+}
+
+/// Runs `stage` on every session of the batch, failing with the first
+/// session that does. With the `parallel` feature the sessions run on the
+/// rayon thread pool.
+pub fn sign_batch<I: Sync, O: Send>(
+    batch: &Secp256k1SignBatch<I>,
+    stage: fn(&I) -> Result<O, Error>,
+) -> Result<Secp256k1SignBatch<O>, Error> {
+    #[cfg(feature = "parallel")]
+    let sessions = batch.sessions.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let sessions = batch.sessions.iter();
+
+    // Collected in session order, so the reported failure does not depend
+    // on scheduling
+    let results: Vec<_> = sessions.map(stage).collect();
+    let sessions = results.into_iter().enumerate()
+        .map(|(i, result)| result.map_err(|e| Error::Session(i, Box::new(e))))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Secp256k1SignBatch {
+        sessions,
+    })
+}
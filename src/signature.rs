@@ -0,0 +1,141 @@
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+const FIELD_PRIME: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+
+/// Plain ECDSA verification of `signature` over the 32-byte digest `message`.
+/// The recovery id is not needed and is ignored.
+pub fn verify(signature: &SignatureRecid, public_key: &Point<Secp256k1>, message: &BigInt) -> bool {
+    if signature.r.is_zero() || public_key.is_zero() {
+        return false;
+    }
+    let s_inv = match signature.s.invert() {
+        Some(s_inv) => s_inv,
+        None => return false,
+    };
+    let z = Scalar::<Secp256k1>::from_bigint(message);
+    let u1 = &z * &s_inv;
+    let u2 = &signature.r * &s_inv;
+    let r_point = Point::generator() * &u1 + public_key * &u2;
+    match r_point.x_coord() {
+        Some(x) => Scalar::<Secp256k1>::from_bigint(&x) == signature.r,
+        None => false,
+    }
+}
+
+/// Recovers the public key that produced `signature` over `message`, using
+/// the recovery id to pick among the candidate R points.
+pub fn recover(signature: &SignatureRecid, message: &BigInt) -> Result<Point<Secp256k1>, Error> {
+    if signature.recid > 3 {
+        return Err(Error::InvalidRecoveryId(signature.recid));
+    }
+    let r_inv = signature.r.invert().ok_or(Error::InvalidSignature)?;
+
+    let mut x = signature.r.to_bigint();
+    if signature.recid & 2 != 0 {
+        x = x + Scalar::<Secp256k1>::group_order();
+        if x >= BigInt::from_hex(FIELD_PRIME).unwrap() {
+            return Err(Error::InvalidRecoveryId(signature.recid));
+        }
+    }
+    let mut encoded = [0; 33];
+    encoded[0] = 0x02 | (signature.recid & 1);
+    encoded[1..].copy_from_slice(&to_32_bytes(&x));
+    let r_point = Point::<Secp256k1>::from_bytes(&encoded).map_err(|_| Error::InvalidSignature)?;
+
+    let z = Scalar::<Secp256k1>::from_bigint(message);
+    let public_key = (&r_point * &signature.s - Point::generator() * &z) * &r_inv;
+    if public_key.is_zero() {
+        return Err(Error::InvalidSignature);
+    }
+    Ok(public_key)
+}
+
+/// The final signature in the wire formats hosts usually need. All of them are
+/// built from the low-S form, with `recid` adjusted to match.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct EncodedSignature {
+    /// ASN.1 DER, as used in Bitcoin scripts (without the sighash byte).
+    pub der: Vec<u8>,
+    /// r || s, 64 bytes.
+    pub compact: Vec<u8>,
+    /// r || s || v with v = 27 + recid, 65 bytes (Ethereum style).
+    pub recoverable: Vec<u8>,
+    /// header || r || s with header = 31 + recid, 65 bytes (Bitcoin signed
+    /// message for a compressed public key).
+    pub bitcoin_compact: Vec<u8>,
+    pub recid: u8,
+}
+
+/// Replaces s by n - s when s is in the upper half of the group order and
+/// flips the parity bit of the recovery id accordingly.
+pub fn normalize_s(signature: &SignatureRecid) -> SignatureRecid {
+    let order = Scalar::<Secp256k1>::group_order();
+    let s = signature.s.to_bigint();
+    if s > order / BigInt::from(2) {
+        SignatureRecid {
+            r: signature.r.clone(),
+            s: Scalar::from_bigint(&(order - &s)),
+            recid: signature.recid ^ 1,
+        }
+    } else {
+        signature.clone()
+    }
+}
+
+pub fn encode(signature: &SignatureRecid) -> EncodedSignature {
+    let signature = normalize_s(signature);
+    let r = to_32_bytes(&signature.r.to_bigint());
+    let s = to_32_bytes(&signature.s.to_bigint());
+
+    let mut compact = Vec::with_capacity(64);
+    compact.extend_from_slice(&r);
+    compact.extend_from_slice(&s);
+
+    let mut recoverable = compact.clone();
+    recoverable.push(27 + signature.recid);
+
+    let mut bitcoin_compact = vec![31 + signature.recid];
+    bitcoin_compact.extend_from_slice(&compact);
+
+    let r = der_integer(&r);
+    let s = der_integer(&s);
+    let mut der = vec![0x30, (r.len() + s.len()) as u8];
+    der.extend_from_slice(&r);
+    der.extend_from_slice(&s);
+
+    EncodedSignature {
+        der,
+        compact,
+        recoverable,
+        bitcoin_compact,
+        recid: signature.recid,
+    }
+}
+
+pub fn to_32_bytes(n: &BigInt) -> [u8; 32] {
+    let bytes = n.to_bytes();
+    let mut out = [0; 32];
+    out[32 - bytes.len()..].copy_from_slice(&bytes);
+    out
+}
+
+fn der_integer(bytes: &[u8]) -> Vec<u8> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len() - 1);
+    let value = &bytes[start..];
+    let mut out = vec![0x02];
+    if value[0] & 0x80 != 0 {
+        out.push(value.len() as u8 + 1);
+        out.push(0);
+    } else {
+        out.push(value.len() as u8);
+    }
+    out.extend_from_slice(value);
+    out
+}
@@ -0,0 +1,374 @@
+//! Shape checks run at the start of every stage. The GG20 code indexes
+//! straight into the party and message vectors and subtracts 1 from party
+//! indices, so a malformed input would otherwise panic or underflow instead
+//! of failing with a description of what is wrong.
+
+use curv::elliptic::curves::secp256_k1::Secp256k1;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+
+use crate::cggmp21::*;
+use crate::error::Error;
+use crate::keygen::*;
+use crate::party::{own_position, PartyIndex};
+use crate::sign::*;
+
+pub(crate) trait Validate {
+    fn validate(&self) -> Result<(), Error>;
+}
+
+fn invalid(reason: String) -> Result<(), Error> {
+    Err(Error::InvalidInput(reason))
+}
+
+fn length(name: &str, actual: usize, expected: usize) -> Result<(), Error> {
+    if actual != expected {
+        return invalid(format!("{} has {} entries, expected {}", name, actual, expected));
+    }
+    Ok(())
+}
+
+/// Any t + 1 of the n parties can sign.
+fn parameters(threshold: u16, share_count: u16) -> Result<(), Error> {
+    if threshold == 0 || threshold >= share_count {
+        return invalid(format!(
+            "threshold must be at least 1 and below share_count, got {} and {}",
+            threshold, share_count
+        ));
+    }
+    Ok(())
+}
+
+fn keygen_index(name: &str, index: usize, share_count: u16) -> Result<(), Error> {
+    if index == 0 || index > usize::from(share_count) {
+        return invalid(format!("{} {} is not a party in 1..={}", name, index, share_count));
+    }
+    Ok(())
+}
+
+/// Indices of signing parties: non-zero and distinct.
+fn parties(parties: &[PartyIndex]) -> Result<(), Error> {
+    if parties.is_empty() {
+        return invalid("parties is empty".to_string());
+    }
+    for (i, &party) in parties.iter().enumerate() {
+        if party.0 == 0 {
+            return invalid("parties are numbered from 1, got 0".to_string());
+        }
+        if parties[..i].contains(&party) {
+            return invalid(format!("party {} appears twice in parties", party));
+        }
+    }
+    Ok(())
+}
+
+fn local_key(key: &LocalKey<Secp256k1>) -> Result<(), Error> {
+    parameters(key.t, key.n)?;
+    keygen_index("local_key.i", usize::from(key.i), key.n)?;
+    let n = usize::from(key.n);
+    length("local_key.pk_vec", key.pk_vec.len(), n)?;
+    length("local_key.paillier_key_vec", key.paillier_key_vec.len(), n)?;
+    length("local_key.h1_h2_n_tilde_vec", key.h1_h2_n_tilde_vec.len(), n)?;
+    length(
+        "local_key.vss_scheme.commitments",
+        key.vss_scheme.commitments.len(),
+        usize::from(key.t) + 1,
+    )
+}
+
+/// A signing quorum of at least t + 1 of the key's n parties, including the
+/// key's own party.
+fn quorum(parties: &[PartyIndex], key: &LocalKey<Secp256k1>) -> Result<(), Error> {
+    local_key(key)?;
+    self::parties(parties)?;
+    for party in parties {
+        keygen_index("party", usize::from(party.0), key.n)?;
+    }
+    if parties.len() <= usize::from(key.t) {
+        return invalid(format!(
+            "{} parties cannot sign with threshold {}, at least {} are needed",
+            parties.len(),
+            key.t,
+            key.t + 1
+        ));
+    }
+    own_position(parties, key).map(|_| ())
+}
+
+/// A CGGMP21 key share: a valid local key with every party's aux info, this
+/// party's own matching its Paillier key.
+fn key_share(share: &Secp256k1Cggmp21KeyShare) -> Result<(), Error> {
+    local_key(&share.local_key)?;
+    length("key_share.aux_infos", share.aux_infos.len(), usize::from(share.local_key.n))?;
+    let own = PartyIndex::of(&share.local_key).offset();
+    if share.aux_infos[own].n != share.paillier_key.n() {
+        return invalid(format!("key_share.aux_infos[{}] does not match key_share.paillier_key", own));
+    }
+    Ok(())
+}
+
+/// One entry per counterparty in the `field` of every message of a round.
+fn counterparty_entries<T>(
+    name: &str,
+    field: &str,
+    messages: &[T],
+    entries: impl Fn(&T) -> usize,
+    ttag: usize,
+) -> Result<(), Error> {
+    for (i, message) in messages.iter().enumerate() {
+        length(&format!("{}[{}].{}", name, i, field), entries(message), ttag - 1)?;
+    }
+    Ok(())
+}
+
+/// A presignature of a quorum with every party's Delta_i and S_i.
+fn presignature(presignature: &Secp256k1Cggmp21Presignature) -> Result<(), Error> {
+    parties(&presignature.parties)?;
+    let ttag = presignature.parties.len();
+    length("presignature.delta_points", presignature.delta_points.len(), ttag)?;
+    length("presignature.s_points", presignature.s_points.len(), ttag)
+}
+
+impl Validate for Secp256k1KeyGenStage1Input {
+    fn validate(&self) -> Result<(), Error> {
+        if self.index.0 == 0 {
+            return invalid("parties are numbered from 1, got index 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Validate for Secp256k1KeyGenStage2Input {
+    fn validate(&self) -> Result<(), Error> {
+        parameters(self.threshold, self.share_count)?;
+        keygen_index("key.party_index", self.key.party_index, self.share_count)?;
+        let n = usize::from(self.share_count);
+        length("bc1s", self.bc1s.len(), n)?;
+        length("decom1s", self.decom1s.len(), n)?;
+        length("chain_code_coms", self.chain_code_coms.len(), n)?;
+        length("chain_code_decoms", self.chain_code_decoms.len(), n)
+    }
+}
+
+impl Validate for Secp256k1KeyGenStage3Input {
+    fn validate(&self) -> Result<(), Error> {
+        parameters(self.threshold, self.share_count)?;
+        keygen_index("key.party_index", self.key.party_index, self.share_count)?;
+        let n = usize::from(self.share_count);
+        length("ys", self.ys.len(), n)?;
+        length("vss_schemes", self.vss_schemes.len(), n)?;
+        length("party_shares", self.party_shares.len(), n)
+    }
+}
+
+impl Validate for Secp256k1KeyGenStage4Input {
+    fn validate(&self) -> Result<(), Error> {
+        parameters(self.threshold, self.share_count)?;
+        let n = usize::from(self.share_count);
+        length("ys", self.ys.len(), n)?;
+        length("vss_schemes", self.vss_schemes.len(), n)?;
+        length("dlog_proofs", self.dlog_proofs.len(), n)
+    }
+}
+
+impl Validate for Secp256k1KeyGenFinalizeInput {
+    fn validate(&self) -> Result<(), Error> {
+        parameters(self.threshold, self.share_count)?;
+        keygen_index("key.party_index", self.key.party_index, self.share_count)?;
+        let n = usize::from(self.share_count);
+        length("bc1s", self.bc1s.len(), n)?;
+        length("dlog_proofs", self.dlog_proofs.len(), n)?;
+        length(
+            "vss_scheme.commitments",
+            self.vss_scheme.commitments.len(),
+            usize::from(self.threshold) + 1,
+        )
+    }
+}
+
+impl Validate for Secp256k1ConstructPrivateKeyInput {
+    fn validate(&self) -> Result<(), Error> {
+        parties(&self.parties)?;
+        let share_count = usize::from(self.vss_scheme.parameters.share_count);
+        if let Some(&party) = self.parties.iter().find(|p| usize::from(p.0) > share_count) {
+            return invalid(format!("party {} is not a party in 1..={}", party, share_count));
+        }
+        let threshold = usize::from(self.vss_scheme.parameters.threshold);
+        if self.parties.len() <= threshold {
+            return invalid(format!(
+                "{} shares cannot reconstruct a key with threshold {}",
+                self.parties.len(),
+                threshold
+            ));
+        }
+        length("xs", self.xs.len(), self.parties.len())
+    }
+}
+
+impl Validate for Secp256k1TweakKeyInput {
+    fn validate(&self) -> Result<(), Error> {
+        local_key(&self.local_key)
+    }
+}
+
+impl Validate for Secp256k1SignStage1Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.local_key)
+    }
+}
+
+impl Validate for Secp256k1SignStage2Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.local_key)?;
+        length("m_as", self.m_as.len(), self.parties.len())
+    }
+}
+
+impl Validate for Secp256k1SignStage3Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.local_key)?;
+        let counterparties = self.parties.len() - 1;
+        length("nis", self.nis.len(), counterparties)?;
+        length("betas", self.betas.len(), counterparties)?;
+        length("m_b_gammas", self.m_b_gammas.len(), counterparties)?;
+        length("m_b_ws", self.m_b_ws.len(), counterparties)
+    }
+}
+
+impl Validate for Secp256k1SignStage4Input {
+    fn validate(&self) -> Result<(), Error> {
+        parties(&self.parties)?;
+        let ttag = self.parties.len();
+        length("deltas", self.deltas.len(), ttag)?;
+        length("ts", self.ts.len(), ttag)?;
+        length("t_proofs", self.t_proofs.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1SignStage5Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.local_key)?;
+        let ttag = self.parties.len();
+        length("m_b_gammas", self.m_b_gammas.len(), ttag - 1)?;
+        length("bc1s", self.bc1s.len(), ttag)?;
+        length("decom1s", self.decom1s.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1SignStage6Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.local_key)?;
+        let ttag = self.parties.len();
+        length("m_as", self.m_as.len(), ttag)?;
+        length("r_dashes", self.r_dashes.len(), ttag)?;
+        length("phase5_proofss", self.phase5_proofss.len(), ttag)?;
+        for (i, proofs) in self.phase5_proofss.iter().enumerate() {
+            length(&format!("phase5_proofss[{}]", i), proofs.len(), ttag - 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl Validate for Secp256k1SignStage7Input {
+    fn validate(&self) -> Result<(), Error> {
+        quorum(&self.parties, &self.completed_offline_stage.local_key)?;
+        let ttag = self.parties.len();
+        length("ss", self.ss.len(), ttag)?;
+        length("homo_elgamal_proofs", self.homo_elgamal_proofs.len(), ttag)?;
+        length("completed_offline_stage.ts", self.completed_offline_stage.ts.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1SignStage8Input {
+    fn validate(&self) -> Result<(), Error> {
+        local_key(&self.completed_offline_stage.local_key)
+    }
+}
+
+impl Validate for Secp256k1SignStage9Input {
+    fn validate(&self) -> Result<(), Error> {
+        if self.partial_signatures.is_empty() {
+            return invalid("partial_signatures is empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Validate for Secp256k1Cggmp21AuxInfoStage1Input {
+    fn validate(&self) -> Result<(), Error> {
+        local_key(&self.local_key)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21AuxInfoStage2Input {
+    fn validate(&self) -> Result<(), Error> {
+        local_key(&self.local_key)?;
+        let n = usize::from(self.local_key.n);
+        length("commitments", self.commitments.len(), n)?;
+        length("decommitments", self.decommitments.len(), n)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21AuxInfoFinalizeInput {
+    fn validate(&self) -> Result<(), Error> {
+        local_key(&self.local_key)?;
+        let n = usize::from(self.local_key.n);
+        length("commitments", self.commitments.len(), n)?;
+        length("decommitments", self.decommitments.len(), n)?;
+        length("mod_proofs", self.mod_proofs.len(), n)?;
+        length("fac_proofs", self.fac_proofs.len(), n - 1)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21PresignStage1Input {
+    fn validate(&self) -> Result<(), Error> {
+        key_share(&self.key_share)?;
+        quorum(&self.parties, &self.key_share.local_key)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21PresignStage2Input {
+    fn validate(&self) -> Result<(), Error> {
+        key_share(&self.key_share)?;
+        quorum(&self.parties, &self.key_share.local_key)?;
+        let ttag = self.parties.len();
+        length("messages1", self.messages1.len(), ttag)?;
+        counterparty_entries("messages1", "enc_proofs", &self.messages1, |m| m.enc_proofs.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21PresignStage3Input {
+    fn validate(&self) -> Result<(), Error> {
+        key_share(&self.key_share)?;
+        quorum(&self.parties, &self.key_share.local_key)?;
+        let ttag = self.parties.len();
+        length("messages1", self.messages1.len(), ttag)?;
+        length("messages2", self.messages2.len(), ttag)?;
+        counterparty_entries("messages2", "mtas", &self.messages2, |m| m.mtas.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21PresignOutputInput {
+    fn validate(&self) -> Result<(), Error> {
+        key_share(&self.key_share)?;
+        quorum(&self.parties, &self.key_share.local_key)?;
+        let ttag = self.parties.len();
+        length("messages1", self.messages1.len(), ttag)?;
+        length("messages2", self.messages2.len(), ttag)?;
+        length("messages3", self.messages3.len(), ttag)?;
+        counterparty_entries("messages2", "mtas", &self.messages2, |m| m.mtas.len(), ttag)?;
+        counterparty_entries("messages3", "proofs", &self.messages3, |m| m.proofs.len(), ttag)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21SignInput {
+    fn validate(&self) -> Result<(), Error> {
+        presignature(&self.presignature)
+    }
+}
+
+impl Validate for Secp256k1Cggmp21SignCombineInput {
+    fn validate(&self) -> Result<(), Error> {
+        presignature(&self.presignature)?;
+        length("partial_signatures", self.partial_signatures.len(), self.presignature.parties.len())
+    }
+}
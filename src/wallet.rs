@@ -0,0 +1,499 @@
+//! Operations on a finished key and on final signatures: extended public
+//! keys, addresses, signature checks and chain-specific hashing and encoding.
+
+use curv::arithmetic::{BitManipulation, Converter};
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use serde::{Deserialize, Serialize};
+
+use crate::address::{self, AddressFormat};
+use crate::bip32::{self, ExtendedPublicKey, Network};
+use crate::eip712::TypedData;
+use crate::error::Error;
+use crate::ethereum::{self, Transaction};
+use crate::keygen::Secp256k1KeyShare;
+use crate::message::MessageHashing;
+use crate::parameters;
+use crate::party::PartyIndex;
+use crate::psbt::{InputSighash, Psbt};
+use crate::secret::Secret;
+use crate::signature::{self, EncodedSignature};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1ExportXpubInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub chain_code: [u8; 32],
+    #[serde(default)]
+    pub network: Network,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1ExportXpubOutput {
+    pub xpub: String,
+}
+
+pub fn export_xpub(input: &Secp256k1ExportXpubInput) -> Result<Secp256k1ExportXpubOutput, Error> {
+    let xpub = ExtendedPublicKey::new(input.local_key.y_sum_s.clone(), input.chain_code, input.network);
+    Ok(Secp256k1ExportXpubOutput {
+        xpub: xpub.to_base58(),
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1ParseXpubInput {
+    pub xpub: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1ParseXpubOutput {
+    pub extended_public_key: ExtendedPublicKey,
+}
+
+pub fn parse_xpub(input: &Secp256k1ParseXpubInput) -> Result<Secp256k1ParseXpubOutput, Error> {
+    let extended_public_key = ExtendedPublicKey::from_base58(&input.xpub)?;
+    Ok(Secp256k1ParseXpubOutput {
+        extended_public_key,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1DeriveXpubInput {
+    pub xpub: String,
+    pub path: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1DeriveXpubOutput {
+    pub xpub: String,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Scalar"))]
+    pub il: Scalar<Secp256k1>,
+}
+
+pub fn derive_xpub(input: &Secp256k1DeriveXpubInput) -> Result<Secp256k1DeriveXpubOutput, Error> {
+    let (child, il) = ExtendedPublicKey::from_base58(&input.xpub)?.derive_path(&input.path)?;
+    Ok(Secp256k1DeriveXpubOutput {
+        xpub: child.to_base58(),
+        public_key: child.public_key,
+        il,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1VerifySignatureInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+    /// The key to verify against. Give either this or `local_key`.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Point>"))]
+    pub public_key: Option<Point<Secp256k1>>,
+    /// A key share whose joint public key `y_sum_s` is verified against.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::LocalKey>"))]
+    pub local_key: Option<Secret<LocalKey<Secp256k1>>>,
+    pub message: Vec<u8>,
+    /// How `message` was hashed when it was signed. The default expects
+    /// the 32-byte digest itself.
+    #[serde(default)]
+    pub hashing: MessageHashing,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1VerifySignatureOutput {
+    pub valid: bool,
+}
+
+pub fn verify_signature(input: &Secp256k1VerifySignatureInput) -> Result<Secp256k1VerifySignatureOutput, Error> {
+    let public_key = match (&input.public_key, &input.local_key) {
+        (Some(public_key), None) => public_key,
+        (None, Some(local_key)) => &local_key.y_sum_s,
+        _ => return Err(Error::InvalidInput("give exactly one of public_key and local_key".to_string())),
+    };
+    let message_hash = input.hashing.digest(&input.message)?;
+    let valid = signature::verify(
+        &input.signature,
+        public_key,
+        &BigInt::from_bytes(&message_hash),
+    );
+    Ok(Secp256k1VerifySignatureOutput {
+        valid,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1RecoverPublicKeyInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+    pub message: Vec<u8>,
+    /// As in [`Secp256k1VerifySignatureInput`].
+    #[serde(default)]
+    pub hashing: MessageHashing,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1RecoverPublicKeyOutput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+}
+
+pub fn recover_public_key(input: &Secp256k1RecoverPublicKeyInput) -> Result<Secp256k1RecoverPublicKeyOutput, Error> {
+    let message_hash = input.hashing.digest(&input.message)?;
+    let public_key = signature::recover(&input.signature, &BigInt::from_bytes(&message_hash))?;
+    Ok(Secp256k1RecoverPublicKeyOutput {
+        public_key,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1EncodeSignatureInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+}
+
+pub fn encode_signature(input: &Secp256k1EncodeSignatureInput) -> Result<EncodedSignature, Error> {
+    Ok(signature::encode(&input.signature))
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1EthTxHashInput {
+    pub transaction: Transaction,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1EthTxHashOutput {
+    pub message_hash: [u8; 32],
+}
+
+pub fn eth_tx_hash(input: &Secp256k1EthTxHashInput) -> Result<Secp256k1EthTxHashOutput, Error> {
+    let message_hash = input.transaction.signing_hash()?;
+    Ok(Secp256k1EthTxHashOutput {
+        message_hash,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1EthTxEncodeSignedInput {
+    pub transaction: Transaction,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+    /// Key share of the signing quorum, the sender is derived from its
+    /// public key, or from the child at `path` if one is given.
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub chain_code: Option<[u8; 32]>,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1EthTxEncodeSignedOutput {
+    pub raw_transaction: String,
+    pub transaction_hash: String,
+    pub from: String,
+}
+
+pub fn eth_tx_encode_signed(input: &Secp256k1EthTxEncodeSignedInput) -> Result<Secp256k1EthTxEncodeSignedOutput, Error> {
+    let (public_key, _) = bip32::derive_public_key(
+        &input.local_key.y_sum_s,
+        input.chain_code,
+        input.path.as_deref(),
+    )?;
+    let message_hash = input.transaction.signing_hash()?;
+    // Refuse to hand out a transaction that would be attributed to another sender
+    let signer = signature::recover(&input.signature, &BigInt::from_bytes(&message_hash))?;
+    if signer != public_key {
+        return Err(Error::InvalidSignature);
+    }
+    let raw_transaction = input.transaction.encode_signed(&input.signature)?;
+    Ok(Secp256k1EthTxEncodeSignedOutput {
+        transaction_hash: format!("0x{}", hex::encode(ethereum::keccak256(&raw_transaction))),
+        raw_transaction: format!("0x{}", hex::encode(&raw_transaction)),
+        from: address::ethereum_address(&public_key),
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Eip712HashInput {
+    pub typed_data: TypedData,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Eip712HashOutput {
+    pub message_hash: [u8; 32],
+}
+
+pub fn eip712_hash(input: &Secp256k1Eip712HashInput) -> Result<Secp256k1Eip712HashOutput, Error> {
+    let message_hash = input.typed_data.signing_hash()?;
+    Ok(Secp256k1Eip712HashOutput {
+        message_hash,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Eip712RecoverInput {
+    pub typed_data: TypedData,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1Eip712RecoverOutput {
+    pub address: String,
+    pub message_hash: [u8; 32],
+}
+
+pub fn eip712_recover(input: &Secp256k1Eip712RecoverInput) -> Result<Secp256k1Eip712RecoverOutput, Error> {
+    let message_hash = input.typed_data.signing_hash()?;
+    let signer = signature::recover(&input.signature, &BigInt::from_bytes(&message_hash))?;
+    Ok(Secp256k1Eip712RecoverOutput {
+        address: address::ethereum_address(&signer),
+        message_hash,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1PsbtSighashesInput {
+    pub psbt: Vec<u8>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub chain_code: Option<[u8; 32]>,
+    pub path: Option<String>,
+    /// Sighash types besides SIGHASH_ALL the quorum agrees to sign.
+    #[serde(default)]
+    pub allowed_sighash_types: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1PsbtSighashesOutput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "Option<crate::schema::Scalar>"))]
+    pub il: Option<Scalar<Secp256k1>>,
+    pub inputs: Vec<InputSighash>,
+}
+
+pub fn psbt_sighashes(input: &Secp256k1PsbtSighashesInput) -> Result<Secp256k1PsbtSighashesOutput, Error> {
+    let (public_key, il) = bip32::derive_public_key(
+        &input.local_key.y_sum_s,
+        input.chain_code,
+        input.path.as_deref(),
+    )?;
+    let inputs = Psbt::parse(&input.psbt)?
+        .segwit_v0_sighashes(&public_key.to_bytes(true), &input.allowed_sighash_types)?;
+    Ok(Secp256k1PsbtSighashesOutput {
+        public_key,
+        il,
+        inputs,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1PsbtSignature {
+    pub input: usize,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::SignatureRecid"))]
+    pub signature: SignatureRecid,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1PsbtAddSignaturesInput {
+    pub psbt: Vec<u8>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+    pub signatures: Vec<Secp256k1PsbtSignature>,
+    /// Must match what was passed to `psbt_sighashes`.
+    #[serde(default)]
+    pub allowed_sighash_types: Vec<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1PsbtAddSignaturesOutput {
+    pub psbt: Vec<u8>,
+}
+
+pub fn psbt_add_signatures(input: &Secp256k1PsbtAddSignaturesInput) -> Result<Secp256k1PsbtAddSignaturesOutput, Error> {
+    let public_key = input.public_key.to_bytes(true);
+    let mut psbt = Psbt::parse(&input.psbt)?;
+    let sighashes = psbt.segwit_v0_sighashes(&public_key, &input.allowed_sighash_types)?;
+    for signed in input.signatures.iter() {
+        let sighash = match sighashes.iter().find(|s| s.input == signed.input) {
+            Some(sighash) => sighash,
+            None => return Err(Error::InvalidPsbtInput(signed.input, "not spendable by this key")),
+        };
+        if !signature::verify(&signed.signature, &input.public_key, &BigInt::from_bytes(&sighash.sighash)) {
+            return Err(Error::InvalidPsbtInput(signed.input, "signature does not verify"));
+        }
+        let mut encoded = signature::encode(&signed.signature).der;
+        encoded.push(sighash.sighash_type as u8);
+        psbt.insert_partial_signature(signed.input, &public_key, &encoded)?;
+    }
+    Ok(Secp256k1PsbtAddSignaturesOutput {
+        psbt: psbt.serialize(),
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1AddressInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+    pub format: AddressFormat,
+    pub chain_code: Option<[u8; 32]>,
+    pub path: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1AddressOutput {
+    pub address: String,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+}
+
+pub fn address(input: &Secp256k1AddressInput) -> Result<Secp256k1AddressOutput, Error> {
+    let (public_key, _) = bip32::derive_public_key(
+        &input.local_key.y_sum_s,
+        input.chain_code,
+        input.path.as_deref(),
+    )?;
+    let address = input.format.address(&public_key)?;
+    Ok(Secp256k1AddressOutput {
+        address,
+        public_key,
+    })
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1InspectKeyShareInput {
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::LocalKey"))]
+    pub local_key: Secret<LocalKey<Secp256k1>>,
+}
+
+/// The public facts about a key share, without any secret material.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1InspectKeyShareOutput {
+    pub index: PartyIndex,
+    pub threshold: u16,
+    pub share_count: u16,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key: Point<Secp256k1>,
+    #[cfg_attr(feature = "schema", schemars(with = "crate::schema::Point"))]
+    pub public_key_share: Point<Secp256k1>,
+    pub ethereum_address: String,
+    pub paillier_modulus_bits: usize,
+    /// Whether the share matches the public key share recorded for this
+    /// party and the joint public key it claims.
+    pub consistent: bool,
+}
+
+pub fn inspect_key_share(input: &Secp256k1InspectKeyShareInput) -> Result<Secp256k1InspectKeyShareOutput, Error> {
+    let key = &input.local_key;
+    let position = usize::from(key.i)
+        .checked_sub(1)
+        .filter(|&i| i < key.pk_vec.len() && i < key.paillier_key_vec.len())
+        .ok_or_else(|| Error::InvalidInput(format!("party index {} out of range", key.i)))?;
+    let public_key_share = Point::generator() * &key.keys_linear.x_i;
+    let consistent = public_key_share == key.pk_vec[position] && key.keys_linear.y == key.y_sum_s;
+    Ok(Secp256k1InspectKeyShareOutput {
+        index: PartyIndex::of(key),
+        threshold: key.t,
+        share_count: key.n,
+        public_key: key.y_sum_s.clone(),
+        ethereum_address: address::ethereum_address(&key.y_sum_s),
+        paillier_modulus_bits: key.paillier_key_vec[position].n.bit_length(),
+        public_key_share,
+        consistent,
+    })
+}
+
+/// The Paillier and ring-Pedersen parameters a key share holds for one party.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct PartyParameters {
+    pub party: PartyIndex,
+    pub paillier_modulus_bits: usize,
+    pub n_tilde_bits: usize,
+    /// Whether the party's h1/h2 proofs in its keygen broadcast verify.
+    pub proofs_verified: bool,
+    pub issues: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Secp256k1AuditKeyShareOutput {
+    pub parties: Vec<PartyParameters>,
+    /// No party has issues.
+    pub ok: bool,
+}
+
+/// Checks the parameters a saved key share holds for every party against
+/// the keygen broadcasts saved with it, and re-verifies their h1/h2 proofs.
+pub fn audit_key_share(input: &Secp256k1KeyShare) -> Result<Secp256k1AuditKeyShareOutput, Error> {
+    let key = &input.local_key;
+    let n = usize::from(key.n);
+    if key.paillier_key_vec.len() != n || key.h1_h2_n_tilde_vec.len() != n {
+        return Err(Error::InvalidInput(format!("local_key does not hold parameters for all {} parties", n)));
+    }
+    if input.bc1s.len() != n {
+        return Err(Error::InvalidInput(format!("bc1s has {} entries, expected {}", input.bc1s.len(), n)));
+    }
+
+    let parties: Vec<_> = parameters::local_key_issues(key)
+        .into_iter()
+        .map(|(party, mut issues)| {
+            let ek = &key.paillier_key_vec[party.offset()];
+            let statement = &key.h1_h2_n_tilde_vec[party.offset()];
+            let bc1 = &input.bc1s[party.offset()];
+            if bc1.e.n != ek.n {
+                issues.push("Paillier modulus differs from the keygen broadcast".to_string());
+            }
+            let published = &bc1.dlog_statement;
+            if (&published.N, &published.g, &published.ni) != (&statement.N, &statement.g, &statement.ni) {
+                issues.push("ring-Pedersen parameters differ from the keygen broadcast".to_string());
+            }
+            let proofs_verified = parameters::verify_ring_pedersen_proofs(bc1);
+            if !proofs_verified {
+                issues.push("h1/h2 proofs do not verify".to_string());
+            }
+            PartyParameters {
+                party,
+                paillier_modulus_bits: ek.n.bit_length(),
+                n_tilde_bits: statement.N.bit_length(),
+                proofs_verified,
+                issues,
+            }
+        })
+        .collect();
+    Ok(Secp256k1AuditKeyShareOutput {
+        ok: parties.iter().all(|p| p.issues.is_empty()),
+        parties,
+    })
+}
@@ -0,0 +1,383 @@
+//! wasm-bindgen bindings with the same exports and JSON format as the Neon
+//! module, so a browser party can join sessions with Node-hosted parties.
+//!
+//! Every export runs on the calling thread. The stages dominated by Paillier
+//! work (keygen stages 1 and 2, sign stages 2, 3, 5 and 6 and their batch
+//! versions, and every CGGMP21 aux info and presigning stage) block it for
+//! seconds. They also have an `_async` export that returns a Promise: it
+//! lets queued callbacks run first, then blocks just the same, so a page
+//! must load the module in a Web Worker and call either version there to
+//! stay responsive.
+
+use js_sys::Promise;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+
+use crate::error::Error;
+use crate::*;
+
+fn call<I, O, F>(json: String, f: F) -> Result<String, JsValue>
+where
+    I: DeserializeOwned,
+    O: Serialize,
+    F: Fn(&I) -> Result<O, Error>,
+{
+    let input: I = secret::from_json(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    let output = f(&input).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    Ok(serde_json::to_string(&output).unwrap())
+}
+
+/// Lets queued callbacks run before a long computation starts.
+async fn yield_now() -> Result<(), JsValue> {
+    JsFuture::from(Promise::resolve(&JsValue::NULL)).await?;
+    Ok(())
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_keygen_stage1(input: String) -> Result<String, JsValue> {
+    call(input, keygen_stage1)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_keygen_stage1_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, keygen_stage1)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_keygen_stage2(input: String) -> Result<String, JsValue> {
+    call(input, keygen_stage2)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_keygen_stage2_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, keygen_stage2)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_keygen_stage3(input: String) -> Result<String, JsValue> {
+    call(input, keygen_stage3)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_keygen_stage4(input: String) -> Result<String, JsValue> {
+    call(input, keygen_stage4)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_keygen_finalize(input: String) -> Result<String, JsValue> {
+    call(input, keygen_finalize)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_construct_private_key(input: String) -> Result<String, JsValue> {
+    call(input, construct_private_key)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage1(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage1)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage2(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage2)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_stage2_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, sign_stage2)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage3(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage3)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_stage3_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, sign_stage3)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage4(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage4)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage5(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage5)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_stage5_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, sign_stage5)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage6(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage6)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_stage6_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, sign_stage6)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage7(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage7)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage8(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage8)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_stage9(input: String) -> Result<String, JsValue> {
+    call(input, sign_stage9)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage1(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage1))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage2(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage2))
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_batch_stage2_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, |batch| sign_batch(batch, sign_stage2))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage3(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage3))
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_batch_stage3_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, |batch| sign_batch(batch, sign_stage3))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage4(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage4))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage5(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage5))
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_batch_stage5_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, |batch| sign_batch(batch, sign_stage5))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage6(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage6))
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_sign_batch_stage6_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, |batch| sign_batch(batch, sign_stage6))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage7(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage7))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage8(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage8))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_sign_batch_stage9(input: String) -> Result<String, JsValue> {
+    call(input, |batch| sign_batch(batch, sign_stage9))
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_aux_info_stage1(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::aux_info_stage1)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_aux_info_stage1_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::aux_info_stage1)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_aux_info_stage2(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::aux_info_stage2)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_aux_info_stage2_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::aux_info_stage2)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_aux_info_finalize(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::aux_info_finalize)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_aux_info_finalize_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::aux_info_finalize)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_presign_stage1(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::presign_stage1)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_presign_stage1_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::presign_stage1)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_presign_stage2(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::presign_stage2)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_presign_stage2_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::presign_stage2)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_presign_stage3(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::presign_stage3)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_presign_stage3_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::presign_stage3)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_presign_output(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::presign_output)
+}
+
+#[wasm_bindgen]
+pub async fn secp256k1_cggmp21_presign_output_async(input: String) -> Result<String, JsValue> {
+    yield_now().await?;
+    call(input, cggmp21::presign_output)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_sign(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::sign)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_cggmp21_sign_combine(input: String) -> Result<String, JsValue> {
+    call(input, cggmp21::sign_combine)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_tweak_key(input: String) -> Result<String, JsValue> {
+    call(input, tweak_key)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_export_xpub(input: String) -> Result<String, JsValue> {
+    call(input, export_xpub)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_parse_xpub(input: String) -> Result<String, JsValue> {
+    call(input, parse_xpub)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_derive_xpub(input: String) -> Result<String, JsValue> {
+    call(input, derive_xpub)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_verify_signature(input: String) -> Result<String, JsValue> {
+    call(input, verify_signature)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_recover_public_key(input: String) -> Result<String, JsValue> {
+    call(input, recover_public_key)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_encode_signature(input: String) -> Result<String, JsValue> {
+    call(input, encode_signature)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_eth_tx_hash(input: String) -> Result<String, JsValue> {
+    call(input, eth_tx_hash)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_eth_tx_encode_signed(input: String) -> Result<String, JsValue> {
+    call(input, eth_tx_encode_signed)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_eip712_hash(input: String) -> Result<String, JsValue> {
+    call(input, eip712_hash)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_eip712_recover(input: String) -> Result<String, JsValue> {
+    call(input, eip712_recover)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_psbt_sighashes(input: String) -> Result<String, JsValue> {
+    call(input, psbt_sighashes)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_psbt_add_signatures(input: String) -> Result<String, JsValue> {
+    call(input, psbt_add_signatures)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_address(input: String) -> Result<String, JsValue> {
+    call(input, address)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_inspect_key_share(input: String) -> Result<String, JsValue> {
+    call(input, inspect_key_share)
+}
+
+#[wasm_bindgen]
+pub fn secp256k1_audit_key_share(input: String) -> Result<String, JsValue> {
+    call(input, audit_key_share)
+}
@@ -0,0 +1,118 @@
+//! Addresses of known keys in every supported format.
+
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+
+use hello::address::{checksum_ethereum_address, AddressFormat};
+use hello::bip32::Network;
+
+/// The public key of private key 1, i.e. the generator.
+fn generator() -> Point<Secp256k1> {
+    Point::generator() * Scalar::from(1)
+}
+
+fn address(format: AddressFormat, public_key: &Point<Secp256k1>) -> String {
+    format.address(public_key).unwrap()
+}
+
+#[test]
+fn eip55_checksums() {
+    // The examples of EIP-55
+    for &expected in [
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+        "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+        "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+        "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+    ]
+    .iter()
+    {
+        let mut bytes = [0; 20];
+        bytes.copy_from_slice(&hex::decode(expected[2..].to_lowercase()).unwrap());
+        assert_eq!(checksum_ethereum_address(&bytes), expected);
+    }
+}
+
+#[test]
+fn ethereum() {
+    assert_eq!(
+        address(AddressFormat::Ethereum, &generator()),
+        "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+    );
+    let two = Point::generator() * Scalar::from(2);
+    assert_eq!(
+        address(AddressFormat::Ethereum, &two),
+        "0x2B5AD5c4795c026514f8317c7a215E218DcCD6cF"
+    );
+}
+
+#[test]
+fn bitcoin_p2pkh() {
+    let mainnet = AddressFormat::BitcoinP2pkh {
+        network: Network::Mainnet,
+    };
+    let testnet = AddressFormat::BitcoinP2pkh {
+        network: Network::Testnet,
+    };
+    assert_eq!(
+        address(mainnet, &generator()),
+        "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH"
+    );
+    assert_eq!(
+        address(testnet, &generator()),
+        "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r"
+    );
+}
+
+#[test]
+fn bitcoin_p2wpkh() {
+    // The P2WPKH example of BIP173
+    let mainnet = AddressFormat::BitcoinP2wpkh {
+        network: Network::Mainnet,
+    };
+    let testnet = AddressFormat::BitcoinP2wpkh {
+        network: Network::Testnet,
+    };
+    assert_eq!(
+        address(mainnet, &generator()),
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+    );
+    assert_eq!(
+        address(testnet, &generator()),
+        "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+    );
+}
+
+#[test]
+fn bitcoin_p2tr_bip86() {
+    // First receiving address of the BIP86 test vectors, m/86'/0'/0'/0/0
+    let internal_key = "cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115";
+    let format = || AddressFormat::BitcoinP2tr {
+        network: Network::Mainnet,
+    };
+    let expected = "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr";
+    // BIP86 lifts the internal key to even y, so both parities give the same output
+    for prefix in ["02", "03"].iter() {
+        let public_key =
+            Point::from_bytes(&hex::decode(format!("{}{}", prefix, internal_key)).unwrap())
+                .unwrap();
+        assert_eq!(address(format(), &public_key), expected);
+    }
+}
+
+#[test]
+fn tron() {
+    assert_eq!(
+        address(AddressFormat::Tron, &generator()),
+        "TMVQGm1qAQYVdetCeGRRkTWYYrLXuHK2HC"
+    );
+}
+
+#[test]
+fn cosmos() {
+    let format = AddressFormat::Cosmos {
+        hrp: "cosmos".to_string(),
+    };
+    assert_eq!(
+        address(format, &generator()),
+        "cosmos1w508d6qejxtdg4y5r3zarvary0c5xw7k6ah60c"
+    );
+}
@@ -0,0 +1,135 @@
+//! BIP32 public derivation against the test vectors of the BIP, and the
+//! chain code every key share carries.
+
+mod common;
+
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point};
+
+use hello::bip32::{self, ExtendedPublicKey};
+use hello::error::Error;
+use hello::*;
+
+use common::*;
+
+// Test vector 1, chain m/0H/1/2H/2/1000000000
+const VECTOR1_M: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+const VECTOR1_M_0H: &str = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+const VECTOR1_M_0H_1: &str = "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ";
+const VECTOR1_M_0H_1_2H: &str = "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5";
+const VECTOR1_M_0H_1_2H_2: &str = "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV";
+const VECTOR1_M_0H_1_2H_2_1000000000: &str = "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy";
+
+// Test vector 2, chain m/0
+const VECTOR2_M: &str = "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB";
+const VECTOR2_M_0: &str = "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH";
+
+fn xpub(s: &str) -> ExtendedPublicKey {
+    ExtendedPublicKey::from_base58(s).unwrap()
+}
+
+#[test]
+fn test_vectors_round_trip() {
+    for &s in [
+        VECTOR1_M,
+        VECTOR1_M_0H,
+        VECTOR1_M_0H_1,
+        VECTOR1_M_0H_1_2H,
+        VECTOR1_M_0H_1_2H_2,
+        VECTOR1_M_0H_1_2H_2_1000000000,
+        VECTOR2_M,
+        VECTOR2_M_0,
+    ]
+    .iter()
+    {
+        assert_eq!(xpub(s).to_base58(), s);
+    }
+}
+
+#[test]
+fn public_derivation_matches_test_vectors() {
+    for &(parent, index, child) in [
+        (VECTOR1_M_0H, 1, VECTOR1_M_0H_1),
+        (VECTOR1_M_0H_1_2H, 2, VECTOR1_M_0H_1_2H_2),
+        (VECTOR1_M_0H_1_2H_2, 1_000_000_000, VECTOR1_M_0H_1_2H_2_1000000000),
+        (VECTOR2_M, 0, VECTOR2_M_0),
+    ]
+    .iter()
+    {
+        let (derived, il) = xpub(parent).derive_child(index).unwrap();
+        assert_eq!(derived.to_base58(), child);
+        assert_eq!(derived.public_key, xpub(parent).public_key + Point::<Secp256k1>::generator() * il);
+    }
+}
+
+#[test]
+fn path_tweak_sums_every_step() {
+    let parent = xpub(VECTOR1_M_0H_1_2H);
+    let (child, tweak) = parent.derive_path("m/2/1000000000").unwrap();
+    assert_eq!(child.to_base58(), VECTOR1_M_0H_1_2H_2_1000000000);
+    assert_eq!(child.public_key, parent.public_key + Point::<Secp256k1>::generator() * tweak);
+}
+
+#[test]
+fn hardened_derivation_is_rejected() {
+    assert_eq!(
+        xpub(VECTOR1_M).derive_path("m/0'").unwrap_err(),
+        Error::HardenedDerivation(0x8000_0000)
+    );
+}
+
+#[test]
+fn paths_parse_strictly() {
+    assert_eq!(bip32::parse_path("m").unwrap(), Vec::<u32>::new());
+    assert_eq!(bip32::parse_path("").unwrap(), Vec::<u32>::new());
+    assert_eq!(bip32::parse_path("m/0/1'").unwrap(), vec![0, 0x8000_0001]);
+    assert_eq!(bip32::parse_path("0/1").unwrap(), vec![0, 1]);
+    for &path in ["m//0", "m/0/", "m/", "/0", "m/0//1", "m/x"].iter() {
+        assert_eq!(
+            bip32::parse_path(path).unwrap_err(),
+            Error::InvalidDerivationPath(path.to_string())
+        );
+    }
+}
+
+#[test]
+fn master_key_must_not_have_a_parent() {
+    let mut master = xpub(VECTOR1_M);
+    master.child_number = 1;
+    assert!(ExtendedPublicKey::from_base58(&master.to_base58()).is_err());
+
+    let mut master = xpub(VECTOR1_M);
+    master.parent_fingerprint = [1, 2, 3, 4];
+    assert!(ExtendedPublicKey::from_base58(&master.to_base58()).is_err());
+}
+
+#[test]
+fn depth_does_not_wrap() {
+    let mut deep = xpub(VECTOR1_M_0H_1);
+    deep.depth = u8::MAX;
+    assert_eq!(
+        deep.derive_child(0).unwrap_err(),
+        Error::InvalidExtendedKey("maximum depth reached")
+    );
+}
+
+#[test]
+fn key_shares_export_the_same_xpub() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, 1, 3), keygen_stage3);
+    let xpubs: Vec<_> = key_shares(&stage1, &stage2, &stage3, 1, 3)
+        .into_iter()
+        .map(|share| {
+            assert_eq!(share.chain_code, stage2[0].chain_code);
+            export_xpub(&Secp256k1ExportXpubInput {
+                local_key: share.local_key,
+                chain_code: share.chain_code,
+                network: Default::default(),
+            })
+            .unwrap()
+            .xpub
+        })
+        .collect();
+    assert!(xpubs.iter().all(|other| other == &xpubs[0]));
+    assert_eq!(xpub(&xpubs[0]).public_key, stage3[0].shared_key.y);
+}
@@ -0,0 +1,231 @@
+//! CGGMP21 on GG20 key shares: aux info, presigning and one-round signing
+//! for the quorum of parties 1 and 3, so quorum positions and keygen indices
+//! differ. A corrupted field must make the receiving stage blame its sender.
+
+mod common;
+
+use curv::arithmetic::{Converter, Samplable};
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+
+use hello::cggmp21::*;
+use hello::error::Error;
+use hello::message::MessageHashing;
+use hello::*;
+
+use common::*;
+
+const THRESHOLD: u16 = 1;
+const SHARE_COUNT: u16 = 3;
+const QUORUM: [u16; 2] = [1, 3];
+const MESSAGE: &[u8] = b"cggmp21 presigned message";
+
+fn blamed(e: Error) -> Vec<u16> {
+    match e {
+        Error::Blame { parties, .. } => parties,
+        e => panic!("expected a blamed party, got {}", e),
+    }
+}
+
+fn aux_info_stage2_inputs(
+    keys: &[LocalKey<Secp256k1>],
+    stage1: &[Secp256k1Cggmp21AuxInfoStage1Output],
+) -> Vec<Secp256k1Cggmp21AuxInfoStage2Input> {
+    keys.iter()
+        .zip(stage1)
+        .map(|(key, s)| Secp256k1Cggmp21AuxInfoStage2Input {
+            local_key: Secret::new(key.clone()),
+            paillier_key: s.paillier_key.clone(),
+            commitments: stage1.iter().map(|s| s.commitment.clone()).collect(),
+            decommitments: stage1.iter().map(|s| s.decommitment.clone()).collect(),
+        })
+        .collect()
+}
+
+/// Every party's key share after a full aux info run.
+fn aux_info(keys: &[LocalKey<Secp256k1>]) -> Vec<Secp256k1Cggmp21KeyShare> {
+    let stage1_inputs: Vec<_> = keys
+        .iter()
+        .map(|key| Secp256k1Cggmp21AuxInfoStage1Input {
+            local_key: Secret::new(key.clone()),
+            use_safe_prime: false,
+        })
+        .collect();
+    let stage1 = run(&stage1_inputs, aux_info_stage1);
+    let stage2_inputs = aux_info_stage2_inputs(keys, &stage1);
+    let stage2 = run(&stage2_inputs, aux_info_stage2);
+    let finalize_inputs: Vec<_> = stage2_inputs
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| Secp256k1Cggmp21AuxInfoFinalizeInput {
+            local_key: s.local_key,
+            paillier_key: s.paillier_key,
+            commitments: s.commitments,
+            decommitments: s.decommitments,
+            mod_proofs: stage2.iter().map(|s| s.mod_proof.clone()).collect(),
+            fac_proofs: received(&stage2, i, |s| &s.fac_proofs),
+        })
+        .collect();
+    run(&finalize_inputs, aux_info_finalize)
+}
+
+/// Honest outputs of the presigning rounds, in quorum order.
+struct Presigning {
+    parties: Vec<PartyIndex>,
+    key_shares: Vec<Secp256k1Cggmp21KeyShare>,
+    stage1: Vec<Secp256k1Cggmp21PresignStage1Output>,
+    stage2: Vec<Secp256k1Cggmp21PresignStage2Output>,
+    stage3: Vec<Secp256k1Cggmp21PresignStage3Output>,
+}
+
+impl Presigning {
+    fn stage2_inputs(&self) -> Vec<Secp256k1Cggmp21PresignStage2Input> {
+        self.key_shares
+            .iter()
+            .zip(&self.stage1)
+            .map(|(key_share, s)| Secp256k1Cggmp21PresignStage2Input {
+                parties: self.parties.clone(),
+                key_share: key_share.clone(),
+                nonces: s.nonces.clone(),
+                messages1: self.stage1.iter().map(|s| s.message.clone()).collect(),
+            })
+            .collect()
+    }
+
+    fn stage3_inputs(&self) -> Vec<Secp256k1Cggmp21PresignStage3Input> {
+        self.stage2_inputs()
+            .into_iter()
+            .map(|s| Secp256k1Cggmp21PresignStage3Input {
+                parties: s.parties,
+                key_share: s.key_share,
+                nonces: s.nonces,
+                messages1: s.messages1,
+                messages2: self.stage2.iter().map(|s| s.message.clone()).collect(),
+            })
+            .collect()
+    }
+
+    fn output_inputs(&self) -> Vec<Secp256k1Cggmp21PresignOutputInput> {
+        self.stage3_inputs()
+            .into_iter()
+            .zip(&self.stage3)
+            .map(|(s, own)| Secp256k1Cggmp21PresignOutputInput {
+                parties: s.parties,
+                key_share: s.key_share,
+                nonces: s.nonces,
+                chi: own.chi.clone(),
+                messages1: s.messages1,
+                messages2: s.messages2,
+                messages3: self.stage3.iter().map(|s| s.message.clone()).collect(),
+            })
+            .collect()
+    }
+}
+
+fn presigning() -> (Vec<LocalKey<Secp256k1>>, Presigning) {
+    let keys = keygen(THRESHOLD, SHARE_COUNT, false);
+    let key_shares = aux_info(&keys);
+    let parties: Vec<_> = QUORUM.iter().map(|&p| PartyIndex(p)).collect();
+    let key_shares: Vec<_> = parties.iter().map(|p| key_shares[p.offset()].clone()).collect();
+    let stage1_inputs: Vec<_> = key_shares
+        .iter()
+        .map(|key_share| Secp256k1Cggmp21PresignStage1Input {
+            parties: parties.clone(),
+            key_share: key_share.clone(),
+        })
+        .collect();
+    let mut presigning = Presigning {
+        parties,
+        key_shares,
+        stage1: run(&stage1_inputs, presign_stage1),
+        stage2: Vec::new(),
+        stage3: Vec::new(),
+    };
+    presigning.stage2 = run(&presigning.stage2_inputs(), presign_stage2);
+    presigning.stage3 = run(&presigning.stage3_inputs(), presign_stage3);
+    (keys, presigning)
+}
+
+fn sign_inputs(presignatures: &[Secp256k1Cggmp21Presignature]) -> Vec<Secp256k1Cggmp21SignInput> {
+    presignatures
+        .iter()
+        .map(|presignature| Secp256k1Cggmp21SignInput {
+            presignature: presignature.clone(),
+            message: MESSAGE.to_vec(),
+            hashing: MessageHashing::Sha256,
+        })
+        .collect()
+}
+
+fn combine_input(
+    presignature: &Secp256k1Cggmp21Presignature,
+    partial_signatures: Vec<Scalar<Secp256k1>>,
+) -> Secp256k1Cggmp21SignCombineInput {
+    Secp256k1Cggmp21SignCombineInput {
+        presignature: presignature.clone(),
+        message: MESSAGE.to_vec(),
+        hashing: MessageHashing::Sha256,
+        partial_signatures,
+    }
+}
+
+#[test]
+fn presignature_signs_for_the_key() {
+    let (keys, presigning) = presigning();
+    let presignatures = run(&presigning.output_inputs(), presign_output);
+    assert_eq!(presignatures[0].r, presignatures[1].r);
+
+    let signed = run(&sign_inputs(&presignatures), sign);
+    let partial_signatures = signed.iter().map(|s| s.partial_signature.clone()).collect();
+    let combined = sign_combine(&combine_input(&presignatures[1], partial_signatures)).unwrap();
+    let message_hash = BigInt::from_bytes(&signed[0].message_hash);
+    assert!(signature::verify(&combined.signature, &keys[0].y_sum_s, &message_hash));
+}
+
+#[test]
+fn aux_info_stage2_blames_bad_commitment() {
+    let keys = keygen(THRESHOLD, SHARE_COUNT, false);
+    let stage1_inputs: Vec<_> = keys
+        .iter()
+        .map(|key| Secp256k1Cggmp21AuxInfoStage1Input {
+            local_key: Secret::new(key.clone()),
+            use_safe_prime: false,
+        })
+        .collect();
+    let mut stage1 = run(&stage1_inputs, aux_info_stage1);
+    stage1[1].decommitment.blind = BigInt::sample(256);
+
+    let inputs = aux_info_stage2_inputs(&keys, &stage1);
+    assert_eq!(blamed(aux_info_stage2(&inputs[0]).unwrap_err()), vec![2]);
+}
+
+#[test]
+fn presign_stage2_blames_bad_k() {
+    let (_, mut presigning) = presigning();
+    presigning.stage1[1].message.k = presigning.stage1[1].message.g.clone();
+
+    let inputs = presigning.stage2_inputs();
+    assert_eq!(blamed(presign_stage2(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn presign_output_blames_wrong_delta() {
+    let (_, mut presigning) = presigning();
+    presigning.stage3[1].message.delta = Scalar::random();
+
+    let inputs = presigning.output_inputs();
+    assert_eq!(blamed(presign_output(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_combine_blames_bad_partial_signature() {
+    let (_, presigning) = presigning();
+    let presignatures = run(&presigning.output_inputs(), presign_output);
+    let signed = run(&sign_inputs(&presignatures), sign);
+    let mut partial_signatures: Vec<_> = signed.iter().map(|s| s.partial_signature.clone()).collect();
+    partial_signatures[1] = Scalar::random();
+
+    let input = combine_input(&presignatures[0], partial_signatures);
+    assert_eq!(blamed(sign_combine(&input).unwrap_err()), vec![3]);
+}
@@ -0,0 +1,128 @@
+//! Runs a keygen ceremony through the `mpc` binary, one process per party
+//! and round, moving the messages between them as files. Run with
+//! `--features cli`.
+
+#![cfg(feature = "cli")]
+
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use hello::*;
+
+use common::*;
+
+struct Ceremony {
+    dir: PathBuf,
+}
+
+impl Ceremony {
+    fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("mpc-cli-{}-{}", name, std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        Ceremony { dir }
+    }
+
+    /// Runs `mpc <args> -i <name>.in.json -o <name>.out.json` for every
+    /// party and returns the output file paths.
+    fn round<I: Serialize>(&self, name: &str, args: &[&str], inputs: &[I]) -> Vec<PathBuf> {
+        inputs
+            .iter()
+            .enumerate()
+            .map(|(i, input)| {
+                let input_path = self.dir.join(format!("{}-{}.in.json", name, i + 1));
+                let output_path = self.dir.join(format!("{}-{}.out.json", name, i + 1));
+                fs::write(&input_path, serde_json::to_string(input).unwrap()).unwrap();
+                mpc(args, &input_path, &output_path);
+                output_path
+            })
+            .collect()
+    }
+}
+
+impl Drop for Ceremony {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+fn mpc(args: &[&str], input: &Path, output: &Path) {
+    let result = Command::new(env!("CARGO_BIN_EXE_mpc"))
+        .args(args)
+        .arg("--input")
+        .arg(input)
+        .arg("--output")
+        .arg(output)
+        .output()
+        .unwrap();
+    assert!(
+        result.status.success(),
+        "mpc {:?}: {}",
+        args,
+        String::from_utf8_lossy(&result.stderr)
+    );
+}
+
+fn read<T: DeserializeOwned>(paths: &[PathBuf]) -> Vec<T> {
+    paths
+        .iter()
+        .map(|path| secret::from_json(fs::read_to_string(path).unwrap()).unwrap())
+        .collect()
+}
+
+#[test]
+fn keygen_ceremony() {
+    let (threshold, share_count) = (1, 3);
+    let ceremony = Ceremony::new("keygen");
+
+    let stage1 = read(&ceremony.round("round1", &["keygen", "1"], &keygen_stage1_inputs(share_count, false)));
+    let inputs = keygen_stage2_inputs(&stage1, threshold, share_count);
+    let stage2 = read(&ceremony.round("round2", &["keygen", "2"], &inputs));
+    let inputs = keygen_stage3_inputs(&stage1, &stage2, threshold, share_count);
+    let stage3 = read(&ceremony.round("round3", &["keygen", "3"], &inputs));
+    let inputs = keygen_stage4_inputs(&stage1, &stage2, &stage3, threshold, share_count);
+    ceremony.round("round4", &["keygen", "4"], &inputs);
+
+    let inputs = keygen_finalize_inputs(&stage1, &stage2, &stage3, threshold, share_count);
+    let share_paths = ceremony.round("finalize", &["finalize"], &inputs);
+    let shares: Vec<Secp256k1KeyShare> = read(&share_paths);
+    assert!(shares.iter().all(|share| share.chain_code == stage2[0].chain_code));
+
+    // Inspect reads the saved key share files as they are
+    let inspected: Vec<Secp256k1InspectKeyShareOutput> = read(&ceremony.round("inspect", &["inspect"], &shares));
+    for (i, facts) in inspected.iter().enumerate() {
+        assert_eq!(facts.index, PartyIndex(i as u16 + 1));
+        assert_eq!((facts.threshold, facts.share_count), (threshold, share_count));
+        assert_eq!(facts.public_key, inspected[0].public_key);
+        assert!(facts.consistent);
+    }
+    let audits: Vec<Secp256k1AuditKeyShareOutput> = read(&ceremony.round("audit", &["audit"], &shares));
+    assert!(audits.iter().all(|audit| audit.ok));
+}
+
+#[cfg(unix)]
+#[test]
+fn output_files_are_private() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let ceremony = Ceremony::new("mode");
+    let inputs = keygen_stage1_inputs(2, false);
+    let output_path = ceremony.dir.join("existing.json");
+    // An existing file is tightened as well
+    fs::write(&output_path, "").unwrap();
+    fs::set_permissions(&output_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+    let input_path = ceremony.dir.join("input.json");
+    fs::write(&input_path, serde_json::to_string(&inputs[0]).unwrap()).unwrap();
+    mpc(&["keygen", "1"], &input_path, &output_path);
+    let paths = ceremony.round("round1", &["keygen", "1"], &inputs);
+    for path in paths.iter().chain(Some(&output_path)) {
+        let mode = fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "{}", path.display());
+    }
+}
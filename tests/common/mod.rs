@@ -0,0 +1,361 @@
+//! Runs every party of a keygen or signing session in-process, passing the
+//! stage outputs around the same way the JS host does.
+
+#![allow(dead_code)]
+
+use curv::elliptic::curves::secp256_k1::Secp256k1;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+
+use hello::message::MessageHashing;
+use hello::*;
+
+pub fn keygen_stage1_inputs(share_count: u16, use_safe_prime: bool) -> Vec<Secp256k1KeyGenStage1Input> {
+    (1..=share_count)
+        .map(|index| Secp256k1KeyGenStage1Input {
+            index: PartyIndex(index),
+            use_safe_prime,
+        })
+        .collect()
+}
+
+pub fn keygen_stage2_inputs(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<Secp256k1KeyGenStage2Input> {
+    stage1
+        .iter()
+        .map(|s| Secp256k1KeyGenStage2Input {
+            key: s.key.clone(),
+            bc1s: stage1.iter().map(|s| s.bc1.clone()).collect(),
+            decom1s: stage1.iter().map(|s| s.decom1.clone()).collect(),
+            chain_code_coms: stage1.iter().map(|s| s.chain_code_com.clone()).collect(),
+            chain_code_decoms: stage1.iter().map(|s| s.chain_code_decom.clone()).collect(),
+            threshold,
+            share_count,
+        })
+        .collect()
+}
+
+pub fn keygen_stage3_inputs(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    stage2: &[Secp256k1KeyGenStage2Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<Secp256k1KeyGenStage3Input> {
+    stage1
+        .iter()
+        .enumerate()
+        .map(|(i, s)| Secp256k1KeyGenStage3Input {
+            key: s.key.clone(),
+            ys: stage1.iter().map(|s| s.decom1.y_i.clone()).collect(),
+            vss_schemes: stage2.iter().map(|s| s.vss.0.clone()).collect(),
+            party_shares: Secret::new(stage2.iter().map(|s| s.vss.1[i].clone()).collect()),
+            threshold,
+            share_count,
+        })
+        .collect()
+}
+
+pub fn keygen_stage4_inputs(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    stage2: &[Secp256k1KeyGenStage2Output],
+    stage3: &[Secp256k1KeyGenStage3Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<Secp256k1KeyGenStage4Input> {
+    stage1
+        .iter()
+        .map(|_| Secp256k1KeyGenStage4Input {
+            ys: stage1.iter().map(|s| s.decom1.y_i.clone()).collect(),
+            vss_schemes: stage2.iter().map(|s| s.vss.0.clone()).collect(),
+            dlog_proofs: stage3.iter().map(|s| s.dlog_proof.clone()).collect(),
+            threshold,
+            share_count,
+        })
+        .collect()
+}
+
+pub fn keygen_finalize_inputs(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    stage2: &[Secp256k1KeyGenStage2Output],
+    stage3: &[Secp256k1KeyGenStage3Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<Secp256k1KeyGenFinalizeInput> {
+    (0..stage1.len())
+        .map(|i| Secp256k1KeyGenFinalizeInput {
+            key: stage1[i].key.clone(),
+            bc1s: stage1.iter().map(|s| s.bc1.clone()).collect(),
+            vss_scheme: stage2[i].vss.0.clone(),
+            shared_key: stage3[i].shared_key.clone(),
+            dlog_proofs: stage3.iter().map(|s| s.dlog_proof.clone()).collect(),
+            chain_code: stage2[i].chain_code,
+            threshold,
+            share_count,
+        })
+        .collect()
+}
+
+/// The key share each party saves at the end of keygen.
+pub fn key_shares(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    stage2: &[Secp256k1KeyGenStage2Output],
+    stage3: &[Secp256k1KeyGenStage3Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<Secp256k1KeyShare> {
+    run(&keygen_finalize_inputs(stage1, stage2, stage3, threshold, share_count), keygen_finalize)
+}
+
+pub fn local_keys(
+    stage1: &[Secp256k1KeyGenStage1Output],
+    stage2: &[Secp256k1KeyGenStage2Output],
+    stage3: &[Secp256k1KeyGenStage3Output],
+    threshold: u16,
+    share_count: u16,
+) -> Vec<LocalKey<Secp256k1>> {
+    key_shares(stage1, stage2, stage3, threshold, share_count)
+        .iter()
+        .map(|share| (*share.local_key).clone())
+        .collect()
+}
+
+pub fn run<I, O>(inputs: &[I], stage: fn(&I) -> Result<O, hello::error::Error>) -> Vec<O> {
+    inputs
+        .iter()
+        .enumerate()
+        .map(|(i, input)| stage(input).unwrap_or_else(|e| panic!("party {}: {}", i + 1, e)))
+        .collect()
+}
+
+pub fn keygen(threshold: u16, share_count: u16, use_safe_prime: bool) -> Vec<LocalKey<Secp256k1>> {
+    let stage1 = run(&keygen_stage1_inputs(share_count, use_safe_prime), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, threshold, share_count), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, threshold, share_count), keygen_stage3);
+    run(&keygen_stage4_inputs(&stage1, &stage2, &stage3, threshold, share_count), keygen_stage4);
+    local_keys(&stage1, &stage2, &stage3, threshold, share_count)
+}
+
+/// The signing quorum: `parties` holds the 1-based keygen indices and
+/// `local_keys` the matching keys, in the same order.
+#[derive(Clone)]
+pub struct Quorum {
+    pub parties: Vec<PartyIndex>,
+    pub local_keys: Vec<LocalKey<Secp256k1>>,
+}
+
+impl Quorum {
+    pub fn new(keys: &[LocalKey<Secp256k1>], parties: &[u16]) -> Self {
+        Quorum {
+            parties: parties.iter().map(|&p| PartyIndex(p)).collect(),
+            local_keys: parties.iter().map(|&p| keys[usize::from(p) - 1].clone()).collect(),
+        }
+    }
+
+    pub fn stage1_inputs(&self) -> Vec<Secp256k1SignStage1Input> {
+        self.local_keys
+            .iter()
+            .map(|local_key| Secp256k1SignStage1Input {
+                parties: self.parties.clone(),
+                local_key: Secret::new(local_key.clone()),
+            })
+            .collect()
+    }
+
+    pub fn stage2_inputs(&self, stage1: &[Secp256k1SignStage1Output]) -> Vec<Secp256k1SignStage2Input> {
+        self.local_keys
+            .iter()
+            .enumerate()
+            .map(|(i, local_key)| Secp256k1SignStage2Input {
+                parties: self.parties.clone(),
+                local_key: Secret::new(local_key.clone()),
+                m_as: stage1.iter().map(|s| s.m_a.0.clone()).collect(),
+                sign_key: stage1[i].sign_key.clone(),
+            })
+            .collect()
+    }
+
+    pub fn stage3_inputs(
+        &self,
+        stage1: &[Secp256k1SignStage1Output],
+        stage2: &[Secp256k1SignStage2Output],
+    ) -> Vec<Secp256k1SignStage3Input> {
+        self.local_keys
+            .iter()
+            .enumerate()
+            .map(|(i, local_key)| Secp256k1SignStage3Input {
+                parties: self.parties.clone(),
+                local_key: Secret::new(local_key.clone()),
+                sign_key: stage1[i].sign_key.clone(),
+                nis: stage2[i].nis.clone(),
+                betas: stage2[i].betas.clone(),
+                m_b_gammas: received(stage2, i, |s| &s.m_b_gammas),
+                m_b_ws: received(stage2, i, |s| &s.m_b_ws),
+            })
+            .collect()
+    }
+
+    pub fn stage4_inputs(&self, stage3: &[Secp256k1SignStage3Output]) -> Vec<Secp256k1SignStage4Input> {
+        self.parties
+            .iter()
+            .map(|_| Secp256k1SignStage4Input {
+                parties: self.parties.clone(),
+                deltas: stage3.iter().map(|s| s.delta_i.clone()).collect(),
+                ts: stage3.iter().map(|s| s.t_i.clone()).collect(),
+                t_proofs: stage3.iter().map(|s| s.t_i_proof.clone()).collect(),
+            })
+            .collect()
+    }
+
+    pub fn stage5_inputs(
+        &self,
+        stage1: &[Secp256k1SignStage1Output],
+        stage2: &[Secp256k1SignStage2Output],
+        stage4: &[Secp256k1SignStage4Output],
+    ) -> Vec<Secp256k1SignStage5Input> {
+        self.local_keys
+            .iter()
+            .enumerate()
+            .map(|(i, local_key)| Secp256k1SignStage5Input {
+                parties: self.parties.clone(),
+                local_key: Secret::new(local_key.clone()),
+                sign_key: stage1[i].sign_key.clone(),
+                m_a: stage1[i].m_a.clone(),
+                m_b_gammas: received(stage2, i, |s| &s.m_b_gammas),
+                bc1s: stage1.iter().map(|s| s.bc1.clone()).collect(),
+                delta_inv: stage4[i].delta_inv.clone(),
+                decom1s: stage1.iter().map(|s| s.decom1.clone()).collect(),
+            })
+            .collect()
+    }
+
+    pub fn stage6_inputs(
+        &self,
+        stage1: &[Secp256k1SignStage1Output],
+        stage3: &[Secp256k1SignStage3Output],
+        stage5: &[Secp256k1SignStage5Output],
+    ) -> Vec<Secp256k1SignStage6Input> {
+        self.local_keys
+            .iter()
+            .enumerate()
+            .map(|(i, local_key)| Secp256k1SignStage6Input {
+                parties: self.parties.clone(),
+                local_key: Secret::new(local_key.clone()),
+                m_as: stage1.iter().map(|s| s.m_a.0.clone()).collect(),
+                t_i: stage3[i].t_i.clone(),
+                l_i: stage3[i].l_i.clone(),
+                sigma_i: stage3[i].sigma_i.clone(),
+                r: stage5[i].r.clone(),
+                r_dashes: stage5.iter().map(|s| s.r_dash.clone()).collect(),
+                phase5_proofss: stage5.iter().map(|s| s.phase5_proofs.clone()).collect(),
+            })
+            .collect()
+    }
+
+    pub fn completed_offline_stages(
+        &self,
+        stage1: &[Secp256k1SignStage1Output],
+        stage3: &[Secp256k1SignStage3Output],
+        stage5: &[Secp256k1SignStage5Output],
+    ) -> Vec<CompletedOfflineStage> {
+        self.local_keys
+            .iter()
+            .enumerate()
+            .map(|(i, local_key)| CompletedOfflineStage {
+                local_key: Secret::new(local_key.clone()),
+                sign_key: stage1[i].sign_key.clone(),
+                ts: stage3.iter().map(|s| s.t_i.clone()).collect(),
+                r: stage5[i].r.clone(),
+                sigma_i: stage3[i].sigma_i.clone(),
+            })
+            .collect()
+    }
+
+    pub fn stage7_inputs(
+        &self,
+        completed: &[CompletedOfflineStage],
+        stage6: &[Secp256k1SignStage6Output],
+    ) -> Vec<Secp256k1SignStage7Input> {
+        completed
+            .iter()
+            .map(|c| Secp256k1SignStage7Input {
+                ss: stage6.iter().map(|s| s.s_i.clone()).collect(),
+                homo_elgamal_proofs: stage6.iter().map(|s| s.homo_elgamal_proof.clone()).collect(),
+                parties: self.parties.clone(),
+                completed_offline_stage: c.clone(),
+            })
+            .collect()
+    }
+
+    pub fn stage8_inputs(
+        &self,
+        completed: &[CompletedOfflineStage],
+        message: &[u8],
+        hashing: MessageHashing,
+    ) -> Vec<Secp256k1SignStage8Input> {
+        completed
+            .iter()
+            .map(|c| Secp256k1SignStage8Input {
+                completed_offline_stage: c.clone(),
+                message: message.to_vec(),
+                hashing,
+            })
+            .collect()
+    }
+
+    pub fn stage9_inputs(&self, stage8: &[Secp256k1SignStage8Output]) -> Vec<Secp256k1SignStage9Input> {
+        (0..stage8.len())
+            .map(|i| Secp256k1SignStage9Input {
+                local_signature: stage8[i].local_signature.clone(),
+                partial_signatures: stage8
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, _)| j != i)
+                    .map(|(_, s)| s.partial_signature.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Runs the offline stages 1 to 7 and returns what every party keeps for
+    /// the online stages.
+    pub fn offline(&self) -> Vec<CompletedOfflineStage> {
+        let stage1 = run(&self.stage1_inputs(), sign_stage1);
+        let stage2 = run(&self.stage2_inputs(&stage1), sign_stage2);
+        let stage3 = run(&self.stage3_inputs(&stage1, &stage2), sign_stage3);
+        let stage4 = run(&self.stage4_inputs(&stage3), sign_stage4);
+        let stage5 = run(&self.stage5_inputs(&stage1, &stage2, &stage4), sign_stage5);
+        let stage6 = run(&self.stage6_inputs(&stage1, &stage3, &stage5), sign_stage6);
+        let completed = self.completed_offline_stages(&stage1, &stage3, &stage5);
+        run(&self.stage7_inputs(&completed, &stage6), sign_stage7);
+        completed
+    }
+
+    /// Runs the online stages 8 and 9 on a completed offline stage.
+    pub fn online(
+        &self,
+        completed: &[CompletedOfflineStage],
+        message: &[u8],
+        hashing: MessageHashing,
+    ) -> Vec<Secp256k1SignStage9Output> {
+        let stage8 = run(&self.stage8_inputs(completed, message, hashing), sign_stage8);
+        run(&self.stage9_inputs(&stage8), sign_stage9)
+    }
+
+    pub fn sign(&self, message: &[u8], hashing: MessageHashing) -> Vec<Secp256k1SignStage9Output> {
+        let completed = self.offline();
+        self.online(&completed, message, hashing)
+    }
+}
+
+/// What party `i` received from every other party, in quorum order, out of
+/// the per-counterparty vectors each party sent.
+pub fn received<S, T: Clone>(outputs: &[S], i: usize, sent: impl Fn(&S) -> &Vec<T>) -> Vec<T> {
+    outputs
+        .iter()
+        .enumerate()
+        .filter(|&(j, _)| j != i)
+        .map(|(j, s)| sent(s)[if i < j { i } else { i - 1 }].clone())
+        .collect()
+}
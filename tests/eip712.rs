@@ -0,0 +1,125 @@
+//! EIP-712 hashing against the example of the EIP, and the checks on
+//! malformed types and out-of-range integers.
+
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+use serde_json::{json, Value};
+
+use hello::eip712::TypedData;
+use hello::error::Error;
+use hello::*;
+
+/// The `Mail` example of EIP-712.
+fn mail() -> TypedData {
+    serde_json::from_value(json!({
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }))
+    .unwrap()
+}
+
+/// Typed data with a single field of type `kind` holding `value`.
+fn single_field(kind: &str, value: Value) -> TypedData {
+    serde_json::from_value(json!({
+        "types": {
+            "EIP712Domain": [],
+            "Test": [{ "name": "value", "type": kind }]
+        },
+        "primaryType": "Test",
+        "domain": {},
+        "message": { "value": value }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn mail_example_hashes() {
+    let typed_data = mail();
+    assert_eq!(
+        typed_data.encode_type("Mail").unwrap(),
+        "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+    );
+    assert_eq!(
+        hex::encode(typed_data.domain_separator().unwrap()),
+        "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+    );
+    assert_eq!(
+        hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap()),
+        "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+    );
+    assert_eq!(
+        hex::encode(typed_data.signing_hash().unwrap()),
+        "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+    );
+}
+
+#[test]
+fn mail_example_recovers_the_sender() {
+    let scalar = |hex: &str| Scalar::<Secp256k1>::from_bigint(&BigInt::from_hex(hex).unwrap());
+    // v = 28 in the EIP
+    let signature = SignatureRecid {
+        r: scalar("4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d"),
+        s: scalar("07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562"),
+        recid: 1,
+    };
+    let output = eip712_recover(&Secp256k1Eip712RecoverInput {
+        typed_data: mail(),
+        signature,
+    })
+    .unwrap();
+    assert_eq!(output.address.to_lowercase(), "0xcd2a3d9f938e13cd947ec05abc7fe734df8dd826");
+}
+
+#[test]
+fn unterminated_array_type_is_rejected() {
+    for &kind in ["uint256[", "uint256[2", "Person[]x"].iter() {
+        let e = single_field(kind, json!([1, 2])).signing_hash().unwrap_err();
+        assert!(matches!(e, Error::InvalidTypedData(_)), "{}: {}", kind, e);
+    }
+}
+
+#[test]
+fn integers_must_fit_their_type() {
+    let fits = |kind: &str, value: Value| single_field(kind, value).signing_hash().is_ok();
+    assert!(fits("uint8", json!(255)));
+    assert!(!fits("uint8", json!(256)));
+    assert!(fits("uint16", json!("0xffff")));
+    assert!(!fits("uint16", json!("0x10000")));
+    assert!(!fits("uint8", json!(-1)));
+    assert!(fits("int8", json!(127)));
+    assert!(!fits("int8", json!(128)));
+    assert!(fits("int8", json!(-128)));
+    assert!(!fits("int8", json!(-129)));
+    assert!(fits("int256", json!(format!("-0x8{:0>63}", ""))));
+    assert!(!fits("int256", json!(format!("0x8{:0>63}", ""))));
+    assert!(fits("uint256", json!(format!("0x{}", "f".repeat(64)))));
+}
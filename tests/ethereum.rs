@@ -0,0 +1,151 @@
+//! EVM transaction hashing and signed encodings.
+
+mod common;
+
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+
+use hello::error::Error;
+use hello::ethereum::{AccessListItem, Eip1559Transaction, LegacyTransaction, Transaction};
+use hello::message::MessageHashing;
+use hello::{address, signature};
+use hello::*;
+
+use common::*;
+
+const TO: &str = "0x3535353535353535353535353535353535353535";
+
+fn signature(r: &str, s: &str, recid: u8) -> SignatureRecid {
+    SignatureRecid {
+        r: Scalar::<Secp256k1>::from_bigint(&BigInt::from_hex(r).unwrap()),
+        s: Scalar::<Secp256k1>::from_bigint(&BigInt::from_hex(s).unwrap()),
+        recid,
+    }
+}
+
+/// The example transaction of EIP-155.
+fn eip155_transaction(chain_id: u64) -> Transaction {
+    Transaction::Legacy(LegacyTransaction {
+        chain_id: Some(chain_id),
+        nonce: "9".to_string(),
+        gas_price: "20000000000".to_string(),
+        gas_limit: "21000".to_string(),
+        to: Some(TO.to_string()),
+        value: "1000000000000000000".to_string(),
+        data: String::new(),
+    })
+}
+
+#[test]
+fn eip155_example() {
+    let tx = eip155_transaction(1);
+    assert_eq!(
+        hex::encode(tx.signing_payload().unwrap()),
+        "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080"
+    );
+    assert_eq!(
+        hex::encode(tx.signing_hash().unwrap()),
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+    );
+    let signature = signature(
+        "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276",
+        "67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+        0,
+    );
+    assert_eq!(
+        hex::encode(tx.encode_signed(&signature).unwrap()),
+        "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc\
+         2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb196\
+         6a3b6d83"
+    );
+    // Signed with the private key 0x4646...46 of the example
+    let signer = signature::recover(&signature, &BigInt::from_bytes(&tx.signing_hash().unwrap())).unwrap();
+    assert_eq!(
+        address::ethereum_address(&signer).to_lowercase(),
+        "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+    );
+}
+
+#[test]
+fn eip1559_signed_encoding() {
+    let tx = Transaction::Eip1559(Eip1559Transaction {
+        chain_id: 1,
+        nonce: "2".to_string(),
+        max_priority_fee_per_gas: "0x77359400".to_string(),
+        max_fee_per_gas: "100000000000".to_string(),
+        gas_limit: "21000".to_string(),
+        to: Some(TO.to_string()),
+        value: "0xde0b6b3a7640000".to_string(),
+        data: "0x".to_string(),
+        access_list: vec![AccessListItem {
+            address: TO.to_string(),
+            storage_keys: vec![format!("0x{:0>64}", "1")],
+        }],
+    });
+    assert_eq!(
+        hex::encode(tx.signing_hash().unwrap()),
+        "868cc1b44b6fcf30e56feb7164a029eea4dbeb62d128e01785770caef5e9ac98"
+    );
+    let signature = signature(
+        "1c551574f7fe9ef569c03b0e42e86a9e03dfc3fa7e1bae73be492269c9c30d87",
+        "3b08cf4ddda32212446f9eafd77af19c3cc9d1d8868d10b5d124b9c9fd23ef40",
+        1,
+    );
+    assert_eq!(
+        hex::encode(tx.encode_signed(&signature).unwrap()),
+        "02f8ac0102847735940085174876e800825208943535353535353535353535353535353535353535880de0b6b3a764000080f838\
+         f7943535353535353535353535353535353535353535e1a000000000000000000000000000000000000000000000000000000000\
+         0000000101a01c551574f7fe9ef569c03b0e42e86a9e03dfc3fa7e1bae73be492269c9c30d87a03b08cf4ddda32212446f9eafd7\
+         7af19c3cc9d1d8868d10b5d124b9c9fd23ef40"
+    );
+    let signer = signature::recover(&signature, &BigInt::from_bytes(&tx.signing_hash().unwrap())).unwrap();
+    assert_eq!(
+        address::ethereum_address(&signer).to_lowercase(),
+        "0x9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+    );
+}
+
+#[test]
+fn huge_chain_id_is_rejected() {
+    let signature = signature("01", "01", 0);
+    assert!(matches!(
+        eip155_transaction(u64::MAX / 2).encode_signed(&signature),
+        Err(Error::InvalidTransaction(_))
+    ));
+    assert!(eip155_transaction((u64::MAX - 36) / 2).encode_signed(&signature).is_ok());
+}
+
+#[test]
+fn sender_comes_from_the_key_share() {
+    let keys = keygen(1, 3, false);
+    let tx = eip155_transaction(1);
+    let message_hash = tx.signing_hash().unwrap();
+    let signature = Quorum::new(&keys, &[1, 2])
+        .sign(&message_hash, MessageHashing::Prehashed)
+        .remove(0)
+        .signature;
+
+    let output = eth_tx_encode_signed(&Secp256k1EthTxEncodeSignedInput {
+        transaction: tx.clone(),
+        signature: signature.clone(),
+        local_key: Secret::new(keys[2].clone()),
+        chain_code: None,
+        path: None,
+    })
+    .unwrap();
+    assert_eq!(output.from, address::ethereum_address(&keys[0].y_sum_s));
+
+    // A signature of another key is refused
+    let other = keygen(1, 3, false);
+    let e = eth_tx_encode_signed(&Secp256k1EthTxEncodeSignedInput {
+        transaction: tx,
+        signature,
+        local_key: Secret::new(other[0].clone()),
+        chain_code: None,
+        path: None,
+    })
+    .unwrap_err();
+    assert_eq!(e, Error::InvalidSignature);
+}
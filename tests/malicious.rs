@@ -0,0 +1,182 @@
+//! A single party corrupts one field of its stage output; the receiving
+//! stage must abort and blame that party.
+
+mod common;
+
+use curv::arithmetic::Samplable;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+
+use hello::error::Error;
+use hello::*;
+
+use common::*;
+
+const THRESHOLD: u16 = 1;
+const SHARE_COUNT: u16 = 3;
+
+fn blamed(e: Error) -> Vec<u16> {
+    match e {
+        Error::Blame { parties, .. } => parties,
+        e => panic!("expected a blamed party, got {}", e),
+    }
+}
+
+#[test]
+fn keygen_stage2_blames_bad_commitment() {
+    let mut stage1 = run(&keygen_stage1_inputs(SHARE_COUNT, false), keygen_stage1);
+    stage1[1].decom1.blind_factor = BigInt::sample(256);
+
+    let inputs = keygen_stage2_inputs(&stage1, THRESHOLD, SHARE_COUNT);
+    assert_eq!(blamed(keygen_stage2(&inputs[0]).unwrap_err()), vec![2]);
+}
+
+#[test]
+fn keygen_stage2_blames_bad_chain_code_commitment() {
+    let mut stage1 = run(&keygen_stage1_inputs(SHARE_COUNT, false), keygen_stage1);
+    stage1[2].chain_code_decom.blind_factor = BigInt::sample(256);
+
+    let inputs = keygen_stage2_inputs(&stage1, THRESHOLD, SHARE_COUNT);
+    assert_eq!(blamed(keygen_stage2(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn keygen_stage3_blames_bad_share() {
+    let stage1 = run(&keygen_stage1_inputs(SHARE_COUNT, false), keygen_stage1);
+    let mut stage2 = run(&keygen_stage2_inputs(&stage1, THRESHOLD, SHARE_COUNT), keygen_stage2);
+    stage2[1].vss.1[0] = Scalar::<Secp256k1>::random();
+
+    let inputs = keygen_stage3_inputs(&stage1, &stage2, THRESHOLD, SHARE_COUNT);
+    assert_eq!(blamed(keygen_stage3(&inputs[0]).unwrap_err()), vec![2]);
+    // Party 3 got an honest share from party 2
+    keygen_stage3(&inputs[2]).unwrap();
+}
+
+#[test]
+fn keygen_stage4_blames_bad_dlog_proof() {
+    let stage1 = run(&keygen_stage1_inputs(SHARE_COUNT, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, THRESHOLD, SHARE_COUNT), keygen_stage2);
+    let mut stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, THRESHOLD, SHARE_COUNT), keygen_stage3);
+    stage3[2].dlog_proof = stage3[0].dlog_proof.clone();
+
+    let inputs = keygen_stage4_inputs(&stage1, &stage2, &stage3, THRESHOLD, SHARE_COUNT);
+    assert_eq!(blamed(keygen_stage4(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+/// Honest outputs of the offline stages for the quorum of parties 2 and 3,
+/// so quorum positions and keygen indices differ.
+struct Offline {
+    quorum: Quorum,
+    stage1: Vec<Secp256k1SignStage1Output>,
+    stage2: Vec<Secp256k1SignStage2Output>,
+    stage3: Vec<Secp256k1SignStage3Output>,
+    stage4: Vec<Secp256k1SignStage4Output>,
+    stage5: Vec<Secp256k1SignStage5Output>,
+    stage6: Vec<Secp256k1SignStage6Output>,
+}
+
+fn offline() -> Offline {
+    let keys = keygen(THRESHOLD, SHARE_COUNT, false);
+    let quorum = Quorum::new(&keys, &[2, 3]);
+    let stage1 = run(&quorum.stage1_inputs(), sign_stage1);
+    let stage2 = run(&quorum.stage2_inputs(&stage1), sign_stage2);
+    let stage3 = run(&quorum.stage3_inputs(&stage1, &stage2), sign_stage3);
+    let stage4 = run(&quorum.stage4_inputs(&stage3), sign_stage4);
+    let stage5 = run(&quorum.stage5_inputs(&stage1, &stage2, &stage4), sign_stage5);
+    let stage6 = run(&quorum.stage6_inputs(&stage1, &stage3, &stage5), sign_stage6);
+    Offline {
+        quorum,
+        stage1,
+        stage2,
+        stage3,
+        stage4,
+        stage5,
+        stage6,
+    }
+}
+
+#[test]
+fn sign_stage3_blames_tampered_mta_proof() {
+    let Offline {
+        quorum,
+        stage1,
+        mut stage2,
+        ..
+    } = offline();
+    stage2[1].m_b_gammas[0].b_proof = stage2[1].m_b_ws[0].b_proof.clone();
+
+    let inputs = quorum.stage3_inputs(&stage1, &stage2);
+    assert_eq!(blamed(sign_stage3(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_stage3_blames_wrong_w_i() {
+    let Offline {
+        quorum,
+        stage1,
+        mut stage2,
+        ..
+    } = offline();
+    stage2[1].m_b_ws[0] = stage2[1].m_b_gammas[0].clone();
+
+    let inputs = quorum.stage3_inputs(&stage1, &stage2);
+    assert_eq!(blamed(sign_stage3(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_stage4_blames_t_i_mismatch() {
+    let Offline {
+        quorum, mut stage3, ..
+    } = offline();
+    stage3[1].t_i = stage3[0].t_i.clone();
+
+    let inputs = quorum.stage4_inputs(&stage3);
+    assert_eq!(blamed(sign_stage4(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_stage5_blames_bad_decommitment() {
+    let Offline {
+        quorum,
+        mut stage1,
+        stage2,
+        stage4,
+        ..
+    } = offline();
+    stage1[1].decom1.blind_factor = BigInt::sample(256);
+
+    let inputs = quorum.stage5_inputs(&stage1, &stage2, &stage4);
+    assert_eq!(blamed(sign_stage5(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_stage6_blames_wrong_r_dash() {
+    let Offline {
+        quorum,
+        stage1,
+        stage3,
+        mut stage5,
+        ..
+    } = offline();
+    stage5[1].r_dash = stage5[0].r_dash.clone();
+
+    let inputs = quorum.stage6_inputs(&stage1, &stage3, &stage5);
+    assert_eq!(blamed(sign_stage6(&inputs[0]).unwrap_err()), vec![3]);
+}
+
+#[test]
+fn sign_stage7_blames_inconsistent_s_i() {
+    let Offline {
+        quorum,
+        stage1,
+        stage3,
+        stage5,
+        mut stage6,
+        ..
+    } = offline();
+    stage6[1].s_i = stage6[0].s_i.clone();
+
+    let completed = quorum.completed_offline_stages(&stage1, &stage3, &stage5);
+    let inputs = quorum.stage7_inputs(&completed, &stage6);
+    assert_eq!(blamed(sign_stage7(&inputs[0]).unwrap_err()), vec![3]);
+}
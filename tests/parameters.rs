@@ -0,0 +1,103 @@
+//! Paillier and ring-Pedersen parameters are checked in keygen, again when
+//! the key share is finalized, before signing and by `audit_key_share`.
+
+mod common;
+
+use curv::BigInt;
+
+use hello::error::Error;
+use hello::parameters::MIN_MODULUS_BITS;
+use hello::*;
+
+use common::*;
+
+fn blamed(e: Error) -> (String, Vec<u16>) {
+    match e {
+        Error::Blame { reason, parties } => (reason, parties),
+        e => panic!("expected a blamed party, got {}", e),
+    }
+}
+
+#[test]
+fn honest_key_passes_audit() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, 1, 3), keygen_stage3);
+    let shares = key_shares(&stage1, &stage2, &stage3, 1, 3);
+
+    let output = audit_key_share(&shares[0]).unwrap();
+    assert!(output.ok);
+    assert_eq!(output.parties.len(), 3);
+    for party in &output.parties {
+        assert!(party.issues.is_empty(), "party {}: {:?}", party.party, party.issues);
+        assert!(party.proofs_verified);
+        assert!(party.paillier_modulus_bits >= MIN_MODULUS_BITS);
+        assert!(party.n_tilde_bits >= MIN_MODULUS_BITS);
+    }
+}
+
+#[test]
+fn audit_and_signing_reject_tampered_parameters() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, 1, 3), keygen_stage3);
+    let mut shares = key_shares(&stage1, &stage2, &stage3, 1, 3);
+    let statement = &mut shares[0].local_key.h1_h2_n_tilde_vec[1];
+    statement.ni = statement.g.clone();
+
+    let output = audit_key_share(&shares[0]).unwrap();
+    assert!(!output.ok);
+    assert!(output.parties[0].issues.is_empty());
+    assert_eq!(
+        output.parties[1].issues,
+        vec![
+            "h1 equals h2".to_string(),
+            "ring-Pedersen parameters differ from the keygen broadcast".to_string(),
+        ]
+    );
+    // The broadcast itself is honest
+    assert!(output.parties[1].proofs_verified);
+
+    let keys: Vec<_> = shares.iter().map(|share| (*share.local_key).clone()).collect();
+    let quorum = Quorum::new(&keys, &[1, 2]);
+    let (reason, parties) = blamed(sign_stage1(&quorum.stage1_inputs()[0]).unwrap_err());
+    assert_eq!(reason, "party 2: h1 equals h2");
+    assert_eq!(parties, vec![2]);
+}
+
+#[test]
+fn keygen_stage2_blames_weak_paillier_modulus() {
+    let mut stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    stage1[1].bc1.e.n = &stage1[1].bc1.e.n * &BigInt::from(3);
+    stage1[2].bc1.e.n = BigInt::from(35);
+
+    let inputs = keygen_stage2_inputs(&stage1, 1, 3);
+    let (reason, parties) = blamed(keygen_stage2(&inputs[0]).unwrap_err());
+    assert_eq!(reason, "party 2: Paillier modulus is divisible by 3");
+    assert_eq!(parties, vec![2, 3]);
+}
+
+#[test]
+fn finalize_and_audit_reject_bad_proofs() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, 1, 3), keygen_stage3);
+    let mut inputs = keygen_finalize_inputs(&stage1, &stage2, &stage3, 1, 3);
+    let mut shares = run(&inputs, keygen_finalize);
+
+    // The proof of h2 to base h1 does not prove h1 to base h2
+    let bc1 = &mut inputs[0].bc1s[2];
+    bc1.composite_dlog_proof_base_h2 = bc1.composite_dlog_proof_base_h1.clone();
+    let (reason, parties) = blamed(keygen_finalize(&inputs[0]).unwrap_err());
+    assert_eq!(reason, "party 3: h1/h2 proofs do not verify");
+    assert_eq!(parties, vec![3]);
+
+    shares[0].bc1s[2] = inputs[0].bc1s[2].clone();
+    let output = audit_key_share(&shares[0]).unwrap();
+    assert!(!output.ok);
+    assert!(!output.parties[2].proofs_verified);
+    assert_eq!(output.parties[2].issues, vec!["h1/h2 proofs do not verify".to_string()]);
+
+    shares[0].bc1s.pop();
+    assert!(matches!(audit_key_share(&shares[0]), Err(Error::InvalidInput(_))));
+}
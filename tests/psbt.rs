@@ -0,0 +1,222 @@
+//! PSBT parsing and BIP143 sighashes against the examples of BIP143.
+
+use sha2::{Digest, Sha256};
+
+use hello::error::Error;
+use hello::psbt::Psbt;
+
+/// Native P2WPKH example of BIP143. Input 1 spends 6 BTC locked to the key.
+const P2WPKH_TX: &str = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b8\
+                         04cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9\
+                         148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2\
+                         f0167faa815988ac11000000";
+const P2WPKH_KEY: &str = "025476c2e83188368da1ff3e292e7acafcdb3566bb0ad253f62fc70f07aeee6357";
+const P2WPKH_SCRIPT: &str = "00141d0f172a0ecb48aee1be1f2687d2963ae33f71a1";
+const P2WPKH_SIGHASH: &str = "c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670";
+
+/// P2SH-P2WPKH example of BIP143, spending 10 BTC.
+const P2SH_P2WPKH_TX: &str = "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a54770100000000feffffff02b8\
+                              b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac0008af2f000000001976a914fd27\
+                              0b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000";
+const P2SH_P2WPKH_KEY: &str = "03ad1d8e89212f0b92c74d23bb710c00662ad1470198ac48c43f7d6f93a2a26873";
+const P2SH_P2WPKH_SCRIPT: &str = "a9144733f37cf4db86fbc2efed2500b4f4e49f31202387";
+const P2SH_P2WPKH_SIGHASH: &str = "64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6";
+
+fn bytes(hex: &str) -> Vec<u8> {
+    hex::decode(hex).unwrap()
+}
+
+fn var_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    if bytes.len() < 0xfd {
+        out.push(bytes.len() as u8);
+    } else {
+        out.push(0xfd);
+        out.extend_from_slice(&(bytes.len() as u16).to_le_bytes());
+    }
+    out.extend_from_slice(bytes);
+}
+
+fn witness_utxo(value: u64, script_pubkey: &[u8]) -> Vec<u8> {
+    let mut out = value.to_le_bytes().to_vec();
+    var_bytes(&mut out, script_pubkey);
+    out
+}
+
+/// A PSBT of `tx` with the given key-value pairs per input and empty output
+/// maps.
+fn psbt(tx: &[u8], inputs: &[Vec<(Vec<u8>, Vec<u8>)>], outputs: usize) -> Vec<u8> {
+    let mut out = b"psbt\xff".to_vec();
+    var_bytes(&mut out, &[0x00]);
+    var_bytes(&mut out, tx);
+    out.push(0x00);
+    for input in inputs {
+        for (key, value) in input {
+            var_bytes(&mut out, key);
+            var_bytes(&mut out, value);
+        }
+        out.push(0x00);
+    }
+    out.extend(std::iter::repeat(0x00).take(outputs));
+    out
+}
+
+/// Legacy serialization of a transaction with one input, spending
+/// `outpoint`, and the given outputs.
+fn transaction(outpoint: &[u8], outputs: &[(u64, Vec<u8>)]) -> Vec<u8> {
+    let mut tx = vec![1, 0, 0, 0, 1];
+    tx.extend_from_slice(outpoint);
+    tx.push(0x00);
+    tx.extend_from_slice(&[0xff; 4]);
+    tx.push(outputs.len() as u8);
+    for (value, script_pubkey) in outputs {
+        tx.extend(witness_utxo(*value, script_pubkey));
+    }
+    tx.extend_from_slice(&[0; 4]);
+    tx
+}
+
+fn txid(tx: &[u8]) -> Vec<u8> {
+    Sha256::digest(&Sha256::digest(tx)).to_vec()
+}
+
+fn sighashes(psbt: &[u8], key: &str) -> Result<Vec<String>, Error> {
+    Ok(Psbt::parse(psbt)?
+        .segwit_v0_sighashes(&bytes(key), &[])?
+        .iter()
+        .map(|s| hex::encode(s.sighash))
+        .collect())
+}
+
+#[test]
+fn bip143_native_p2wpkh() {
+    let utxo = witness_utxo(600_000_000, &bytes(P2WPKH_SCRIPT));
+    // The first input is P2PK and has no UTXO information, it is skipped
+    let psbt = psbt(&bytes(P2WPKH_TX), &[vec![], vec![(vec![0x01], utxo)]], 2);
+    let parsed = Psbt::parse(&psbt).unwrap();
+    let sighashes = parsed.segwit_v0_sighashes(&bytes(P2WPKH_KEY), &[]).unwrap();
+    assert_eq!(sighashes.len(), 1);
+    assert_eq!(sighashes[0].input, 1);
+    assert_eq!(sighashes[0].sighash_type, 1);
+    assert_eq!(hex::encode(sighashes[0].sighash), P2WPKH_SIGHASH);
+}
+
+#[test]
+fn bip143_p2sh_p2wpkh() {
+    let utxo = witness_utxo(1_000_000_000, &bytes(P2SH_P2WPKH_SCRIPT));
+    let psbt = psbt(&bytes(P2SH_P2WPKH_TX), &[vec![(vec![0x01], utxo)]], 2);
+    assert_eq!(sighashes(&psbt, P2SH_P2WPKH_KEY).unwrap(), vec![P2SH_P2WPKH_SIGHASH]);
+}
+
+#[test]
+fn bip174_round_trip() {
+    let utxo = witness_utxo(1_000_000_000, &bytes(P2SH_P2WPKH_SCRIPT));
+    // Input keys this crate ignores, proprietary ones included, survive untouched
+    let inputs = [vec![
+        (vec![0x01], utxo),
+        (vec![0x03], 1u32.to_le_bytes().to_vec()),
+        (vec![0xfc, 0x01, 0x61, 0x00], vec![0xde, 0xad]),
+    ]];
+    let original = psbt(&bytes(P2SH_P2WPKH_TX), &inputs, 2);
+    let parsed = Psbt::parse(&original).unwrap();
+    assert_eq!(parsed.serialize(), original);
+
+    let mut signed = parsed.clone();
+    signed.insert_partial_signature(0, &bytes(P2SH_P2WPKH_KEY), &[0x30; 72]).unwrap();
+    let reparsed = Psbt::parse(&signed.serialize()).unwrap();
+    assert_eq!(reparsed.serialize(), signed.serialize());
+    assert_ne!(signed.serialize(), original);
+}
+
+#[test]
+fn trailing_bytes_are_rejected() {
+    let mut tx = bytes(P2SH_P2WPKH_TX);
+    tx.push(0x00);
+    assert!(Psbt::parse(&psbt(&tx, &[vec![]], 2)).is_err());
+
+    let mut utxo = witness_utxo(1_000_000_000, &bytes(P2SH_P2WPKH_SCRIPT));
+    utxo.push(0x00);
+    let psbt = psbt(&bytes(P2SH_P2WPKH_TX), &[vec![(vec![0x01], utxo)]], 2);
+    assert!(sighashes(&psbt, P2SH_P2WPKH_KEY).is_err());
+}
+
+#[test]
+fn non_witness_utxo_must_match_the_outpoint() {
+    let script = bytes(P2WPKH_SCRIPT);
+    let prev = transaction(&[7; 36], &[(1_000, vec![0x51]), (600_000_000, script.clone())]);
+    let mut outpoint = txid(&prev);
+    outpoint.extend_from_slice(&1u32.to_le_bytes());
+    let tx = transaction(&outpoint, &[(599_990_000, vec![0x51])]);
+
+    let with_witness_utxo = psbt(&tx, &[vec![(vec![0x01], witness_utxo(600_000_000, &script))]], 1);
+    let expected = sighashes(&with_witness_utxo, P2WPKH_KEY).unwrap();
+    assert_eq!(expected.len(), 1);
+
+    let with_prev = psbt(&tx, &[vec![(vec![0x00], prev.clone())]], 1);
+    assert_eq!(sighashes(&with_prev, P2WPKH_KEY).unwrap(), expected);
+
+    // Both present and consistent
+    let both = psbt(
+        &tx,
+        &[vec![
+            (vec![0x00], prev.clone()),
+            (vec![0x01], witness_utxo(600_000_000, &script)),
+        ]],
+        1,
+    );
+    assert_eq!(sighashes(&both, P2WPKH_KEY).unwrap(), expected);
+
+    // A witness UTXO lying about the amount is caught by the full transaction
+    let lying = psbt(
+        &tx,
+        &[vec![
+            (vec![0x00], prev.clone()),
+            (vec![0x01], witness_utxo(700_000_000, &script)),
+        ]],
+        1,
+    );
+    assert_eq!(
+        sighashes(&lying, P2WPKH_KEY).unwrap_err(),
+        Error::InvalidPsbtInput(0, "witness UTXO differs from the previous transaction")
+    );
+
+    // A previous transaction that is not the one the outpoint names
+    let other = transaction(&[8; 36], &[(1_000, vec![0x51]), (700_000_000, script)]);
+    let wrong = psbt(&tx, &[vec![(vec![0x00], other)]], 1);
+    assert_eq!(
+        sighashes(&wrong, P2WPKH_KEY).unwrap_err(),
+        Error::InvalidPsbtInput(0, "previous transaction does not match the outpoint")
+    );
+}
+
+#[test]
+fn sighash_types_other_than_all_must_be_allowed() {
+    let with_type = |sighash_type: u32| {
+        let utxo = witness_utxo(1_000_000_000, &bytes(P2SH_P2WPKH_SCRIPT));
+        let input = vec![(vec![0x01], utxo), (vec![0x03], sighash_type.to_le_bytes().to_vec())];
+        Psbt::parse(&psbt(&bytes(P2SH_P2WPKH_TX), &[input], 2)).unwrap()
+    };
+    let key = bytes(P2SH_P2WPKH_KEY);
+
+    let all = with_type(0x01).segwit_v0_sighashes(&key, &[]).unwrap();
+    assert_eq!(hex::encode(all[0].sighash), P2SH_P2WPKH_SIGHASH);
+
+    // SIGHASH_NONE, SIGHASH_SINGLE and ANYONECANPAY are refused by default
+    for &sighash_type in [0x02, 0x03, 0x81, 0x82, 0x83].iter() {
+        let parsed = with_type(sighash_type);
+        assert_eq!(
+            parsed.segwit_v0_sighashes(&key, &[]).unwrap_err(),
+            Error::InvalidPsbtInput(0, "sighash type not allowed")
+        );
+        let allowed = parsed.segwit_v0_sighashes(&key, &[sighash_type]).unwrap();
+        assert_eq!(allowed[0].sighash_type, sighash_type);
+        assert_ne!(allowed[0].sighash, all[0].sighash);
+    }
+
+    // Bits outside 0x9f are never signed, even when listed
+    for &sighash_type in [0x41, 0x101, 0x8000_0001].iter() {
+        assert_eq!(
+            with_type(sighash_type).segwit_v0_sighashes(&key, &[sighash_type]).unwrap_err(),
+            Error::InvalidPsbtInput(0, "undefined sighash type")
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! The generated schemas must accept what the stages actually serialize,
+//! which checks the shadow types against the real curv and
+//! multi-party-ecdsa serde layouts. Run with `--features schema`.
+
+#![cfg(feature = "schema")]
+
+mod common;
+
+use std::collections::HashMap;
+
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::Value;
+
+use hello::message::MessageHashing;
+use hello::schema;
+use hello::*;
+
+use common::*;
+
+/// Why `value` does not match `schema`, if it does not.
+fn mismatch(value: &Value, schema: &Value, definitions: &Value, path: &str) -> Option<String> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        return mismatch(value, &definitions[name], definitions, path);
+    }
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        if let Some(e) = all_of.iter().find_map(|s| mismatch(value, s, definitions, path)) {
+            return Some(e);
+        }
+    }
+    for key in &["anyOf", "oneOf"] {
+        if let Some(alternatives) = schema.get(*key).and_then(Value::as_array) {
+            if alternatives.iter().all(|s| mismatch(value, s, definitions, path).is_some()) {
+                return Some(format!("{}: no alternative of {} matches {}", path, key, value));
+            }
+        }
+    }
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        if !values.contains(value) {
+            return Some(format!("{}: {} is not one of {:?}", path, value, values));
+        }
+    }
+    if let Some(types) = schema.get("type") {
+        let types: Vec<&str> = match types {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            t => t.as_str().into_iter().collect(),
+        };
+        let actual = match value {
+            Value::Null => "null",
+            Value::Bool(_) => "boolean",
+            Value::Number(n) if n.is_f64() => "number",
+            Value::Number(_) => "integer",
+            Value::String(_) => "string",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        };
+        if !types.iter().any(|&t| t == actual || (t == "number" && actual == "integer")) {
+            return Some(format!("{}: expected {:?}, got {}", path, types, value));
+        }
+    }
+
+    match value {
+        Value::Array(items)
+            if schema.get("minItems").and_then(Value::as_u64).map_or(false, |n| (items.len() as u64) < n)
+                || schema.get("maxItems").and_then(Value::as_u64).map_or(false, |n| (items.len() as u64) > n) =>
+        {
+            Some(format!("{}: wrong length {}", path, items.len()))
+        }
+        Value::Array(items) => match schema.get("items") {
+            Some(Value::Array(tuple)) if tuple.len() != items.len() => {
+                Some(format!("{}: expected a {}-tuple, got {}", path, tuple.len(), items.len()))
+            }
+            Some(Value::Array(tuple)) => items.iter().zip(tuple).enumerate().find_map(|(i, (item, s))| {
+                mismatch(item, s, definitions, &format!("{}[{}]", path, i))
+            }),
+            Some(s) => items
+                .iter()
+                .enumerate()
+                .find_map(|(i, item)| mismatch(item, s, definitions, &format!("{}[{}]", path, i))),
+            None => None,
+        },
+        Value::Object(fields) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let additional = schema.get("additionalProperties");
+            for name in schema.get("required").and_then(Value::as_array).into_iter().flatten() {
+                let name = name.as_str().unwrap();
+                if !fields.contains_key(name) {
+                    return Some(format!("{}: missing {}", path, name));
+                }
+            }
+            fields.iter().find_map(|(name, field)| {
+                let path = format!("{}.{}", path, name);
+                match (properties.and_then(|p| p.get(name)), additional) {
+                    (Some(s), _) | (None, Some(s)) => mismatch(field, s, definitions, &path),
+                    (None, None) if properties.is_some() => Some(format!("{}: not in the schema", path)),
+                    (None, None) => None,
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+struct Schemas(HashMap<String, Value>);
+
+impl Schemas {
+    fn new() -> Self {
+        Schemas(
+            schema::schemas()
+                .into_iter()
+                .map(|(name, root)| (name, serde_json::to_value(root).unwrap()))
+                .collect(),
+        )
+    }
+
+    fn check<T: Serialize + JsonSchema>(&self, values: &[T]) {
+        let name = T::schema_name();
+        let root = self.0.get(&name).unwrap_or_else(|| panic!("no schema for {}", name));
+        let definitions = root.get("definitions").cloned().unwrap_or(Value::Null);
+        let value = serde_json::to_value(&values[0]).unwrap();
+        if let Some(e) = mismatch(&value, root, &definitions, &name) {
+            panic!("{}", e);
+        }
+    }
+}
+
+#[test]
+fn stage_messages_match_their_schemas() {
+    let schemas = Schemas::new();
+
+    let inputs = keygen_stage1_inputs(3, false);
+    let stage1 = run(&inputs, keygen_stage1);
+    schemas.check(&inputs);
+    schemas.check(&stage1);
+    let inputs = keygen_stage2_inputs(&stage1, 1, 3);
+    let stage2 = run(&inputs, keygen_stage2);
+    schemas.check(&inputs);
+    schemas.check(&stage2);
+    let inputs = keygen_stage3_inputs(&stage1, &stage2, 1, 3);
+    let stage3 = run(&inputs, keygen_stage3);
+    schemas.check(&inputs);
+    schemas.check(&stage3);
+    let inputs = keygen_stage4_inputs(&stage1, &stage2, &stage3, 1, 3);
+    schemas.check(&inputs);
+    schemas.check(&run(&inputs, keygen_stage4));
+
+    let inputs = keygen_finalize_inputs(&stage1, &stage2, &stage3, 1, 3);
+    let shares = run(&inputs, keygen_finalize);
+    schemas.check(&inputs);
+    schemas.check(&shares);
+
+    let keys = local_keys(&stage1, &stage2, &stage3, 1, 3);
+    let quorum = Quorum::new(&keys, &[1, 3]);
+    let inputs = quorum.stage1_inputs();
+    let stage1 = run(&inputs, sign_stage1);
+    schemas.check(&inputs);
+    schemas.check(&stage1);
+    let inputs = quorum.stage2_inputs(&stage1);
+    let stage2 = run(&inputs, sign_stage2);
+    schemas.check(&inputs);
+    schemas.check(&stage2);
+    let inputs = quorum.stage3_inputs(&stage1, &stage2);
+    let stage3 = run(&inputs, sign_stage3);
+    schemas.check(&inputs);
+    schemas.check(&stage3);
+    let inputs = quorum.stage4_inputs(&stage3);
+    let stage4 = run(&inputs, sign_stage4);
+    schemas.check(&inputs);
+    schemas.check(&stage4);
+    let inputs = quorum.stage5_inputs(&stage1, &stage2, &stage4);
+    let stage5 = run(&inputs, sign_stage5);
+    schemas.check(&inputs);
+    schemas.check(&stage5);
+    let inputs = quorum.stage6_inputs(&stage1, &stage3, &stage5);
+    let stage6 = run(&inputs, sign_stage6);
+    schemas.check(&inputs);
+    schemas.check(&stage6);
+    let completed = quorum.completed_offline_stages(&stage1, &stage3, &stage5);
+    let inputs = quorum.stage7_inputs(&completed, &stage6);
+    schemas.check(&inputs);
+    schemas.check(&run(&inputs, sign_stage7));
+    let inputs = quorum.stage8_inputs(&completed, b"schema", MessageHashing::Sha256);
+    let stage8 = run(&inputs, sign_stage8);
+    schemas.check(&inputs);
+    schemas.check(&stage8);
+    let inputs = quorum.stage9_inputs(&stage8);
+    schemas.check(&inputs);
+    schemas.check(&run(&inputs, sign_stage9));
+
+    let inputs = [Secp256k1InspectKeyShareInput {
+        local_key: Secret::new(keys[0].clone()),
+    }];
+    schemas.check(&inputs);
+    schemas.check(&run(&inputs, inspect_key_share));
+
+    schemas.check(&run(&shares, audit_key_share));
+}
+
+#[test]
+fn typescript_declares_every_schema() {
+    let declarations = schema::typescript();
+    for (name, _) in schema::schemas() {
+        assert!(
+            declarations.contains(&format!("export interface {} ", name))
+                || declarations.contains(&format!("export type {} ", name)),
+            "{} is not declared",
+            name
+        );
+    }
+}
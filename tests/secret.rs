@@ -0,0 +1,105 @@
+//! Secrets in stage messages are redacted in `Debug` output, wiped on
+//! request and serialized like the values they wrap.
+
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use curv::arithmetic::Zero;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+
+use hello::secret::Wipe;
+use hello::*;
+
+use common::*;
+
+#[test]
+fn debug_output_redacts_secrets() {
+    let keys = keygen(1, 3, false);
+    let quorum = Quorum::new(&keys, &[1, 2]);
+    let inputs = quorum.stage1_inputs();
+    let stage1 = run(&inputs, sign_stage1);
+
+    let debug = format!("{:?} {:?}", inputs[0], stage1[0]);
+    assert!(debug.contains("Secret([REDACTED])"));
+    assert!(!debug.contains(&format!("{:?}", keys[0].keys_linear.x_i)));
+    assert!(!debug.contains(&format!("{:?}", stage1[0].sign_key.k_i)));
+}
+
+#[test]
+fn secrets_serialize_transparently() {
+    let keys = keygen(1, 3, false);
+    let secret = Secret::new(keys[0].clone());
+    assert_eq!(
+        serde_json::to_value(&secret).unwrap(),
+        serde_json::to_value(&keys[0]).unwrap()
+    );
+    let parsed: Secret<LocalKey<Secp256k1>> = serde_json::from_value(serde_json::to_value(&keys[0]).unwrap()).unwrap();
+    assert_eq!(parsed.keys_linear.x_i, keys[0].keys_linear.x_i);
+}
+
+#[test]
+fn wipe_clears_key_material() {
+    let mut key = keygen(1, 3, false).remove(0);
+    key.wipe();
+    assert_eq!(key.keys_linear.x_i, Scalar::<Secp256k1>::zero());
+    assert_eq!(key.paillier_dk.p, BigInt::zero());
+    assert_eq!(key.paillier_dk.q, BigInt::zero());
+}
+
+#[test]
+fn keygen_shares_are_secret() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let mut stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let debug = format!("{:?}", stage2[0]);
+    assert!(debug.contains("Secret([REDACTED])"));
+    assert!(!debug.contains(&format!("{:?}", stage2[0].vss.1[1])));
+
+    let commitments = stage2[0].vss.0.commitments.clone();
+    stage2[0].vss.wipe();
+    assert!(stage2[0].vss.1.iter().all(|share| *share == Scalar::<Secp256k1>::zero()));
+    // The public scheme is kept
+    assert_eq!(stage2[0].vss.0.commitments, commitments);
+}
+
+#[test]
+fn reconstructed_private_key_is_secret() {
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let stage2 = run(&keygen_stage2_inputs(&stage1, 1, 3), keygen_stage2);
+    let stage3 = run(&keygen_stage3_inputs(&stage1, &stage2, 1, 3), keygen_stage3);
+    let keys = local_keys(&stage1, &stage2, &stage3, 1, 3);
+
+    let private_key = construct_private_key(&Secp256k1ConstructPrivateKeyInput {
+        vss_scheme: keys[0].vss_scheme.clone(),
+        parties: vec![PartyIndex(1), PartyIndex(3)],
+        xs: Secret::new(vec![keys[0].keys_linear.x_i.clone(), keys[2].keys_linear.x_i.clone()]),
+    })
+    .unwrap();
+    assert_eq!(Point::generator() * &*private_key, keys[0].y_sum_s);
+    assert_eq!(format!("{:?}", private_key), "Secret([REDACTED])");
+    assert_eq!(
+        serde_json::to_value(&private_key).unwrap(),
+        serde_json::to_value(&*private_key).unwrap()
+    );
+}
+
+/// Records whether it was wiped.
+struct Probe(Rc<Cell<bool>>);
+
+impl Wipe for Probe {
+    fn wipe(&mut self) {
+        self.0.set(true);
+    }
+}
+
+#[test]
+fn drop_wipes_the_value() {
+    let wiped = Rc::new(Cell::new(false));
+    let secret = Secret::new(Probe(wiped.clone()));
+    assert!(!wiped.get());
+    drop(secret);
+    assert!(wiped.get());
+}
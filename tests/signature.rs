@@ -0,0 +1,90 @@
+//! Known-answer tests of the signature encodings.
+
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::party_i::SignatureRecid;
+
+use hello::signature::{encode, normalize_s};
+
+const ORDER: &str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+fn scalar(hex: &str) -> Scalar<Secp256k1> {
+    Scalar::from_bigint(&BigInt::from_hex(hex).unwrap())
+}
+
+fn signature(r: &str, s: &str, recid: u8) -> SignatureRecid {
+    SignatureRecid {
+        r: scalar(r),
+        s: scalar(s),
+        recid,
+    }
+}
+
+fn bytes(hex: &str) -> Vec<u8> {
+    hex::decode(hex).unwrap()
+}
+
+#[test]
+fn high_bit_r_gets_a_zero_pad() {
+    // RFC 6979 signature of "Satoshi Nakamoto" with private key 1
+    let r = "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8";
+    let s = "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5";
+    let encoded = encode(&signature(r, s, 1));
+    assert_eq!(encoded.der, bytes(&format!("3045022100{}0220{}", r, s)));
+    assert_eq!(encoded.compact, bytes(&format!("{}{}", r, s)));
+    assert_eq!(encoded.recoverable, bytes(&format!("{}{}1c", r, s)));
+    assert_eq!(encoded.bitcoin_compact, bytes(&format!("20{}{}", r, s)));
+    assert_eq!(encoded.recid, 1);
+}
+
+#[test]
+fn leading_zero_bytes_are_stripped() {
+    let r = "0000ff0000000000000000000000000000000000000000000000000000000001";
+    let s = "0000000000000000000000000000000000000000000000000000000000007f01";
+    let encoded = encode(&signature(r, s, 0));
+    assert_eq!(
+        encoded.der,
+        bytes("3025021f00ff000000000000000000000000000000000000000000000000000000000102027f01")
+    );
+    // The fixed-width encodings keep the zero bytes
+    assert_eq!(encoded.compact, bytes(&format!("{}{}", r, s)));
+    assert_eq!(encoded.recoverable, bytes(&format!("{}{}1b", r, s)));
+    assert_eq!(encoded.bitcoin_compact, bytes(&format!("1f{}{}", r, s)));
+}
+
+#[test]
+fn single_byte_values() {
+    let one = "0000000000000000000000000000000000000000000000000000000000000001";
+    let high = "0000000000000000000000000000000000000000000000000000000000000080";
+    let encoded = encode(&signature(one, high, 0));
+    assert_eq!(encoded.der, bytes("300702010102020080"));
+}
+
+#[test]
+fn high_s_is_normalized() {
+    // s = n - 1 becomes 1 and the recovery id flips parity
+    let r = "8000000000000000000000000000000000000000000000000000000000000001";
+    let n_minus_1 = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+    let encoded = encode(&signature(r, n_minus_1, 0));
+    assert_eq!(encoded.recid, 1);
+    assert_eq!(encoded.der, bytes(&format!("3026022100{}020101", r)));
+    assert_eq!(encoded.compact, bytes(&format!("{}{:0>64}", r, "1")));
+    assert_eq!(encoded.recoverable[64], 28);
+    assert_eq!(encoded.bitcoin_compact[0], 32);
+}
+
+#[test]
+fn half_order_is_the_boundary() {
+    let order = BigInt::from_hex(ORDER).unwrap();
+    let half = &order / BigInt::from(2);
+    let r = "01";
+
+    let low = normalize_s(&signature(r, &half.to_hex(), 2));
+    assert_eq!(low.s.to_bigint(), half);
+    assert_eq!(low.recid, 2);
+
+    let high = normalize_s(&signature(r, &(&half + BigInt::from(1)).to_hex(), 2));
+    assert_eq!(high.s.to_bigint(), half);
+    assert_eq!(high.recid, 3);
+}
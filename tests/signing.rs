@@ -0,0 +1,136 @@
+//! End-to-end keygen and signing with every party simulated in-process.
+
+mod common;
+
+use curv::arithmetic::Converter;
+use curv::elliptic::curves::{secp256_k1::Secp256k1, Point, Scalar};
+use curv::BigInt;
+use multi_party_ecdsa::protocols::multi_party_ecdsa::gg_2020::state_machine::keygen::LocalKey;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use hello::message::MessageHashing;
+use hello::signature;
+use hello::{tweak_key, Secp256k1TweakKeyInput, Secret};
+
+use common::{keygen, Quorum};
+
+fn random_quorum<R: Rng>(rng: &mut R, threshold: u16, share_count: u16) -> Vec<u16> {
+    let size = rng.gen_range(threshold + 1..=share_count);
+    let all: Vec<u16> = (1..=share_count).collect();
+    let mut parties: Vec<u16> = all.choose_multiple(rng, usize::from(size)).cloned().collect();
+    parties.sort_unstable();
+    parties
+}
+
+fn check_keys(keys: &[LocalKey<Secp256k1>], parties: &[u16]) {
+    let y_sum_s = &keys[0].y_sum_s;
+    for key in keys {
+        assert_eq!(&key.y_sum_s, y_sum_s);
+        assert_eq!(&key.keys_linear.y, y_sum_s);
+        assert_eq!(
+            Point::generator() * &key.keys_linear.x_i,
+            key.pk_vec[usize::from(key.i) - 1]
+        );
+    }
+
+    let indices: Vec<u16> = parties.iter().map(|&p| p - 1).collect();
+    let xs: Vec<_> = parties
+        .iter()
+        .map(|&p| keys[usize::from(p) - 1].keys_linear.x_i.clone())
+        .collect();
+    let x = keys[0].vss_scheme.reconstruct(&indices, &xs);
+    assert_eq!(&(Point::generator() * &x), y_sum_s, "quorum {:?}", parties);
+}
+
+fn check_signing(keys: &[LocalKey<Secp256k1>], parties: &[u16], message: &[u8]) {
+    let quorum = Quorum::new(keys, parties);
+    let outputs = quorum.sign(message, MessageHashing::Sha256);
+    let message_hash = BigInt::from_bytes(&MessageHashing::Sha256.digest(message).unwrap());
+    let y_sum_s = &keys[0].y_sum_s;
+
+    for output in outputs.iter() {
+        assert_eq!(output.signature.r, outputs[0].signature.r);
+        assert_eq!(output.signature.s, outputs[0].signature.s);
+        assert!(
+            signature::verify(&output.signature, y_sum_s, &message_hash),
+            "quorum {:?}",
+            parties
+        );
+        assert_eq!(&signature::recover(&output.signature, &message_hash).unwrap(), y_sum_s);
+    }
+}
+
+#[test]
+fn two_of_three() {
+    let keys = keygen(1, 3, false);
+    for parties in [[1, 2], [1, 3], [2, 3]].iter() {
+        check_keys(&keys, parties);
+        check_signing(&keys, parties, b"two of three");
+    }
+}
+
+#[test]
+fn full_quorum() {
+    let keys = keygen(1, 3, false);
+    check_keys(&keys, &[1, 2, 3]);
+    check_signing(&keys, &[1, 2, 3], b"full quorum");
+}
+
+/// Draws the configurations from a seeded generator. A failure prints the
+/// seed; rerun with `MPC_TEST_SEED=<seed>` to reproduce it.
+#[test]
+fn random_configurations() {
+    let seed = match std::env::var("MPC_TEST_SEED") {
+        Ok(seed) => seed.parse().expect("MPC_TEST_SEED is not a number"),
+        Err(_) => rand::thread_rng().gen(),
+    };
+    println!("MPC_TEST_SEED={}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..3 {
+        let share_count = rng.gen_range(2..=5);
+        let threshold = rng.gen_range(1..share_count);
+        let keys = keygen(threshold, share_count, false);
+
+        for _ in 0..2 {
+            let parties = random_quorum(&mut rng, threshold, share_count);
+            let mut message = vec![0u8; rng.gen_range(0..100)];
+            rng.fill(&mut message[..]);
+            check_keys(&keys, &parties);
+            check_signing(&keys, &parties, &message);
+        }
+    }
+}
+
+#[test]
+fn offline_then_online_stages() {
+    let keys = keygen(2, 4, false);
+    let quorum = Quorum::new(&keys, &[1, 3, 4]);
+    let completed = quorum.offline();
+    let outputs = quorum.online(&completed, b"online", MessageHashing::Keccak256);
+    let message_hash = BigInt::from_bytes(&MessageHashing::Keccak256.digest(b"online").unwrap());
+    assert!(signature::verify(&outputs[0].signature, &keys[0].y_sum_s, &message_hash));
+}
+
+#[test]
+fn tweaked_keys_sign_for_the_tweaked_public_key() {
+    let keys = keygen(1, 3, false);
+    let il = Scalar::<Secp256k1>::random();
+    let tweaked: Vec<_> = keys
+        .iter()
+        .map(|local_key| {
+            let input = Secp256k1TweakKeyInput {
+                local_key: Secret::new(local_key.clone()),
+                il: il.clone(),
+            };
+            (*tweak_key(&input).unwrap().new_local_key).clone()
+        })
+        .collect();
+    assert_eq!(tweaked[0].y_sum_s, &keys[0].y_sum_s + Point::generator() * &il);
+
+    for parties in [[1, 2], [2, 3]].iter() {
+        check_keys(&tweaked, parties);
+        check_signing(&tweaked, parties, b"tweaked");
+    }
+}
@@ -0,0 +1,95 @@
+//! Malformed stage inputs are rejected with `Error::InvalidInput` before
+//! the protocol code can panic on them.
+
+mod common;
+
+use hello::error::Error;
+use hello::*;
+
+use common::*;
+
+fn invalid<T: std::fmt::Debug>(result: Result<T, Error>) -> String {
+    match result {
+        Err(Error::InvalidInput(reason)) => reason,
+        r => panic!("expected invalid input, got {:?}", r.map(|_| ())),
+    }
+}
+
+#[test]
+fn keygen_rejects_bad_parameters() {
+    let mut inputs = keygen_stage1_inputs(3, false);
+    inputs[0].index = PartyIndex(0);
+    invalid(keygen_stage1(&inputs[0]));
+
+    let stage1 = run(&keygen_stage1_inputs(3, false), keygen_stage1);
+    let inputs = keygen_stage2_inputs(&stage1, 3, 3);
+    invalid(keygen_stage2(&inputs[0]));
+    let inputs = keygen_stage2_inputs(&stage1, 0, 3);
+    invalid(keygen_stage2(&inputs[0]));
+
+    let mut inputs = keygen_stage2_inputs(&stage1, 1, 3);
+    inputs[0].decom1s.pop();
+    assert_eq!(invalid(keygen_stage2(&inputs[0])), "decom1s has 2 entries, expected 3");
+}
+
+#[test]
+fn sign_rejects_bad_quorums() {
+    let keys = keygen(1, 3, false);
+    let quorum = Quorum::new(&keys, &[1, 3]);
+    let inputs = quorum.stage1_inputs();
+
+    let mut input = inputs[1].clone();
+    input.parties = vec![PartyIndex(3), PartyIndex(3)];
+    invalid(sign_stage1(&input));
+    input.parties = vec![PartyIndex(0), PartyIndex(3)];
+    invalid(sign_stage1(&input));
+    input.parties = vec![PartyIndex(1), PartyIndex(4)];
+    invalid(sign_stage1(&input));
+    input.parties = vec![PartyIndex(3)];
+    invalid(sign_stage1(&input));
+
+    // The quorum must include the holder of the local key
+    let mut input = inputs[1].clone();
+    input.parties = vec![PartyIndex(1), PartyIndex(2)];
+    assert_eq!(
+        invalid(sign_stage1(&input)),
+        "parties does not include this key's party 3"
+    );
+}
+
+#[test]
+fn sign_rejects_mismatched_messages() {
+    let keys = keygen(1, 3, false);
+    let quorum = Quorum::new(&keys, &[2, 3]);
+    let stage1 = run(&quorum.stage1_inputs(), sign_stage1);
+
+    let mut inputs = quorum.stage2_inputs(&stage1);
+    inputs[0].m_as.pop();
+    assert_eq!(invalid(sign_stage2(&inputs[0])), "m_as has 1 entries, expected 2");
+
+    let stage2 = run(&quorum.stage2_inputs(&stage1), sign_stage2);
+    let mut inputs = quorum.stage3_inputs(&stage1, &stage2);
+    inputs[1].m_b_ws.clear();
+    invalid(sign_stage3(&inputs[1]));
+}
+
+#[test]
+fn construct_private_key_needs_enough_shares() {
+    let keys = keygen(1, 3, false);
+    let input = Secp256k1ConstructPrivateKeyInput {
+        vss_scheme: keys[0].vss_scheme.clone(),
+        parties: vec![PartyIndex(2)],
+        xs: Secret::new(vec![keys[1].keys_linear.x_i.clone()]),
+    };
+    invalid(construct_private_key(&input));
+}
+
+#[test]
+fn inspect_rejects_out_of_range_index() {
+    let mut key = keygen(1, 3, false).remove(0);
+    key.i = 4;
+    let input = Secp256k1InspectKeyShareInput {
+        local_key: Secret::new(key),
+    };
+    assert_eq!(invalid(inspect_key_share(&input)), "party index 4 out of range");
+}
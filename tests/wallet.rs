@@ -0,0 +1,122 @@
+//! Signature verification and public key recovery on quorum signatures.
+
+mod common;
+
+use hello::error::Error;
+use hello::message::MessageHashing;
+use hello::*;
+
+use common::*;
+
+#[test]
+fn verify_and_recover_round_trip() {
+    let keys = keygen(1, 3, false);
+    let message = b"Hello world";
+    let hashing = MessageHashing::EthereumPersonal;
+    let signature = Quorum::new(&keys, &[1, 3]).sign(message, hashing).remove(0).signature;
+
+    let verified = verify_signature(&Secp256k1VerifySignatureInput {
+        signature: signature.clone(),
+        public_key: Some(keys[0].y_sum_s.clone()),
+        local_key: None,
+        message: message.to_vec(),
+        hashing,
+    })
+    .unwrap();
+    assert!(verified.valid);
+
+    let recovered = recover_public_key(&Secp256k1RecoverPublicKeyInput {
+        signature: signature.clone(),
+        message: message.to_vec(),
+        hashing,
+    })
+    .unwrap();
+    assert_eq!(recovered.public_key, keys[0].y_sum_s);
+
+    // The same signature checked against the digest itself
+    let recovered = recover_public_key(&Secp256k1RecoverPublicKeyInput {
+        signature,
+        message: hashing.digest(message).unwrap().to_vec(),
+        hashing: MessageHashing::Prehashed,
+    })
+    .unwrap();
+    assert_eq!(recovered.public_key, keys[0].y_sum_s);
+}
+
+#[test]
+fn other_message_does_not_verify() {
+    let keys = keygen(1, 3, false);
+    let hashing = MessageHashing::Sha256;
+    let signature = Quorum::new(&keys, &[1, 2]).sign(b"Hello world", hashing).remove(0).signature;
+
+    let verified = verify_signature(&Secp256k1VerifySignatureInput {
+        signature: signature.clone(),
+        public_key: Some(keys[0].y_sum_s.clone()),
+        local_key: None,
+        message: b"Hello world!".to_vec(),
+        hashing,
+    })
+    .unwrap();
+    assert!(!verified.valid);
+
+    let recovered = recover_public_key(&Secp256k1RecoverPublicKeyInput {
+        signature,
+        message: b"Hello world!".to_vec(),
+        hashing,
+    })
+    .unwrap();
+    assert_ne!(recovered.public_key, keys[0].y_sum_s);
+}
+
+#[test]
+fn prehashed_message_must_be_32_bytes() {
+    let keys = keygen(1, 3, false);
+    let signature = Quorum::new(&keys, &[1, 2])
+        .sign(&[7; 32], MessageHashing::Prehashed)
+        .remove(0)
+        .signature;
+
+    let e = verify_signature(&Secp256k1VerifySignatureInput {
+        signature: signature.clone(),
+        public_key: Some(keys[0].y_sum_s.clone()),
+        local_key: None,
+        message: vec![7; 31],
+        hashing: MessageHashing::Prehashed,
+    })
+    .unwrap_err();
+    assert_eq!(e, Error::InvalidMessageLength(31));
+
+    let e = recover_public_key(&Secp256k1RecoverPublicKeyInput {
+        signature,
+        message: vec![7; 33],
+        hashing: MessageHashing::Prehashed,
+    })
+    .unwrap_err();
+    assert_eq!(e, Error::InvalidMessageLength(33));
+}
+
+#[test]
+fn verify_against_a_local_key() {
+    let keys = keygen(1, 3, false);
+    let message = b"Hello world";
+    let hashing = MessageHashing::Sha256;
+    let signature = Quorum::new(&keys, &[2, 3]).sign(message, hashing).remove(0).signature;
+    let input = |public_key, local_key| Secp256k1VerifySignatureInput {
+        signature: signature.clone(),
+        public_key,
+        local_key,
+        message: message.to_vec(),
+        hashing,
+    };
+
+    let verified = verify_signature(&input(None, Some(Secret::new(keys[1].clone())))).unwrap();
+    assert!(verified.valid);
+    let other = keygen(1, 3, false).remove(0);
+    let verified = verify_signature(&input(None, Some(Secret::new(other)))).unwrap();
+    assert!(!verified.valid);
+
+    // Exactly one of the two keys
+    let both = input(Some(keys[0].y_sum_s.clone()), Some(Secret::new(keys[0].clone())));
+    assert!(matches!(verify_signature(&both), Err(Error::InvalidInput(_))));
+    assert!(matches!(verify_signature(&input(None, None)), Err(Error::InvalidInput(_))));
+}
@@ -0,0 +1,51 @@
+//! The wasm-bindgen exports: JSON in, JSON out, errors as thrown strings.
+
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use wasm_bindgen_test::*;
+
+use hello::wasm;
+use hello::*;
+
+#[wasm_bindgen_test]
+fn keygen_stage1_returns_json() {
+    let input = serde_json::to_string(&Secp256k1KeyGenStage1Input {
+        index: PartyIndex(1),
+        use_safe_prime: false,
+    })
+    .unwrap();
+    let output = wasm::secp256k1_keygen_stage1(input).unwrap();
+    let output: Secp256k1KeyGenStage1Output = secret::from_json(output).unwrap();
+    assert_eq!(output.key.party_index, 1);
+}
+
+#[wasm_bindgen_test]
+fn errors_are_thrown_as_strings() {
+    let e = wasm::secp256k1_keygen_stage1("not json".to_string()).unwrap_err();
+    assert!(e.as_string().is_some());
+
+    let input = serde_json::to_string(&Secp256k1KeyGenStage1Input {
+        index: PartyIndex(0),
+        use_safe_prime: false,
+    })
+    .unwrap();
+    let e = wasm::secp256k1_keygen_stage1(input).unwrap_err();
+    assert!(e.as_string().is_some());
+}
+
+#[wasm_bindgen_test]
+async fn async_exports_resolve_to_json() {
+    let input = serde_json::to_string(&Secp256k1KeyGenStage1Input {
+        index: PartyIndex(2),
+        use_safe_prime: false,
+    })
+    .unwrap();
+    let output = wasm::secp256k1_keygen_stage1_async(input).await.unwrap();
+    let output: Secp256k1KeyGenStage1Output = secret::from_json(output).unwrap();
+    assert_eq!(output.key.party_index, 2);
+
+    let e = wasm::secp256k1_keygen_stage1_async("not json".to_string())
+        .await
+        .unwrap_err();
+    assert!(e.as_string().is_some());
+}